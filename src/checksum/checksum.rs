@@ -1,23 +1,190 @@
+use serde::{Deserialize, Serialize};
+
+use sha2::{Digest, Sha256};
+
+use std::fmt::{Display, Formatter};
+
 const MOD_ADLER: u32 = 65521;
 
+/// the most bytes that can be summed before `b` could overflow a u32 and has to be reduced.
+const NMAX: usize = 5552;
+
 // adler u32 checksum. i saw the zlib implementation
 // and i am gonna have to print it out and look at wtf is happening
 pub fn checksum(data: &[u8]) -> u32 {
-    let mut a: u32 = 1;
-    let mut b: u32 = 0;
+    let mut adler = Adler32::new();
+
+    adler.update(data);
+
+    adler.finish()
+}
+
+/// a rolling adler-32 checksum that can be fed a file one chunk at a time.
+#[derive(Debug, Clone, Copy)]
+pub struct Adler32 {
+    a: u32,
+    b: u32,
+}
+
+impl Adler32 {
+    pub fn new() -> Self {
+        Adler32 { a: 1, b: 0 }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        // deferring the modulo until just before overflow is what makes zlib's version fast
+        for block in data.chunks(NMAX) {
+            for byte in block {
+                self.a += *byte as u32;
+                self.b += self.a;
+            }
 
-    for byte in data {
-        a = (a + *byte as u32) % MOD_ADLER;
-        b = (b + a) % MOD_ADLER;
+            self.a %= MOD_ADLER;
+            self.b %= MOD_ADLER;
+        }
     }
 
-    b << 16 | a
+    pub fn finish(&self) -> u32 {
+        self.b << 16 | self.a
+    }
+}
+
+impl Default for Adler32 {
+    fn default() -> Self {
+        Adler32::new()
+    }
+}
+
+pub fn sha256(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Adler32,
+    Sha256,
+}
+
+/// a checksum of a single file. adler-32 checksums are stored as numbers and sha-256 digests as hex
+/// strings, so a map written before sha-256 was supported deserializes unchanged.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum Checksum {
+    Adler32(u32),
+    Sha256(String),
+}
+
+/// computes a `Checksum` incrementally with either algorithm.
+pub enum Hasher {
+    Adler32(Adler32),
+    Sha256(Sha256),
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Adler32 => Hasher::Adler32(Adler32::new()),
+            Algorithm::Sha256 => Hasher::Sha256(Sha256::new()),
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Adler32(adler) => adler.update(data),
+            Hasher::Sha256(sha) => sha.update(data),
+        }
+    }
+
+    pub fn finish(self) -> Checksum {
+        match self {
+            Hasher::Adler32(adler) => Checksum::Adler32(adler.finish()),
+            Hasher::Sha256(sha) => Checksum::Sha256(format!("{:x}", sha.finalize())),
+        }
+    }
+}
+
+impl Checksum {
+    pub fn compute(algorithm: Algorithm, data: &[u8]) -> Self {
+        let mut hasher = Hasher::new(algorithm);
+
+        hasher.update(data);
+
+        hasher.finish()
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        match self {
+            Checksum::Adler32(_) => Algorithm::Adler32,
+            Checksum::Sha256(_) => Algorithm::Sha256,
+        }
+    }
+}
+
+impl Display for Checksum {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Checksum::Adler32(c) => write!(f, "adler32:{c:08x}"),
+            Checksum::Sha256(c) => write!(f, "sha256:{c}"),
+        }
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_sha256() {
+        assert_eq!(
+            sha256("Wikipedia".as_bytes()),
+            "d38b38a2dd476e045c299e8ee5d6466834456d97bd592a71746b423a6a05f386"
+        );
+    }
+
+    #[test]
+    fn test_rolling_checksums_match_whole() {
+        let data = (0..100_000u32)
+            .map(|i| (i * 31 % 251) as u8)
+            .collect::<Vec<_>>();
+
+        for algorithm in [Algorithm::Adler32, Algorithm::Sha256] {
+            let mut hasher = Hasher::new(algorithm);
+
+            for chunk in data.chunks(4096) {
+                hasher.update(chunk);
+            }
+
+            assert_eq!(hasher.finish(), Checksum::compute(algorithm, &data));
+        }
+    }
+
+    #[test]
+    fn test_adler_large_input() {
+        // 0xff bytes overflow a u32 quickly if the modulo is deferred too long
+        let data = vec![0xffu8; 1 << 20];
+
+        let mut a: u32 = 1;
+        let mut b: u32 = 0;
+
+        for byte in &data {
+            a = (a + *byte as u32) % MOD_ADLER;
+            b = (b + a) % MOD_ADLER;
+        }
+
+        assert_eq!(checksum(&data), b << 16 | a);
+    }
+
+    #[test]
+    fn test_checksum_serialization() {
+        let adler: Checksum = serde_json::from_str("300286872").unwrap();
+        assert_eq!(adler, Checksum::Adler32(0x11E60398));
+
+        let sha = Checksum::compute(Algorithm::Sha256, "Wikipedia".as_bytes());
+        let json = serde_json::to_string(&sha).unwrap();
+
+        assert_eq!(serde_json::from_str::<Checksum>(&json).unwrap(), sha);
+    }
+
     #[test]
     fn test_checksum() {
         let data = "Wikipedia".as_bytes();
@@ -1,110 +1,198 @@
+use crate::checksum::checksum::Checksum;
+
 use crate::format::path_manager::nba_checksum_file;
 
+use crate::storage;
+
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use std::collections::HashMap;
-use std::fs::{self, File};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::{Display, Formatter};
 use std::io;
 use std::path::PathBuf;
 
 use thiserror::Error;
 
+/// the current version of the checksum file. version 1 is the unversioned map of source paths to
+/// adler-32 checksums.
+pub const CHECKSUM_MAP_VERSION: u32 = 2;
+
+/// the kinds of files in the data directory that are covered by checksums.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum DataClass {
+    /// raw json downloaded from nba.com
+    Source,
+    /// the local edit list
+    Edits,
+    /// processed `.vol` files
+    Volumes,
+    /// the player and team directories built from the volumes
+    Directory,
+    /// the artifacts of trained model runs
+    Models,
+}
+
+impl DataClass {
+    pub const ALL: [DataClass; 5] = [
+        DataClass::Source,
+        DataClass::Edits,
+        DataClass::Volumes,
+        DataClass::Directory,
+        DataClass::Models,
+    ];
+}
+
+impl Display for DataClass {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            DataClass::Source => "source",
+            DataClass::Edits => "edits",
+            DataClass::Volumes => "volumes",
+            DataClass::Directory => "directory",
+            DataClass::Models => "models",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// checksums of every file in each data class, keyed by the file's path relative to the data (or
+/// models) directory.
 #[derive(Deserialize, Serialize, PartialEq, Eq, Debug, Clone)]
 pub struct ChecksumMap {
-    pub map: HashMap<PathBuf, u32>,
+    pub version: u32,
+    pub classes: BTreeMap<DataClass, BTreeMap<PathBuf, Checksum>>,
 }
 
 impl ChecksumMap {
     pub fn load() -> Result<Self, ChecksumMapError> {
         let path = nba_checksum_file();
 
-        let file = File::open(&path).map_err(|e| ChecksumMapError::ChecksumFileError(e))?;
+        let contents = storage::read(&path).map_err(ChecksumMapError::ChecksumFileError)?;
 
-        let map = serde_json::from_reader(file)
+        let json: Value = serde_json::from_slice(&contents)
             .map_err(|e| ChecksumMapError::ChecksumSerializerError(e))?;
 
-        Ok(ChecksumMap { map })
+        ChecksumMap::from_json(json)
+    }
+
+    /// parse either checksum file format. a map without a version is the original flat map of
+    /// source paths to adler-32 checksums.
+    pub fn from_json(json: Value) -> Result<Self, ChecksumMapError> {
+        match json.get("version").and_then(Value::as_u64) {
+            None => {
+                let legacy: HashMap<PathBuf, u32> = serde_json::from_value(json)
+                    .map_err(ChecksumMapError::ChecksumSerializerError)?;
+
+                let mut map = ChecksumMap::new();
+                map.version = 1;
+
+                for (path, checksum) in legacy {
+                    map.insert(DataClass::Source, path, Checksum::Adler32(checksum));
+                }
+
+                Ok(map)
+            }
+            Some(version) if version as u32 <= CHECKSUM_MAP_VERSION => {
+                serde_json::from_value(json).map_err(ChecksumMapError::ChecksumSerializerError)
+            }
+            Some(version) => Err(ChecksumMapError::UnsupportedVersion(version)),
+        }
     }
 
     pub fn new() -> Self {
         ChecksumMap {
-            map: HashMap::new(),
+            version: CHECKSUM_MAP_VERSION,
+            classes: BTreeMap::new(),
         }
     }
 
-    pub fn insert(&mut self, path: PathBuf, checksum: u32) {
-        self.map.insert(path, checksum);
+    pub fn insert(&mut self, class: DataClass, path: PathBuf, checksum: Checksum) {
+        self.classes
+            .entry(class)
+            .or_default()
+            .insert(path, checksum);
     }
 
-    pub fn get(&self, path: &PathBuf) -> Option<&u32> {
-        self.map.get(path)
+    /// record that every file of `class` is covered, even if it has none yet, so files added to it
+    /// later are reported as unrecorded.
+    pub fn cover(&mut self, class: DataClass) {
+        self.classes.entry(class).or_default();
     }
 
-    pub fn verify_checksums(&self, other: &ChecksumMap) -> Vec<PathBuf> {
-        let mut errors = Vec::new();
-
-        for path in self.map.keys() {
-            if let Some(self_checksum) = self.map.get(path) {
-                if let Some(other_checksum) = other.map.get(path) {
-                    if self_checksum != other_checksum {
-                        errors.push(path.clone());
-                    }
-                } else {
-                    errors.push(path.clone());
-                }
-            } else {
-                //if its in keys and its not in self then how can it possibly exist ?
-                panic!("💀 ChecksumMap::verify_checksums: domain not found in self");
-            }
-        }
+    /// whether the map records checksums for `class`. a version 1 map only covers source files.
+    pub fn covers(&self, class: DataClass) -> bool {
+        self.classes.contains_key(&class)
+    }
+
+    pub fn get(&self, class: DataClass, path: &PathBuf) -> Option<&Checksum> {
+        self.classes.get(&class).and_then(|files| files.get(path))
+    }
+
+    /// the checksums recorded for a single class of data.
+    pub fn class(&self, class: DataClass) -> Option<&BTreeMap<PathBuf, Checksum>> {
+        self.classes.get(&class)
+    }
 
-        for path in other.map.keys() {
-            if !self.map.contains_key(path) {
-                errors.push(path.clone());
+    /// replace every checksum of `class` with those in `other`.
+    pub fn replace_class(&mut self, class: DataClass, other: &ChecksumMap) {
+        match other.classes.get(&class) {
+            Some(files) => {
+                self.classes.insert(class, files.clone());
+            }
+            None => {
+                self.classes.remove(&class);
             }
         }
+    }
 
-        errors
+    pub fn verify_checksums(&self, other: &ChecksumMap) -> Vec<Mismatch> {
+        self.diff(other)
     }
 
     pub fn save(&self) -> Result<(), ChecksumMapError> {
         let path = nba_checksum_file();
 
-        let _ = fs::create_dir_all(
-            &path
-                .parent()
-                .expect("💀 static checksum path does not have a parent directory."),
-        )
-        .map_err(|e| ChecksumMapError::ChecksumFileError(e));
-
-        let file = File::create(&path).map_err(|e| ChecksumMapError::ChecksumFileError(e))?;
-
-        let serializable_map = self
-            .map
-            .iter()
-            .map(|(path, checksum)| (path.display().to_string(), *checksum))
-            .collect::<HashMap<String, u32>>();
+        let mut map = self.clone();
+        map.version = CHECKSUM_MAP_VERSION;
 
-        serde_json::to_writer_pretty(file, &serializable_map)
+        let contents = serde_json::to_vec_pretty(&map)
             .map_err(|e| ChecksumMapError::ChecksumSerializerError(e))?;
 
-        Ok(())
+        storage::write(&path, contents).map_err(ChecksumMapError::ChecksumFileError)
     }
 
-    pub fn diff(&self, other: &ChecksumMap) -> Vec<PathBuf> {
+    /// every file whose checksum differs between `self` (expected) and `other` (actual). only the
+    /// classes `self` covers are compared, so an older map isn't failed for files it never recorded.
+    pub fn diff(&self, other: &ChecksumMap) -> Vec<Mismatch> {
+        let empty = BTreeMap::new();
         let mut diffs = Vec::new();
 
-        // Check all keys in self
-        for (k, v) in self.map.iter() {
-            if other.get(k) != Some(v) {
-                diffs.push(k.clone());
+        for (&class, expected) in &self.classes {
+            let actual = other.classes.get(&class).unwrap_or(&empty);
+
+            for (path, checksum) in expected {
+                let kind = match actual.get(path) {
+                    Some(c) if c == checksum => continue,
+                    Some(_) => MismatchKind::Changed,
+                    None => MismatchKind::Missing,
+                };
+
+                diffs.push(Mismatch {
+                    class,
+                    path: path.clone(),
+                    kind,
+                });
             }
-        }
 
-        // Check for keys that exist in other but not in self
-        for k in other.map.keys() {
-            if !self.map.contains_key(k) {
-                diffs.push(k.clone());
+            for path in actual.keys().filter(|path| !expected.contains_key(*path)) {
+                diffs.push(Mismatch {
+                    class,
+                    path: path.clone(),
+                    kind: MismatchKind::Unrecorded,
+                });
             }
         }
 
@@ -112,10 +200,42 @@ impl ChecksumMap {
     }
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MismatchKind {
+    /// the file's contents no longer match its checksum
+    Changed,
+    /// the file has a checksum but no longer exists
+    Missing,
+    /// the file exists but has no checksum
+    Unrecorded,
+}
+
+/// a single file that failed verification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub class: DataClass,
+    pub path: PathBuf,
+    pub kind: MismatchKind,
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            MismatchKind::Changed => "changed",
+            MismatchKind::Missing => "missing",
+            MismatchKind::Unrecorded => "no checksum",
+        };
+
+        write!(f, "📄 [{}] {} ({kind})", self.class, self.path.display())
+    }
+}
+
 #[derive(Error, Debug)]
 pub enum ChecksumMapError {
     #[error("❌ failed to create or read checksum file. ")]
     ChecksumFileError(io::Error),
     #[error("❌ failed to serialize checksums with serde_json formatter. ")]
     ChecksumSerializerError(serde_json::Error),
+    #[error("❌ checksum file version {0} is newer than this version of warheads supports. ")]
+    UnsupportedVersion(u64),
 }
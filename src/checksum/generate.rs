@@ -1,42 +1,208 @@
-use crate::checksum::checksum_map::ChecksumMap;
-use crate::checksum::read_checksum::read_checksum;
+use crate::checksum::checksum::Algorithm;
+use crate::checksum::checksum_map::{ChecksumMap, DataClass};
+use crate::checksum::read_checksum::read_checksum_as;
 
 use crate::dapi::season_manager::nba_lifespan_period;
-use crate::format::path_manager::{nba_source_path, universal_nba_source_path};
+use crate::format::path_manager::{
+    models_root, nba_edit_file, nba_player_directory_file, nba_source_path, nba_storage_path,
+    nba_team_directory_file, universal_nba_edit_file, universal_nba_player_directory_file,
+    universal_nba_source_path, universal_nba_storage_path, universal_nba_team_directory_file,
+};
 
 use crate::stats::nba_kind::NBAStatKind;
 
+use crate::storage;
+
+use crate::tui::progress::Progress;
+
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
+
+/// directories in the models directory that hold data rather than models. by default models are
+/// stored next to the source files, volumes and directories in `data/nba`.
+const NON_MODEL_DIRS: [&str; 3] = ["source", "volumes", "directory"];
+
+/// the directory of a model's runs. only run artifacts are checksummed, not the model's saved
+/// forecasts or which run is promoted.
+const RUNS_DIR: &str = "runs";
+
+/// sha-256 checksums of every file in every data class.
 pub fn generate_checksums() -> ChecksumMap {
-    let mut checksums: ChecksumMap = ChecksumMap::new();
+    generate_checksums_like(&ChecksumMap::new())
+}
+
+/// checksums of every file in every data class, using the algorithm each file was recorded with in
+/// `reference` so that adler-32 entries from an older map can still be verified. files that
+/// `reference` doesn't know about get sha-256 checksums.
+pub fn generate_checksums_like(reference: &ChecksumMap) -> ChecksumMap {
+    let mut checksums = ChecksumMap::new();
+
+    for class in DataClass::ALL {
+        generate_class_checksums(class, reference, &mut checksums);
+    }
+
+    checksums
+}
 
-    for era in nba_lifespan_period() {
-        //team
-        let team_path = nba_source_path(era, NBAStatKind::Team);
+/// add the checksums of every file in `class` to `checksums`.
+pub fn generate_class_checksums(
+    class: DataClass,
+    reference: &ChecksumMap,
+    checksums: &mut ChecksumMap,
+) {
+    let jobs = checksum_jobs(class, reference);
 
-        let team_display_path = universal_nba_source_path(era, NBAStatKind::Team);
+    checksums.cover(class);
 
-        let team_checksum = read_checksum(&team_path);
+    let progress = Progress::new(jobs.len() as u64, &format!("🔐 checksumming {class}"));
 
-        if let Ok(checksum) = team_checksum {
-            checksums.insert(team_display_path, checksum);
-        } else {
-            eprintln!("❌ tried to verify team checksum for the {era} but couldn't read data file.")
+    for (display_path, path, algorithm) in jobs {
+        match read_checksum_as(&path, algorithm) {
+            Ok(checksum) => checksums.insert(class, display_path, checksum),
+            Err(_) => unreadable(class, &display_path),
         }
 
-        // player
-        let player_path = nba_source_path(era, NBAStatKind::Player);
+        progress.inc(1);
+    }
+}
+
+/// the same as `generate_checksums_like`, but every file is hashed on tokio's blocking pool, as
+/// many at a time as there are cores, with a progress bar.
+pub async fn generate_checksums_parallel(reference: &ChecksumMap) -> ChecksumMap {
+    let jobs = DataClass::ALL
+        .into_iter()
+        .flat_map(|class| {
+            checksum_jobs(class, reference)
+                .into_iter()
+                .map(move |(display_path, path, algorithm)| (class, display_path, path, algorithm))
+        })
+        .collect::<Vec<_>>();
+
+    let progress = Progress::new(jobs.len() as u64, "🔐 checksumming");
 
-        let player_display_path = universal_nba_source_path(era, NBAStatKind::Player);
+    let permits = std::thread::available_parallelism().map_or(4, |n| n.get());
+    let semaphore = Arc::new(Semaphore::new(permits));
 
-        let player_checksum = read_checksum(&player_path);
+    let mut tasks = JoinSet::new();
+
+    for (class, display_path, path, algorithm) in jobs {
+        let semaphore = semaphore.clone();
+
+        tasks.spawn(async move {
+            let _permit = semaphore
+                .acquire_owned()
+                .await
+                .expect("💀 checksum semaphore closed");
+
+            let checksum =
+                tokio::task::spawn_blocking(move || read_checksum_as(&path, algorithm)).await;
+
+            (class, display_path, checksum)
+        });
+    }
 
-        if let Ok(checksum) = player_checksum {
-            checksums.insert(player_display_path, checksum);
-        } else {
-            eprintln!(
-                "❌ tried to verify player checksum for the {era} but couldn't read data file."
-            )
+    let mut checksums = ChecksumMap::new();
+
+    for class in DataClass::ALL {
+        checksums.cover(class);
+    }
+
+    while let Some(result) = tasks.join_next().await {
+        progress.inc(1);
+
+        match result {
+            Ok((class, display_path, Ok(Ok(checksum)))) => {
+                checksums.insert(class, display_path, checksum)
+            }
+            Ok((class, display_path, _)) => unreadable(class, &display_path),
+            Err(e) => log::error!("{e}\n❌ checksum task failed"),
         }
     }
+
+    progress.finish();
+
     checksums
 }
+
+/// the files in `class` paired with the algorithm `reference` recorded them with.
+fn checksum_jobs(class: DataClass, reference: &ChecksumMap) -> Vec<(PathBuf, PathBuf, Algorithm)> {
+    data_files(class)
+        .into_iter()
+        .map(|(display_path, path)| {
+            let algorithm = reference
+                .get(class, &display_path)
+                .map_or(Algorithm::Sha256, |c| c.algorithm());
+
+            (display_path, path, algorithm)
+        })
+        .collect()
+}
+
+fn unreadable(class: DataClass, display_path: &Path) {
+    if class == DataClass::Source {
+        log::error!(
+            "❌ tried to verify checksum for {} but couldn't read data file.",
+            display_path.display()
+        )
+    }
+}
+
+/// every file in a data class as the path its checksum is recorded under and the path it is
+/// stored at.
+pub fn data_files(class: DataClass) -> Vec<(PathBuf, PathBuf)> {
+    match class {
+        DataClass::Source => nba_lifespan_period()
+            .into_iter()
+            .flat_map(|era| {
+                [NBAStatKind::Team, NBAStatKind::Player].map(|kind| {
+                    (
+                        universal_nba_source_path(era, kind),
+                        nba_source_path(era, kind),
+                    )
+                })
+            })
+            .collect(),
+        DataClass::Edits => vec![(universal_nba_edit_file(), nba_edit_file())],
+        DataClass::Volumes => nba_lifespan_period()
+            .into_iter()
+            .map(|era| (universal_nba_storage_path(era), nba_storage_path(era)))
+            .filter(|(_, path)| storage::exists(path))
+            .collect(),
+        DataClass::Directory => vec![
+            (
+                universal_nba_player_directory_file(),
+                nba_player_directory_file(),
+            ),
+            (
+                universal_nba_team_directory_file(),
+                nba_team_directory_file(),
+            ),
+        ]
+        .into_iter()
+        .filter(|(_, path)| storage::exists(path))
+        .collect(),
+        DataClass::Models => {
+            let root = models_root();
+
+            storage::list(&root)
+                .unwrap_or_default()
+                .into_iter()
+                .filter_map(|path| {
+                    let display_path = path.strip_prefix(&root).ok()?.to_path_buf();
+
+                    let mut parts = display_path.components().map(|c| c.as_os_str());
+
+                    let model = parts.next()?;
+
+                    let is_data = NON_MODEL_DIRS.iter().any(|d| model == *d);
+                    let is_run = parts.next().is_some_and(|dir| dir == RUNS_DIR);
+
+                    (is_run && !is_data).then_some((display_path, path))
+                })
+                .collect()
+        }
+    }
+}
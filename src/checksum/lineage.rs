@@ -0,0 +1,167 @@
+use crate::checksum::checksum::{Algorithm, Checksum};
+use crate::checksum::read_checksum::read_checksum;
+
+use crate::edit::edit_list::EditList;
+
+use crate::format::path_manager::{nba_lineage_path, nba_source_path, universal_nba_source_path};
+
+use crate::stats::nba_kind::NBAStatKind;
+
+use crate::storage;
+
+use crate::types::SeasonId;
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use std::collections::BTreeMap;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+pub const WARHEADS_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// ## Lineage
+///
+/// the inputs a volume was built from. it is written next to the volume by `inscribe` so that
+/// `chronicle_nba` can tell when a volume is out of date with its source files or the edit list.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct Lineage {
+    pub season: SeasonId,
+    /// checksums of the team and player source files.
+    pub sources: BTreeMap<PathBuf, Checksum>,
+    /// a checksum of the edits for this season, independent of their order and provenance.
+    pub edits: Checksum,
+    pub warheads_version: String,
+}
+
+impl Lineage {
+    /// the lineage a volume for `season` would have if it were inscribed now.
+    pub fn current(season: SeasonId, edits: &EditList) -> Result<Self, LineageError> {
+        let mut sources = BTreeMap::new();
+
+        for kind in [NBAStatKind::Team, NBAStatKind::Player] {
+            let path = nba_source_path(season, kind);
+
+            let checksum =
+                read_checksum(&path).map_err(|e| LineageError::SourceReadError(path, e))?;
+
+            sources.insert(universal_nba_source_path(season, kind), checksum);
+        }
+
+        Ok(Lineage {
+            season,
+            sources,
+            edits: season_edits_checksum(season, edits),
+            warheads_version: WARHEADS_VERSION.to_owned(),
+        })
+    }
+
+    pub fn load(season: SeasonId) -> Result<Self, LineageError> {
+        let path = nba_lineage_path(season);
+
+        let contents =
+            storage::read_to_string(&path).map_err(|e| LineageError::ReadError(path.clone(), e))?;
+
+        serde_json::from_str(&contents).map_err(|e| LineageError::ParseError(path, e))
+    }
+
+    pub fn save(&self) -> Result<(), LineageError> {
+        let path = nba_lineage_path(self.season);
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| LineageError::ParseError(path.clone(), e))?;
+
+        storage::write(&path, contents).map_err(|e| LineageError::WriteError(path, e))
+    }
+
+    /// every input that differs between this (recorded) lineage and `current`.
+    pub fn stale_inputs(&self, current: &Lineage) -> Vec<StaleInput> {
+        let mut stale = Vec::new();
+
+        for (path, checksum) in &current.sources {
+            if self.sources.get(path) != Some(checksum) {
+                stale.push(StaleInput::Source(path.clone()));
+            }
+        }
+
+        if self.edits != current.edits {
+            stale.push(StaleInput::Edits);
+        }
+
+        if self.warheads_version != current.warheads_version {
+            stale.push(StaleInput::Version(self.warheads_version.clone()));
+        }
+
+        stale
+    }
+}
+
+/// why a volume needs to be rebuilt.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StaleInput {
+    /// the volume doesn't exist
+    MissingVolume,
+    /// the volume was built before lineage was recorded
+    MissingLineage,
+    /// the volume was written in an older format or schema and has to be regenerated
+    OutdatedFormat,
+    /// a source file changed
+    Source(PathBuf),
+    /// the edits for the season changed
+    Edits,
+    /// the volume was built by a different version of warheads
+    Version(String),
+}
+
+impl Display for StaleInput {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StaleInput::MissingVolume => write!(f, "volume does not exist"),
+            StaleInput::MissingLineage => write!(f, "no lineage recorded"),
+            StaleInput::OutdatedFormat => write!(f, "volume format or schema is out of date"),
+            StaleInput::Source(path) => write!(f, "source changed: {}", path.display()),
+            StaleInput::Edits => write!(f, "edits changed"),
+            StaleInput::Version(v) => {
+                write!(f, "built by warheads {v}, current is {WARHEADS_VERSION}")
+            }
+        }
+    }
+}
+
+/// a checksum of the edits that apply to `season`. only the fields that change the volume are
+/// hashed, and in a fixed order, so re-sorting or re-attributing edits doesn't mark volumes stale.
+pub fn season_edits_checksum(season: SeasonId, edits: &EditList) -> Checksum {
+    let mut lines = edits
+        .list()
+        .iter()
+        .filter(|edit| edit.season == season)
+        .map(|edit| {
+            json!({
+                "game_id": edit.game_id,
+                "team_id": edit.team_id,
+                "player_id": edit.player_id,
+                "delete": edit.delete,
+                "corrections": edit.corrections.iter().collect::<BTreeMap<_, _>>(),
+            })
+            .to_string()
+        })
+        .collect::<Vec<_>>();
+
+    lines.sort();
+
+    Checksum::compute(Algorithm::Sha256, lines.join("\n").as_bytes())
+}
+
+#[derive(Debug, Error)]
+pub enum LineageError {
+    #[error("{1}\n❌ failed to read source file {path}", path = .0.display())]
+    SourceReadError(PathBuf, std::io::Error),
+    #[error("{1}\n❌ failed to read lineage file {path}", path = .0.display())]
+    ReadError(PathBuf, std::io::Error),
+    #[error("{1}\n❌ failed to write lineage file {path}", path = .0.display())]
+    WriteError(PathBuf, std::io::Error),
+    #[error("{1}\n❌ failed to parse lineage file {path}", path = .0.display())]
+    ParseError(PathBuf, serde_json::Error),
+}
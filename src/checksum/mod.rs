@@ -4,6 +4,8 @@ pub mod checksum_map;
 
 pub mod generate;
 
+pub mod lineage;
+
 pub mod sign;
 
 mod tests;
@@ -1,10 +1,35 @@
-use crate::checksum::checksum::checksum;
-use std::path::PathBuf;
+use crate::checksum::checksum::{Algorithm, Checksum, Hasher};
 
-pub fn read_checksum(path: &PathBuf) -> Result<u32, std::io::Error> {
-    let data = std::fs::read(path)?;
+use crate::storage;
 
-    let checksum = checksum(&data);
+use std::path::Path;
 
-    Ok(checksum)
+/// files are hashed this many bytes at a time so that large volumes are never read into memory at
+/// once.
+const CHUNK_SIZE: usize = 64 * 1024;
+
+/// the sha-256 checksum of the file at `path`. new checksums are always sha-256.
+pub fn read_checksum(path: &Path) -> Result<Checksum, std::io::Error> {
+    read_checksum_as(path, Algorithm::Sha256)
+}
+
+/// the checksum of the file at `path` using the same algorithm it was recorded with. files under the
+/// data root are read from the configured storage.
+pub fn read_checksum_as(path: &Path, algorithm: Algorithm) -> Result<Checksum, std::io::Error> {
+    let mut file = storage::open(path)?;
+
+    let mut hasher = Hasher::new(algorithm);
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+
+    loop {
+        let n = file.read(&mut buffer)?;
+
+        if n == 0 {
+            break;
+        }
+
+        hasher.update(&buffer[..n]);
+    }
+
+    Ok(hasher.finish())
 }
@@ -1,7 +1,47 @@
-use crate::checksum::generate::generate_checksums;
+use crate::checksum::checksum_map::{ChecksumMap, ChecksumMapError, DataClass};
+use crate::checksum::generate::generate_class_checksums;
+use crate::checksum::read_checksum::read_checksum;
 
+use std::path::PathBuf;
+
+/// re-sign the nba source files.
 pub fn sign_nba() -> Result<(), ()> {
-    let checksums = generate_checksums();
+    sign(&[DataClass::Source]).map_err(|_| ())
+}
+
+/// replace the checksums of `classes` with fresh sha-256 checksums, leaving the other classes as
+/// they were so that unrelated changes are still caught by `checksums verify`.
+pub fn sign(classes: &[DataClass]) -> Result<(), ChecksumMapError> {
+    let mut checksums = ChecksumMap::load().unwrap_or_else(|_| ChecksumMap::new());
+
+    let mut signed = ChecksumMap::new();
+
+    for &class in classes {
+        generate_class_checksums(class, &ChecksumMap::new(), &mut signed);
+        checksums.replace_class(class, &signed);
+    }
+
+    checksums.save()
+}
+
+/// replace the checksum of a single file of `class`, e.g. a volume upgraded when it was read. a
+/// checksum file that doesn't cover `class` is left as it is.
+pub fn sign_file(
+    class: DataClass,
+    display_path: PathBuf,
+    path: &PathBuf,
+) -> Result<(), ChecksumMapError> {
+    let Ok(mut checksums) = ChecksumMap::load() else {
+        return Ok(());
+    };
+
+    if !checksums.covers(class) {
+        return Ok(());
+    }
+
+    let checksum = read_checksum(path).map_err(ChecksumMapError::ChecksumFileError)?;
+
+    checksums.insert(class, display_path, checksum);
 
-    checksums.save().map_err(|_| ())
+    checksums.save()
 }
@@ -29,14 +29,256 @@ mod test_save_load_verify {
 /// it can be run ahead of runtime to verify accuracy of starting data.
 #[cfg(test)]
 mod assert_checksums {
-    use crate::checksum::{checksum_map::ChecksumMap, generate::generate_checksums};
+    use crate::checksum::{checksum_map::ChecksumMap, generate::generate_checksums_like};
 
     #[test]
     fn test_assert_checksums() {
         let expected_map = ChecksumMap::load().expect("failed to load checksum from checksum file. check that data has been initialized properly");
 
-        let actual_map = generate_checksums();
+        let actual_map = generate_checksums_like(&expected_map);
+
+        pretty_assertions::assert_eq!(expected_map.diff(&actual_map), Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod test_checksum_map {
+    use crate::checksum::checksum::{Algorithm, Checksum};
+    use crate::checksum::checksum_map::{
+        ChecksumMap, DataClass, Mismatch, MismatchKind, CHECKSUM_MAP_VERSION,
+    };
+
+    use serde_json::json;
+
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_load_legacy_adler_map() {
+        let legacy = json!({
+            "nba/source/teams/2024_25/regularseason_teams.json": 300286872u32,
+        });
+
+        let map = ChecksumMap::from_json(legacy).unwrap();
+
+        assert_eq!(map.version, 1);
+        assert_eq!(
+            map.get(
+                DataClass::Source,
+                &PathBuf::from("nba/source/teams/2024_25/regularseason_teams.json")
+            ),
+            Some(&Checksum::Adler32(300286872))
+        );
+    }
+
+    #[test]
+    fn test_versioned_round_trip() {
+        let mut map = ChecksumMap::new();
+
+        map.insert(
+            DataClass::Volumes,
+            PathBuf::from("nba/volumes/2024_regularseason.vol"),
+            Checksum::compute(Algorithm::Sha256, b"volume"),
+        );
+        map.insert(
+            DataClass::Source,
+            PathBuf::from("nba/source/teams/2024_25/regularseason_teams.json"),
+            Checksum::compute(Algorithm::Adler32, b"source"),
+        );
+
+        let json = serde_json::to_value(&map).unwrap();
+
+        assert_eq!(json["version"], CHECKSUM_MAP_VERSION);
+        assert_eq!(ChecksumMap::from_json(json).unwrap(), map);
+    }
+
+    #[test]
+    fn test_unsupported_version() {
+        let json = json!({ "version": CHECKSUM_MAP_VERSION + 1, "classes": {} });
+
+        assert!(ChecksumMap::from_json(json).is_err());
+    }
+
+    #[test]
+    fn test_diff_reports_class_and_file() {
+        let changed = PathBuf::from("nba/edits.json");
+        let missing = PathBuf::from("nba/volumes/2024_regularseason.vol");
+        let unrecorded = PathBuf::from("elo/records.csv");
+
+        let mut expected = ChecksumMap::new();
+        expected.cover(DataClass::Models);
+        expected.insert(
+            DataClass::Edits,
+            changed.clone(),
+            Checksum::compute(Algorithm::Sha256, b"old"),
+        );
+        expected.insert(
+            DataClass::Volumes,
+            missing.clone(),
+            Checksum::compute(Algorithm::Sha256, b"volume"),
+        );
+
+        let mut actual = ChecksumMap::new();
+        actual.insert(
+            DataClass::Edits,
+            changed.clone(),
+            Checksum::compute(Algorithm::Sha256, b"new"),
+        );
+        actual.insert(
+            DataClass::Models,
+            unrecorded.clone(),
+            Checksum::compute(Algorithm::Sha256, b"model"),
+        );
+
+        assert_eq!(
+            expected.diff(&actual),
+            vec![
+                Mismatch {
+                    class: DataClass::Edits,
+                    path: changed,
+                    kind: MismatchKind::Changed
+                },
+                Mismatch {
+                    class: DataClass::Volumes,
+                    path: missing,
+                    kind: MismatchKind::Missing
+                },
+                Mismatch {
+                    class: DataClass::Models,
+                    path: unrecorded,
+                    kind: MismatchKind::Unrecorded
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_legacy_map_only_diffs_source() {
+        let source = PathBuf::from("nba/source/teams/2024_25/regularseason_teams.json");
+
+        let legacy = ChecksumMap::from_json(json!({
+            source.to_str().unwrap(): 300286872u32,
+        }))
+        .unwrap();
+
+        let mut actual = ChecksumMap::new();
+        actual.insert(DataClass::Source, source, Checksum::Adler32(300286872));
+        actual.insert(
+            DataClass::Edits,
+            PathBuf::from("nba/edits.json"),
+            Checksum::compute(Algorithm::Sha256, b"edits"),
+        );
+
+        assert!(!legacy.covers(DataClass::Edits));
+        assert_eq!(legacy.diff(&actual), Vec::new());
+    }
+}
+
+#[cfg(test)]
+mod test_parallel_checksums {
+    use crate::checksum::checksum_map::ChecksumMap;
+    use crate::checksum::generate::{generate_checksums, generate_checksums_parallel};
+
+    #[tokio::test]
+    async fn test_parallel_matches_serial() {
+        let parallel = generate_checksums_parallel(&ChecksumMap::new()).await;
+
+        pretty_assertions::assert_eq!(parallel, generate_checksums());
+    }
+}
+
+#[cfg(test)]
+mod test_lineage {
+    use crate::checksum::checksum::{Algorithm, Checksum};
+    use crate::checksum::lineage::{season_edits_checksum, Lineage, StaleInput, WARHEADS_VERSION};
+
+    use crate::edit::edit::Edit;
+    use crate::edit::edit_list::EditList;
+    use crate::edit::provenance::Provenance;
+
+    use crate::stats::stat_column::StatColumn;
+
+    use crate::types::{GameDate, GameId, SeasonId, TeamAbbreviation, TeamId};
+
+    use serde_json::Value;
+
+    use std::collections::{BTreeMap, HashMap};
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    fn edit(game_id: u64, season: i32, pts: i32) -> Edit {
+        Edit {
+            game_id: GameId(game_id),
+            game_date: GameDate::ymd(2022, 1, 15).expect("Failed to create GameDate"),
+            season: SeasonId::from(season),
+            player_id: None,
+            team_id: TeamId(1),
+            team_abbr: TeamAbbreviation::from_str("LAL")
+                .expect("Failed to create TeamAbbreviation"),
+            delete: false,
+            corrections: HashMap::from([
+                (StatColumn::PTS, Value::from(pts)),
+                (StatColumn::REB, Value::from(45)),
+            ]),
+            provenance: Vec::new(),
+        }
+    }
+
+    fn lineage(source: &[u8], edits: Checksum) -> Lineage {
+        Lineage {
+            season: SeasonId::from(22021),
+            sources: BTreeMap::from([(
+                PathBuf::from("nba/source/teams/2021_22/regularseason_teams.json"),
+                Checksum::compute(Algorithm::Sha256, source),
+            )]),
+            edits,
+            warheads_version: WARHEADS_VERSION.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_edits_checksum_ignores_order_provenance_and_other_seasons() {
+        let season = SeasonId::from(22021);
+
+        let a = EditList::new(vec![edit(1, 22021, 110), edit(2, 22021, 120)]);
+
+        let mut reattributed = edit(1, 22021, 110);
+        reattributed.provenance = vec![Provenance::local()];
+
+        let b = EditList::new(vec![edit(2, 22021, 120), edit(3, 22022, 99), reattributed]);
+
+        assert_eq!(
+            season_edits_checksum(season, &a),
+            season_edits_checksum(season, &b)
+        );
+
+        let c = EditList::new(vec![edit(1, 22021, 111), edit(2, 22021, 120)]);
+
+        assert_ne!(
+            season_edits_checksum(season, &a),
+            season_edits_checksum(season, &c)
+        );
+    }
+
+    #[test]
+    fn test_stale_inputs() {
+        let edits = Checksum::compute(Algorithm::Sha256, b"edits");
+
+        let recorded = lineage(b"source", edits.clone());
+
+        assert_eq!(recorded.stale_inputs(&recorded.clone()), Vec::new());
+
+        let mut current = lineage(b"new source", Checksum::compute(Algorithm::Sha256, b"new"));
+        current.warheads_version = "0.0.0".to_owned();
 
-        pretty_assertions::assert_eq!(expected_map, actual_map);
+        assert_eq!(
+            recorded.stale_inputs(&current),
+            vec![
+                StaleInput::Source(PathBuf::from(
+                    "nba/source/teams/2021_22/regularseason_teams.json"
+                )),
+                StaleInput::Edits,
+                StaleInput::Version(WARHEADS_VERSION.to_owned()),
+            ]
+        );
     }
 }
@@ -0,0 +1,478 @@
+use once_cell::sync::OnceCell;
+
+use serde::Deserialize;
+
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use thiserror::Error;
+
+/// environment variables that override the values in `warheads.toml`.
+pub const DATA_VAR: &str = "WARHEADS_DATA";
+pub const HEADERS_VAR: &str = "WARHEADS_HEADERS";
+pub const MODELS_VAR: &str = "WARHEADS_MODELS";
+pub const CACHE_VAR: &str = "WARHEADS_CACHE";
+pub const CONFIG_VAR: &str = "WARHEADS_CONFIG";
+pub const STORAGE_VAR: &str = "WARHEADS_STORAGE";
+pub const S3_ENDPOINT_VAR: &str = "WARHEADS_S3_ENDPOINT";
+pub const S3_BUCKET_VAR: &str = "WARHEADS_S3_BUCKET";
+pub const S3_REGION_VAR: &str = "WARHEADS_S3_REGION";
+pub const S3_PREFIX_VAR: &str = "WARHEADS_S3_PREFIX";
+
+/// the data root variable the README has always told users to put in `.env`.
+pub const LEGACY_DATA_VAR: &str = "DATA";
+
+pub const CONFIG_FILE: &str = "warheads.toml";
+
+pub const HEADERS_FILE: &str = "headers.json";
+
+static CONFIG: OnceCell<Config> = OnceCell::new();
+
+/// ## Config
+///
+/// the locations warheads reads from and writes to. each path is resolved from, in order of
+/// precedence:
+///
+/// 1. command line flags (`--data`, `--headers`, `--models`, `--cache`)
+/// 2. environment variables (`WARHEADS_DATA`, ...), including those set in `./.env`
+/// 3. `warheads.toml`, either `--config`, `$WARHEADS_CONFIG`, `./warheads.toml` or
+///    `~/.config/warheads/warheads.toml`
+/// 4. defaults relative to the data root. the headers file defaults to `headers.json` next to
+///    `warheads.toml`, or in the data root when there is no config file
+///
+/// volumes, lineages and other data files are read from and written to `storage`, which is either
+/// the data root itself or an s3 compatible bucket (e.g. the minio started by `services/start_s3`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// root of the source files, edits, volumes and checksums.
+    pub data: PathBuf,
+    /// the json file of http headers used for requests to the nba.
+    pub headers: PathBuf,
+    /// the directory each model keeps its records, results and predictions in.
+    pub models: PathBuf,
+    /// scratch space for downloaded and intermediate files.
+    pub cache: PathBuf,
+    /// where data files are stored.
+    pub storage: StorageKind,
+    /// the bucket data files are stored in when `storage` is `s3`.
+    pub s3: S3Config,
+}
+
+/// the backend data files are stored in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StorageKind {
+    /// files under the data root.
+    #[default]
+    Local,
+    /// objects in an s3 compatible bucket, keyed by their path under the data root.
+    S3,
+}
+
+impl FromStr for StorageKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "local" => Ok(StorageKind::Local),
+            "s3" => Ok(StorageKind::S3),
+            _ => Err(format!(
+                "{s} is not a storage backend. expected local or s3"
+            )),
+        }
+    }
+}
+
+impl Display for StorageKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StorageKind::Local => write!(f, "local"),
+            StorageKind::S3 => write!(f, "s3"),
+        }
+    }
+}
+
+/// the location of the s3 bucket. credentials are never read from config files, only from
+/// `WARHEADS_S3_ACCESS_KEY`/`WARHEADS_S3_SECRET_KEY`, `AWS_ACCESS_KEY_ID`/`AWS_SECRET_ACCESS_KEY`
+/// or the `MINIO_ROOT_USER`/`MINIO_ROOT_PASSWORD` used by `services/start_s3`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct S3Config {
+    pub endpoint: String,
+    pub bucket: Option<String>,
+    pub region: String,
+    /// prepended to every key, so several data roots can share a bucket.
+    pub prefix: String,
+}
+
+/// the `[s3]` table of a config layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct S3Layer {
+    pub endpoint: Option<String>,
+    pub bucket: Option<String>,
+    pub region: Option<String>,
+    pub prefix: Option<String>,
+}
+
+/// one source of configuration. any field left as `None` falls through to the next layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigLayer {
+    pub data: Option<PathBuf>,
+    pub headers: Option<PathBuf>,
+    pub models: Option<PathBuf>,
+    pub cache: Option<PathBuf>,
+    pub storage: Option<StorageKind>,
+    #[serde(default)]
+    pub s3: S3Layer,
+    /// the directory of the config file this layer was read from.
+    #[serde(skip)]
+    pub dir: Option<PathBuf>,
+}
+
+impl ConfigLayer {
+    pub fn from_env() -> Self {
+        let var = |name: &str| {
+            std::env::var_os(name)
+                .filter(|v| !v.is_empty())
+                .map(PathBuf::from)
+        };
+
+        let string = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        let storage = string(STORAGE_VAR).and_then(|v| match v.parse() {
+            Ok(kind) => Some(kind),
+            Err(e) => {
+                log::warn!("⚠️ ignoring {STORAGE_VAR}: {e}");
+                None
+            }
+        });
+
+        ConfigLayer {
+            data: var(DATA_VAR).or_else(|| var(LEGACY_DATA_VAR)),
+            headers: var(HEADERS_VAR),
+            models: var(MODELS_VAR),
+            cache: var(CACHE_VAR),
+            storage,
+            s3: S3Layer {
+                endpoint: string(S3_ENDPOINT_VAR),
+                bucket: string(S3_BUCKET_VAR),
+                region: string(S3_REGION_VAR),
+                prefix: string(S3_PREFIX_VAR),
+            },
+            dir: None,
+        }
+    }
+
+    /// parse a `warheads.toml`. relative paths are taken relative to the directory containing the
+    /// file, so a config checked in next to the data works from anywhere.
+    pub fn from_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = std::fs::read_to_string(path)
+            .map_err(|e| ConfigError::ConfigReadError(path.to_path_buf(), e))?;
+
+        let layer: ConfigLayer = toml::from_str(&contents)
+            .map_err(|e| ConfigError::ConfigParseError(path.to_path_buf(), e))?;
+
+        let base = path.parent().unwrap_or(Path::new(""));
+        let anchor = |p: Option<PathBuf>| p.map(|p| base.join(p));
+
+        Ok(ConfigLayer {
+            data: anchor(layer.data),
+            headers: anchor(layer.headers),
+            models: anchor(layer.models),
+            cache: anchor(layer.cache),
+            dir: Some(base.to_path_buf()),
+            ..layer
+        })
+    }
+}
+
+impl Config {
+    /// resolve each path from the first layer that sets it, falling back to the defaults.
+    pub fn resolve(layers: &[&ConfigLayer]) -> Self {
+        let pick = |f: fn(&ConfigLayer) -> &Option<PathBuf>| {
+            layers.iter().find_map(|layer| f(layer).clone())
+        };
+
+        let pick_s3 = |f: fn(&S3Layer) -> &Option<String>| {
+            layers.iter().find_map(|layer| f(&layer.s3).clone())
+        };
+
+        let data = pick(|l| &l.data).unwrap_or_else(default_data);
+
+        let headers = pick(|l| &l.headers).unwrap_or_else(|| {
+            layers
+                .iter()
+                .find_map(|layer| layer.dir.clone())
+                .unwrap_or_else(|| data.clone())
+                .join(HEADERS_FILE)
+        });
+
+        Config {
+            headers,
+            models: pick(|l| &l.models).unwrap_or_else(|| data.join("nba")),
+            cache: pick(|l| &l.cache).unwrap_or_else(|| data.join("cache")),
+            data,
+            storage: layers
+                .iter()
+                .find_map(|layer| layer.storage)
+                .unwrap_or_default(),
+            s3: S3Config {
+                endpoint: pick_s3(|s| &s.endpoint)
+                    .unwrap_or_else(|| "http://localhost:9000".to_string()),
+                bucket: pick_s3(|s| &s.bucket),
+                region: pick_s3(|s| &s.region).unwrap_or_else(|| "us-east-1".to_string()),
+                prefix: pick_s3(|s| &s.prefix).unwrap_or_default(),
+            },
+        }
+    }
+
+    /// build the config from the flags, the environment and the config file.
+    ///
+    /// `config_file` is the `--config` flag. a config file that was asked for explicitly must
+    /// exist; the implicit locations are skipped when missing.
+    pub fn load(flags: &ConfigLayer, config_file: Option<&Path>) -> Result<Self, ConfigError> {
+        let _ = dotenvy::dotenv();
+
+        let env = ConfigLayer::from_env();
+
+        let explicit = config_file
+            .map(Path::to_path_buf)
+            .or_else(|| std::env::var_os(CONFIG_VAR).map(PathBuf::from));
+
+        let file = match explicit {
+            Some(path) => ConfigLayer::from_file(&path)?,
+            None => match implicit_config_file() {
+                Some(path) => ConfigLayer::from_file(&path)?,
+                None => ConfigLayer::default(),
+            },
+        };
+
+        Ok(Config::resolve(&[flags, &env, &file]))
+    }
+}
+
+impl Display for Config {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "📁 data:    {}", self.data.display())?;
+        writeln!(f, "🔑 headers: {}", self.headers.display())?;
+        writeln!(f, "🧠 models:  {}", self.models.display())?;
+        writeln!(f, "🗃️ cache:   {}", self.cache.display())?;
+
+        match self.storage {
+            StorageKind::Local => write!(f, "☁️ storage: local"),
+            StorageKind::S3 => write!(
+                f,
+                "☁️ storage: s3 {}/{}/{}",
+                self.s3.endpoint.trim_end_matches('/'),
+                self.s3.bucket.as_deref().unwrap_or("<no bucket>"),
+                self.s3.prefix
+            ),
+        }
+    }
+}
+
+/// set the global config. must be called before any path is used, otherwise the config is loaded
+/// without flags the first time it is needed.
+pub fn configure(config: Config) -> Result<(), ConfigError> {
+    CONFIG
+        .set(config)
+        .map_err(|_| ConfigError::AlreadyConfigured)
+}
+
+/// the global config, loading it from the environment and config file if `configure` was never
+/// called.
+pub fn try_config() -> Result<&'static Config, ConfigError> {
+    CONFIG.get_or_try_init(|| Config::load(&ConfigLayer::default(), None))
+}
+
+/// the global config for path helpers. `Dispatch` configures warheads before running a command,
+/// so this only panics when the library is used without a valid config.
+pub fn config() -> &'static Config {
+    try_config().unwrap_or_else(|e| panic!("{e}"))
+}
+
+fn implicit_config_file() -> Option<PathBuf> {
+    let local = PathBuf::from(CONFIG_FILE);
+
+    if local.is_file() {
+        return Some(local);
+    }
+
+    let config_home = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+
+    let global = config_home.join("warheads").join(CONFIG_FILE);
+
+    global.is_file().then_some(global)
+}
+
+/// the `data` directory of the repository the executable was built in, for running straight out
+/// of `target/`. anywhere else (e.g. an installed binary) falls back to `./data`.
+fn default_data() -> PathBuf {
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| {
+            // skip the executable itself, which is also named warheads
+            exe.ancestors()
+                .skip(1)
+                .find(|dir| dir.file_name().is_some_and(|name| name == "warheads"))
+                .map(|dir| dir.join("data"))
+        })
+        .unwrap_or_else(|| PathBuf::from("data"))
+}
+
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    #[error("{1}\n❌ failed to read config file: {path}", path = .0.display())]
+    ConfigReadError(PathBuf, std::io::Error),
+    #[error("{1}\n❌ failed to parse config file: {path}", path = .0.display())]
+    ConfigParseError(PathBuf, toml::de::Error),
+    #[error("❌ configuration was already loaded before it could be set")]
+    AlreadyConfigured,
+    #[error("❌ data directory {path} does not exist. run `warheads init` or set --data", path = .0.display())]
+    MissingDataDirectory(PathBuf),
+}
+
+#[cfg(test)]
+mod test_config {
+    use super::*;
+
+    use std::io::Write;
+
+    #[test]
+    fn test_earlier_layers_take_precedence() {
+        let flags = ConfigLayer {
+            data: Some(PathBuf::from("/flag/data")),
+            ..Default::default()
+        };
+
+        let env = ConfigLayer {
+            data: Some(PathBuf::from("/env/data")),
+            headers: Some(PathBuf::from("/env/headers.json")),
+            ..Default::default()
+        };
+
+        let file = ConfigLayer {
+            headers: Some(PathBuf::from("/file/headers.json")),
+            models: Some(PathBuf::from("/file/models")),
+            ..Default::default()
+        };
+
+        let config = Config::resolve(&[&flags, &env, &file]);
+
+        assert_eq!(
+            config,
+            Config {
+                data: PathBuf::from("/flag/data"),
+                headers: PathBuf::from("/env/headers.json"),
+                models: PathBuf::from("/file/models"),
+                cache: PathBuf::from("/flag/data/cache"),
+                storage: StorageKind::Local,
+                s3: S3Config {
+                    endpoint: "http://localhost:9000".to_string(),
+                    bucket: None,
+                    region: "us-east-1".to_string(),
+                    prefix: String::new(),
+                },
+            }
+        );
+    }
+
+    #[test]
+    fn test_defaults_follow_data_root() {
+        let layer = ConfigLayer {
+            data: Some(PathBuf::from("/srv/warheads")),
+            ..Default::default()
+        };
+
+        let config = Config::resolve(&[&layer]);
+
+        assert_eq!(config.models, PathBuf::from("/srv/warheads/nba"));
+        assert_eq!(config.cache, PathBuf::from("/srv/warheads/cache"));
+        assert_eq!(config.headers, PathBuf::from("/srv/warheads/headers.json"));
+    }
+
+    #[test]
+    fn test_headers_default_next_to_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+
+        std::fs::write(&path, "data = \"/srv/warheads\"\n").unwrap();
+
+        let file = ConfigLayer::from_file(&path).unwrap();
+
+        let config = Config::resolve(&[&ConfigLayer::default(), &file]);
+
+        assert_eq!(config.data, PathBuf::from("/srv/warheads"));
+        assert_eq!(config.headers, dir.path().join(HEADERS_FILE));
+    }
+
+    #[test]
+    fn test_config_file_paths_are_relative_to_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+
+        let mut file = std::fs::File::create(&path).unwrap();
+        writeln!(
+            file,
+            "data = \"data\"\nheaders = \"/etc/warheads/headers.json\""
+        )
+        .unwrap();
+
+        let layer = ConfigLayer::from_file(&path).unwrap();
+
+        assert_eq!(layer.data, Some(dir.path().join("data")));
+        assert_eq!(
+            layer.headers,
+            Some(PathBuf::from("/etc/warheads/headers.json"))
+        );
+        assert_eq!(layer.models, None);
+    }
+
+    #[test]
+    fn test_s3_settings_are_layered_by_field() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+
+        std::fs::write(
+            &path,
+            "storage = \"s3\"\n\n[s3]\nbucket = \"warheads\"\nprefix = \"team\"\n",
+        )
+        .unwrap();
+
+        let file = ConfigLayer::from_file(&path).unwrap();
+
+        let env = ConfigLayer {
+            s3: S3Layer {
+                endpoint: Some("http://minio:9000".to_string()),
+                bucket: Some("shared".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let config = Config::resolve(&[&env, &file]);
+
+        assert_eq!(config.storage, StorageKind::S3);
+        assert_eq!(config.s3.endpoint, "http://minio:9000");
+        assert_eq!(config.s3.bucket.as_deref(), Some("shared"));
+        assert_eq!(config.s3.prefix, "team");
+        assert_eq!(config.s3.region, "us-east-1");
+    }
+
+    #[test]
+    fn test_unknown_config_keys_are_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+
+        std::fs::write(&path, "dta = \"typo\"\n").unwrap();
+
+        assert!(matches!(
+            ConfigLayer::from_file(&path),
+            Err(ConfigError::ConfigParseError(_, _))
+        ));
+    }
+}
@@ -1,42 +1,62 @@
+use std::path::PathBuf;
 use std::{collections::HashMap, fs};
 
-use reqwest::header::{HeaderMap, HeaderName, ACCEPT_ENCODING, TE};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, ACCEPT_ENCODING, TE};
 use reqwest::header::{
     ACCEPT, ACCEPT_LANGUAGE, CACHE_CONTROL, CONNECTION, ORIGIN, PRAGMA, REFERER, USER_AGENT,
 };
 
-use once_cell::sync::Lazy;
+use once_cell::sync::OnceCell;
 
-pub static HEADER_MANAGER: Lazy<HeaderManager> = Lazy::new(|| HeaderManager::load());
+use thiserror::Error;
+
+use crate::constants::config::config;
+
+/// headers that every request to the nba needs a value for.
+pub const REQUIRED_HEADERS: [&str; 3] =
+    ["User-Agent", "Ocp-Apim-Subscription-Key", "x-postal-code"];
+
+static HEADER_MANAGER: OnceCell<HeaderManager> = OnceCell::new();
+
+/// the header manager, loading the headers file the first time it is needed. only commands that
+/// make requests to the nba call this, so everything else runs without a headers file.
+pub fn header_manager() -> Result<&'static HeaderManager, HeaderError> {
+    HEADER_MANAGER.get_or_try_init(HeaderManager::load)
+}
 
 pub struct HeaderManager {
     headers: HashMap<String, String>,
 }
 
-//header manager requires a  well configured headers.json file in the root directory of the project
+//header manager requires a well configured headers.json file, by default in the data root.
+//see `Config` for setting its location.
 impl HeaderManager {
-    pub fn load() -> Self {
-        let contents = fs::read_to_string("./headers.json").expect(
-            "💀 http header request file not found. expected headers. to be in .../warheads/headers.json",
-        );
+    pub fn load() -> Result<Self, HeaderError> {
+        let path = &config().headers;
 
-        let headers: HashMap<String, String> = serde_json::from_str(&contents).expect("💀 failed to parse headers.json as json. check the configuration in .../warheads/headers.json");
+        let contents = fs::read_to_string(path)
+            .map_err(|e| HeaderError::HeaderFileNotFound(path.clone(), e))?;
 
-        assert!(!headers.is_empty(), "💀 headers.json is empty");
-        assert!(
-            headers.contains_key("User-Agent"),
-            "💀 headers.json is missing User-Agent"
-        );
-        assert!(
-            headers.contains_key("Ocp-Apim-Subscription-Key"),
-            "💀 headers.json is missing User-Agent"
-        );
-        assert!(
-            headers.contains_key("x-postal-code"),
-            "💀 headers.json is missing User-Agent"
-        );
+        let headers: HashMap<String, String> = serde_json::from_str(&contents)
+            .map_err(|e| HeaderError::HeaderParseError(path.clone(), e))?;
 
-        Self { headers }
+        HeaderManager::from_headers(headers).map_err(|e| match e {
+            HeaderError::MissingHeader(key, _) => HeaderError::MissingHeader(key, path.clone()),
+            e => e,
+        })
+    }
+
+    /// validate that every required header has a value that can be sent in a request.
+    pub fn from_headers(headers: HashMap<String, String>) -> Result<Self, HeaderError> {
+        for key in REQUIRED_HEADERS {
+            let value = headers
+                .get(key)
+                .ok_or_else(|| HeaderError::MissingHeader(key, PathBuf::from("headers.json")))?;
+
+            HeaderValue::from_str(value).map_err(|_| HeaderError::InvalidHeaderValue(key))?;
+        }
+
+        Ok(Self { headers })
     }
 
     pub fn history_request_headers(&self) -> HeaderMap {
@@ -47,10 +67,7 @@ impl HeaderManager {
             ("en-US,en;q=0.9,de;q=0.8").parse().unwrap(),
         );
 
-        headers.insert(
-            USER_AGENT,
-            self.headers.get("User-Agent").unwrap().parse().unwrap(),
-        );
+        headers.insert(USER_AGENT, self.value("User-Agent"));
 
         headers
     }
@@ -67,28 +84,27 @@ impl HeaderManager {
 
         headers.insert(TE, ("trailers").parse().unwrap());
 
-        headers.insert(
-            USER_AGENT,
-            self.headers.get("User-Agent").unwrap().parse().unwrap(),
-        );
+        headers.insert(USER_AGENT, self.value("User-Agent"));
 
         headers.insert(
             "Ocp-Apim-Subscription-Key".parse::<HeaderName>().unwrap(),
-            self.headers
-                .get("Ocp-Apim-Subscription-Key")
-                .unwrap()
-                .parse()
-                .unwrap(),
+            self.value("Ocp-Apim-Subscription-Key"),
         );
 
         headers.insert(
             "x-postal-code".parse::<HeaderName>().unwrap(),
-            self.headers.get("x-postal-code").unwrap().parse().unwrap(),
+            self.value("x-postal-code"),
         );
 
         headers
     }
 
+    /// the value of a required header. `from_headers` has already checked that it is present and
+    /// valid.
+    fn value(&self, key: &'static str) -> HeaderValue {
+        HeaderValue::from_str(&self.headers[key]).expect("💀 required header was not validated")
+    }
+
     fn default() -> HeaderMap {
         let mut headers = HeaderMap::new();
 
@@ -113,14 +129,67 @@ impl HeaderManager {
     }
 }
 
+#[derive(Debug, Error)]
+pub enum HeaderError {
+    #[error("{1}\n❌ http header request file not found. expected headers to be in {path}", path = .0.display())]
+    HeaderFileNotFound(PathBuf, std::io::Error),
+    #[error("{1}\n❌ failed to parse headers as json. check the configuration in {path}", path = .0.display())]
+    HeaderParseError(PathBuf, serde_json::Error),
+    #[error("❌ {path} is missing {0}", path = .1.display())]
+    MissingHeader(&'static str, PathBuf),
+    #[error("❌ the value of {0} is not a valid http header")]
+    InvalidHeaderValue(&'static str),
+}
+
 #[cfg(test)]
 mod test_header_manager {
-    use crate::constants::header_manager::HeaderManager;
+    use crate::constants::header_manager::{HeaderError, HeaderManager, REQUIRED_HEADERS};
+
+    use std::collections::HashMap;
+
+    fn headers() -> HashMap<String, String> {
+        REQUIRED_HEADERS
+            .iter()
+            .map(|&key| (key.to_string(), "value".to_string()))
+            .collect()
+    }
 
     #[test]
     fn are_headers_present() {
-        let _manager = HeaderManager::load();
+        let _manager = HeaderManager::load().unwrap();
 
         assert!(true);
     }
+
+    #[test]
+    fn test_missing_header_is_named() {
+        for key in REQUIRED_HEADERS {
+            let mut headers = headers();
+            headers.remove(key);
+
+            match HeaderManager::from_headers(headers) {
+                Err(HeaderError::MissingHeader(missing, _)) => assert_eq!(missing, key),
+                _ => panic!("💀 expected {key} to be reported missing"),
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_header_value() {
+        let mut headers = headers();
+        headers.insert("x-postal-code".to_string(), "98101\n".to_string());
+
+        assert!(matches!(
+            HeaderManager::from_headers(headers),
+            Err(HeaderError::InvalidHeaderValue("x-postal-code"))
+        ));
+    }
+
+    #[test]
+    fn test_request_headers() {
+        let manager = HeaderManager::from_headers(headers()).unwrap();
+
+        assert_eq!(manager.gamecard_request_headers()["x-postal-code"], "value");
+        assert_eq!(manager.history_request_headers()["User-Agent"], "value");
+    }
 }
@@ -1,25 +1,54 @@
-pub fn data() -> String {
-    let curr_dir = std::env::current_exe().expect("💀 could not get current executable path");
+use crate::constants::config::{try_config, ConfigError};
 
-    let mut parent = curr_dir.parent();
-    while parent.is_some() && !parent.as_ref().unwrap().file_name().unwrap().eq("warheads") {
-        parent = parent.unwrap().parent();
-    }
-
-    if parent.is_none() {
-        panic!("💀 could not find warheads directory");
-    }
+use std::path::PathBuf;
 
-    let data_path = parent.unwrap().join("data");
+/// the data root. see `Config` for how it is resolved.
+pub fn data() -> Result<PathBuf, ConfigError> {
+    let data = &try_config()?.data;
 
-    data_path.to_string_lossy().into_owned()
+    if data.is_dir() {
+        Ok(data.clone())
+    } else {
+        Err(ConfigError::MissingDataDirectory(data.clone()))
+    }
 }
 
 #[test]
 fn test_data_path() {
-    let path = data();
-    let parts = path.split("/").collect::<Vec<_>>();
+    use crate::constants::config::{Config, ConfigLayer, CONFIG_FILE};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(CONFIG_FILE);
+
+    std::fs::write(&path, "data = \"volumes\"\n").unwrap();
+
+    let flags = ConfigLayer {
+        data: Some(PathBuf::from("/flag/data")),
+        ..Default::default()
+    };
+
+    let env = ConfigLayer {
+        data: Some(PathBuf::from("/env/data")),
+        ..Default::default()
+    };
+
+    let file = ConfigLayer::from_file(&path).unwrap();
+
+    let none = ConfigLayer::default();
 
-    assert_eq!(parts[parts.len() - 1], "data");
-    assert_eq!(parts[parts.len() - 2], "warheads");
+    assert_eq!(
+        Config::resolve(&[&flags, &env, &file]).data,
+        PathBuf::from("/flag/data")
+    );
+    assert_eq!(
+        Config::resolve(&[&none, &env, &file]).data,
+        PathBuf::from("/env/data")
+    );
+    assert_eq!(
+        Config::resolve(&[&none, &none, &file]).data,
+        dir.path().join("volumes")
+    );
+    assert!(Config::resolve(&[&none, &none, &none])
+        .data
+        .ends_with("data"));
 }
@@ -0,0 +1,325 @@
+use std::fmt::{Display, Formatter};
+use std::path::{Path, PathBuf};
+
+use chrono::Duration;
+
+use serde::Deserialize;
+
+use thiserror::Error;
+
+use crate::daemon::schedule::{parse_duration, Schedule};
+
+use crate::storage;
+
+/// how many times a failed job is retried before it waits for its next scheduled run.
+const DEFAULT_RETRIES: u32 = 3;
+
+/// how long a failed job waits before its first retry. each retry waits twice as long as the last.
+const DEFAULT_RETRY_DELAY: &str = "5m";
+
+/// the most times a job can be retried. a job that keeps failing waits for its next run instead.
+const MAX_RETRIES: u32 = 10;
+
+/// the longest a failed job waits before it's retried, however many times it has failed.
+fn max_retry_delay() -> Duration {
+    Duration::days(1)
+}
+
+/// ## JobKind
+///
+/// what a daemon job does, as the `job` of its table in `daemon.toml`.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "job", rename_all = "lowercase")]
+pub enum JobKind {
+    /// fetch new source data and chronicle it, like `warheads sync`
+    Sync,
+    /// train a model on all of history, like `warheads train`
+    Train {
+        model: String,
+        #[serde(default)]
+        args: Vec<String>,
+        /// promote the new run so forecasts use it
+        #[serde(default = "promote_by_default")]
+        promote: bool,
+    },
+    /// learn the games played since the promoted run was trained, like `warheads update`. a model
+    /// without a run is trained instead
+    Update {
+        model: String,
+        #[serde(default)]
+        args: Vec<String>,
+    },
+    /// forecast upcoming games, like `warheads forecast`
+    Forecast {
+        model: String,
+        #[serde(default)]
+        args: Vec<String>,
+        #[serde(default = "default_days")]
+        days: usize,
+        /// keep the predictions so they can be graded
+        #[serde(default)]
+        save: bool,
+    },
+}
+
+fn promote_by_default() -> bool {
+    true
+}
+
+fn default_days() -> usize {
+    7
+}
+
+impl Display for JobKind {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            JobKind::Sync => write!(f, "sync"),
+            JobKind::Train { model, .. } => write!(f, "train {model}"),
+            JobKind::Update { model, .. } => write!(f, "update {model}"),
+            JobKind::Forecast { model, days, .. } => write!(f, "forecast {model} {days} days"),
+        }
+    }
+}
+
+/// a job as it's written in `daemon.toml`.
+#[derive(Debug, Deserialize)]
+struct JobEntry {
+    name: String,
+    #[serde(flatten)]
+    kind: JobKind,
+    at: Option<String>,
+    every: Option<String>,
+    retries: Option<u32>,
+    retry_delay: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DaemonFile {
+    #[serde(default)]
+    jobs: Vec<JobEntry>,
+}
+
+/// ## Job
+///
+/// a named job, when it runs and how it's retried.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Job {
+    pub name: String,
+    pub kind: JobKind,
+    pub schedule: Schedule,
+    pub retries: u32,
+    pub retry_delay: Duration,
+}
+
+impl Job {
+    pub fn new(name: &str, kind: JobKind, schedule: Schedule) -> Self {
+        Job {
+            name: name.to_owned(),
+            kind,
+            schedule,
+            retries: DEFAULT_RETRIES,
+            retry_delay: parse_duration(DEFAULT_RETRY_DELAY).expect("💀 default retry delay"),
+        }
+    }
+
+    /// how long to wait before retry number `attempt`, counting from 1. never longer than a day.
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        2i32.checked_pow(attempt.saturating_sub(1))
+            .and_then(|factor| self.retry_delay.checked_mul(factor))
+            .map_or_else(max_retry_delay, |delay| delay.min(max_retry_delay()))
+    }
+
+    fn from_entry(entry: JobEntry) -> Result<Self, String> {
+        let schedule = Schedule::parse(entry.at.as_deref(), entry.every.as_deref())?;
+
+        let retry_delay =
+            parse_duration(entry.retry_delay.as_deref().unwrap_or(DEFAULT_RETRY_DELAY))?;
+
+        let retries = entry.retries.unwrap_or(DEFAULT_RETRIES);
+
+        if retries > MAX_RETRIES {
+            return Err(format!(
+                "a job can be retried at most {MAX_RETRIES} times, not {retries}"
+            ));
+        }
+
+        Ok(Job {
+            name: entry.name,
+            kind: entry.kind,
+            schedule,
+            retries,
+            retry_delay,
+        })
+    }
+}
+
+/// the jobs in `path`.
+pub fn load_jobs(path: &Path) -> Result<Vec<Job>, JobConfigError> {
+    let contents =
+        storage::read_to_string(path).map_err(|e| JobConfigError::ReadError(path.to_owned(), e))?;
+
+    parse_jobs(&contents).map_err(|e| match e {
+        JobConfigError::ParseError(_, e) => JobConfigError::ParseError(path.to_owned(), e),
+        JobConfigError::InvalidJob(_, name, e) => {
+            JobConfigError::InvalidJob(path.to_owned(), name, e)
+        }
+        e => e,
+    })
+}
+
+/// the jobs in the contents of a `daemon.toml`.
+pub fn parse_jobs(contents: &str) -> Result<Vec<Job>, JobConfigError> {
+    let file: DaemonFile = toml::from_str(contents)
+        .map_err(|e| JobConfigError::ParseError(PathBuf::from("daemon.toml"), e))?;
+
+    let jobs = file
+        .jobs
+        .into_iter()
+        .map(|entry| {
+            let name = entry.name.clone();
+
+            Job::from_entry(entry)
+                .map_err(|e| JobConfigError::InvalidJob(PathBuf::from("daemon.toml"), name, e))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    match jobs.is_empty() {
+        true => Err(JobConfigError::NoJobs),
+        false => Ok(jobs),
+    }
+}
+
+/// the routine the daemon runs without a `daemon.toml`: sync at 6am, update elo with the new games
+/// at 6:30 and save a week of forecasts at 7.
+pub fn default_jobs() -> Vec<Job> {
+    let daily = |at: &str| Schedule::parse(Some(at), None).expect("💀 default job schedule");
+
+    vec![
+        Job::new("sync", JobKind::Sync, daily("06:00")),
+        Job::new(
+            "update elo",
+            JobKind::Update {
+                model: "elo-v1".to_owned(),
+                args: Vec::new(),
+            },
+            daily("06:30"),
+        ),
+        Job::new(
+            "forecast elo",
+            JobKind::Forecast {
+                model: "elo-v1".to_owned(),
+                args: Vec::new(),
+                days: default_days(),
+                save: true,
+            },
+            daily("07:00"),
+        ),
+    ]
+}
+
+#[derive(Debug, Error)]
+pub enum JobConfigError {
+    #[error("❌ {1}\n❌ failed to read daemon jobs from {path}", path = .0.display())]
+    ReadError(PathBuf, std::io::Error),
+    #[error("❌ {1}\n❌ failed to parse daemon jobs in {path}", path = .0.display())]
+    ParseError(PathBuf, toml::de::Error),
+    #[error("❌ {2}\n❌ job {1} in {path} is invalid", path = .0.display())]
+    InvalidJob(PathBuf, String, String),
+    #[error("❌ no jobs to run. add a [[jobs]] table to daemon.toml")]
+    NoJobs,
+}
+
+#[cfg(test)]
+mod test_jobs {
+    use super::{default_jobs, parse_jobs, JobConfigError, JobKind};
+
+    use crate::daemon::schedule::Schedule;
+
+    use chrono::Duration;
+
+    #[test]
+    fn test_parse_jobs() {
+        let jobs = parse_jobs(
+            r#"
+            [[jobs]]
+            name = "sync"
+            job = "sync"
+            at = "06:00"
+
+            [[jobs]]
+            name = "update"
+            job = "update"
+            model = "last-n-games"
+            args = ["--games", "10"]
+            at = "06:30"
+
+            [[jobs]]
+            name = "forecast"
+            job = "forecast"
+            model = "elo-v1"
+            save = true
+            every = "6h"
+            retries = 1
+            retry_delay = "1m"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(jobs.len(), 3);
+        assert_eq!(jobs[0].kind, JobKind::Sync);
+        assert_eq!(jobs[0].retries, 3);
+        assert_eq!(
+            jobs[1].kind,
+            JobKind::Update {
+                model: "last-n-games".to_owned(),
+                args: vec!["--games".to_owned(), "10".to_owned()],
+            }
+        );
+        assert_eq!(
+            jobs[2].kind,
+            JobKind::Forecast {
+                model: "elo-v1".to_owned(),
+                args: Vec::new(),
+                days: 7,
+                save: true
+            }
+        );
+        assert_eq!(jobs[2].schedule, Schedule::Every(Duration::hours(6)));
+        assert_eq!(jobs[2].retries, 1);
+        assert_eq!(jobs[2].backoff(1), Duration::minutes(1));
+        assert_eq!(jobs[2].backoff(3), Duration::minutes(4));
+        assert_eq!(jobs[2].backoff(32), Duration::days(1));
+    }
+
+    #[test]
+    fn test_invalid_jobs() {
+        assert!(matches!(
+            parse_jobs("[[jobs]]\nname = \"sync\"\njob = \"sync\""),
+            Err(JobConfigError::InvalidJob(..))
+        ));
+        assert!(matches!(
+            parse_jobs("[[jobs]]\nname = \"x\"\njob = \"teleport\"\nat = \"06:00\""),
+            Err(JobConfigError::ParseError(..))
+        ));
+        assert!(matches!(
+            parse_jobs(
+                "[[jobs]]\nname = \"sync\"\njob = \"sync\"\nat = \"06:00\"\nretries = 32\nretry_delay = \"1d\""
+            ),
+            Err(JobConfigError::InvalidJob(..))
+        ));
+        assert!(matches!(parse_jobs(""), Err(JobConfigError::NoJobs)));
+    }
+
+    #[test]
+    fn test_default_jobs_run_in_order() {
+        let jobs = default_jobs();
+
+        assert_eq!(jobs[0].kind, JobKind::Sync);
+        assert!(jobs
+            .windows(2)
+            .all(|pair| match (pair[0].schedule, pair[1].schedule) {
+                (Schedule::Daily(a), Schedule::Daily(b)) => a < b,
+                _ => false,
+            }));
+    }
+}
@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter};
+
+use chrono::{Duration, NaiveDateTime, NaiveTime};
+
+/// ## Schedule
+///
+/// when a daemon job runs: once a day at a local time, or over and over at an interval.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Schedule {
+    /// `at = "06:00"`
+    Daily(NaiveTime),
+    /// `every = "6h"`
+    Every(Duration),
+}
+
+impl Schedule {
+    /// the schedule of a job given `at` or `every`, but not both.
+    pub fn parse(at: Option<&str>, every: Option<&str>) -> Result<Schedule, String> {
+        match (at, every) {
+            (Some(at), None) => NaiveTime::parse_from_str(at, "%H:%M")
+                .map(Schedule::Daily)
+                .map_err(|_| format!("{at} is not a time of day like 06:00")),
+            (None, Some(every)) => parse_duration(every).map(Schedule::Every),
+            (Some(_), Some(_)) => {
+                Err("a job runs either `at` a time or `every` interval, not both".to_owned())
+            }
+            (None, None) => Err("a job needs `at` a time of day or `every` interval".to_owned()),
+        }
+    }
+
+    /// the first time the job runs after `now`.
+    pub fn next_after(&self, now: NaiveDateTime) -> NaiveDateTime {
+        match self {
+            Schedule::Daily(time) => {
+                let today = now.date().and_time(*time);
+
+                match today > now {
+                    true => today,
+                    false => today + Duration::days(1),
+                }
+            }
+            Schedule::Every(interval) => now + *interval,
+        }
+    }
+}
+
+impl Display for Schedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Schedule::Daily(time) => write!(f, "daily at {}", time.format("%H:%M")),
+            Schedule::Every(interval) => write!(f, "every {}", fmt_duration(*interval)),
+        }
+    }
+}
+
+/// a duration like `30s`, `5m`, `6h` or `1d`.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+
+    let invalid = || format!("{s} is not a duration like 30s, 5m, 6h or 1d");
+
+    let split = s.find(|c: char| !c.is_ascii_digit()).ok_or_else(invalid)?;
+
+    let (amount, unit) = s.split_at(split);
+
+    let amount = amount.parse::<i64>().map_err(|_| invalid())?;
+
+    let duration = match unit.trim() {
+        "s" => Duration::seconds(amount),
+        "m" => Duration::minutes(amount),
+        "h" => Duration::hours(amount),
+        "d" => Duration::days(amount),
+        _ => return Err(invalid()),
+    };
+
+    match duration > Duration::zero() {
+        true => Ok(duration),
+        false => Err(invalid()),
+    }
+}
+
+/// the largest whole unit of a duration, e.g. `90m` is `90m` and `120m` is `2h`.
+pub fn fmt_duration(duration: Duration) -> String {
+    let seconds = duration.num_seconds();
+
+    match seconds {
+        s if s % 86_400 == 0 => format!("{}d", s / 86_400),
+        s if s % 3_600 == 0 => format!("{}h", s / 3_600),
+        s if s % 60 == 0 => format!("{}m", s / 60),
+        s => format!("{s}s"),
+    }
+}
+
+#[cfg(test)]
+mod test_schedule {
+    use super::{fmt_duration, parse_duration, Schedule};
+
+    use chrono::{Duration, NaiveDateTime};
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("30s"), Ok(Duration::seconds(30)));
+        assert_eq!(parse_duration("5m"), Ok(Duration::minutes(5)));
+        assert_eq!(parse_duration("6h"), Ok(Duration::hours(6)));
+        assert_eq!(parse_duration("1d"), Ok(Duration::days(1)));
+        assert!(parse_duration("0m").is_err());
+        assert!(parse_duration("soon").is_err());
+        assert!(parse_duration("5y").is_err());
+
+        assert_eq!(fmt_duration(Duration::minutes(120)), "2h");
+        assert_eq!(fmt_duration(Duration::minutes(90)), "90m");
+    }
+
+    #[test]
+    fn test_daily_schedule() {
+        let schedule = Schedule::parse(Some("06:00"), None).unwrap();
+
+        assert_eq!(
+            schedule.next_after(at("2024-01-15 05:59")),
+            at("2024-01-15 06:00")
+        );
+        assert_eq!(
+            schedule.next_after(at("2024-01-15 06:00")),
+            at("2024-01-16 06:00")
+        );
+        assert_eq!(schedule.to_string(), "daily at 06:00");
+    }
+
+    #[test]
+    fn test_interval_schedule() {
+        let schedule = Schedule::parse(None, Some("6h")).unwrap();
+
+        assert_eq!(
+            schedule.next_after(at("2024-01-15 23:00")),
+            at("2024-01-16 05:00")
+        );
+        assert!(Schedule::parse(None, None).is_err());
+        assert!(Schedule::parse(Some("06:00"), Some("6h")).is_err());
+        assert!(Schedule::parse(Some("6am"), None).is_err());
+    }
+}
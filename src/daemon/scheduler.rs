@@ -0,0 +1,506 @@
+use std::future::Future;
+use std::path::{Path, PathBuf};
+
+use chrono::{Local, NaiveDateTime};
+
+use serde::Serialize;
+
+use thiserror::Error;
+
+use crate::daemon::jobs::{Job, JobKind};
+
+use crate::dapi::write::write_serializable_with_directory;
+
+/// ## Clock
+///
+/// where the daemon gets the time and how it waits, so tests can skip ahead instead of sleeping.
+pub trait Clock {
+    fn now(&self) -> NaiveDateTime;
+    fn sleep_until(&self, time: NaiveDateTime) -> impl Future<Output = ()>;
+}
+
+/// the local wall clock.
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> NaiveDateTime {
+        Local::now().naive_local()
+    }
+
+    fn sleep_until(&self, time: NaiveDateTime) -> impl Future<Output = ()> {
+        let wait = (time - self.now()).to_std().unwrap_or_default();
+
+        tokio::time::sleep(wait)
+    }
+}
+
+/// ## JobRunner
+///
+/// does the work of a job. an error is the reason the job failed.
+pub trait JobRunner {
+    fn run(&mut self, kind: &JobKind) -> impl Future<Output = Result<(), String>>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    /// waiting for its next run
+    Scheduled,
+    /// failed and waiting to try again
+    Retrying,
+    /// failed every retry of its last run
+    Failed,
+}
+
+/// ## JobStatus
+///
+/// how a job has gone, as written to the status file.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub name: String,
+    pub job: String,
+    pub schedule: String,
+    pub state: JobState,
+    pub next_run: NaiveDateTime,
+    /// failures of the current run so far
+    pub attempts: u32,
+    pub last_run: Option<NaiveDateTime>,
+    pub last_success: Option<NaiveDateTime>,
+    pub last_error: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventKind {
+    Started,
+    Succeeded,
+    Retrying,
+    Failed,
+}
+
+/// ## DaemonEvent
+///
+/// something that happened to a job. it's logged with its fields as key values, so the json log
+/// format writes them out.
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonEvent {
+    pub at: NaiveDateTime,
+    pub job: String,
+    pub event: EventKind,
+    pub attempt: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_run: Option<NaiveDateTime>,
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EventKind::Started => "started",
+            EventKind::Succeeded => "succeeded",
+            EventKind::Retrying => "retrying",
+            EventKind::Failed => "failed",
+        }
+    }
+}
+
+impl DaemonEvent {
+    fn log(&self) {
+        let (job, event, attempt) = (self.job.as_str(), self.event.as_str(), self.attempt);
+
+        let error = self.error.as_deref().unwrap_or_default();
+
+        let next_run = self
+            .next_run
+            .map(|next_run| next_run.to_string())
+            .unwrap_or_default();
+
+        match self.event {
+            EventKind::Started => log::info!(
+                job, event, attempt, at:% = self.at;
+                "🏃 {job} started (attempt {attempt})"
+            ),
+            EventKind::Succeeded => log::info!(
+                job, event, attempt, at:% = self.at, next_run;
+                "✅ {job} succeeded. next run at {next_run}"
+            ),
+            EventKind::Retrying => log::warn!(
+                job, event, attempt, at:% = self.at, next_run, error;
+                "⚠️ {error}\n⚠️ {job} failed. retrying at {next_run}"
+            ),
+            EventKind::Failed => log::error!(
+                job, event, attempt, at:% = self.at, next_run, error;
+                "❌ {error}\n❌ {job} failed {attempt} times. next run at {next_run}"
+            ),
+        }
+    }
+}
+
+/// ## Daemon
+///
+/// runs jobs on their schedules, retrying failures, until it's stopped.
+pub struct Daemon {
+    jobs: Vec<(Job, JobStatus)>,
+    status_file: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+struct StatusFile<'a> {
+    updated_at: NaiveDateTime,
+    jobs: Vec<&'a JobStatus>,
+}
+
+impl Daemon {
+    /// a daemon whose jobs first run at their next scheduled time after `now`.
+    pub fn new(jobs: Vec<Job>, now: NaiveDateTime) -> Self {
+        let jobs = jobs
+            .into_iter()
+            .map(|job| {
+                let status = JobStatus {
+                    name: job.name.clone(),
+                    job: job.kind.to_string(),
+                    schedule: job.schedule.to_string(),
+                    state: JobState::Scheduled,
+                    next_run: job.schedule.next_after(now),
+                    attempts: 0,
+                    last_run: None,
+                    last_success: None,
+                    last_error: None,
+                };
+
+                (job, status)
+            })
+            .collect();
+
+        Daemon {
+            jobs,
+            status_file: None,
+        }
+    }
+
+    /// write the status of every job to `path` after each run.
+    pub fn with_status_file(mut self, path: &Path) -> Self {
+        self.status_file = Some(path.to_owned());
+        self
+    }
+
+    pub fn statuses(&self) -> impl Iterator<Item = &JobStatus> {
+        self.jobs.iter().map(|(_, status)| status)
+    }
+
+    /// the next time a job is due.
+    pub fn next_wake(&self) -> Option<NaiveDateTime> {
+        self.statuses().map(|status| status.next_run).min()
+    }
+
+    /// run every job that is due by `clock`'s time, earliest first, and return what happened.
+    pub async fn tick(
+        &mut self,
+        clock: &impl Clock,
+        runner: &mut impl JobRunner,
+    ) -> Vec<DaemonEvent> {
+        let now = clock.now();
+
+        let mut due = (0..self.jobs.len())
+            .filter(|i| self.jobs[*i].1.next_run <= now)
+            .collect::<Vec<_>>();
+
+        due.sort_by_key(|i| self.jobs[*i].1.next_run);
+
+        let mut events = Vec::new();
+
+        for i in due {
+            let (job, status) = &mut self.jobs[i];
+
+            let attempt = status.attempts + 1;
+
+            let started = clock.now();
+
+            events.push(DaemonEvent {
+                at: started,
+                job: job.name.clone(),
+                event: EventKind::Started,
+                attempt,
+                error: None,
+                next_run: None,
+            });
+
+            let result = runner.run(&job.kind).await;
+
+            let finished = clock.now();
+
+            status.last_run = Some(started);
+
+            let event = match result {
+                Ok(_) => {
+                    status.state = JobState::Scheduled;
+                    status.attempts = 0;
+                    status.last_success = Some(finished);
+                    status.last_error = None;
+                    status.next_run = job.schedule.next_after(finished);
+
+                    EventKind::Succeeded
+                }
+                Err(e) => {
+                    status.last_error = Some(e);
+
+                    match attempt <= job.retries {
+                        true => {
+                            status.state = JobState::Retrying;
+                            status.attempts = attempt;
+                            status.next_run = finished
+                                .checked_add_signed(job.backoff(attempt))
+                                .unwrap_or_else(|| job.schedule.next_after(finished));
+
+                            EventKind::Retrying
+                        }
+                        false => {
+                            status.state = JobState::Failed;
+                            status.attempts = 0;
+                            status.next_run = job.schedule.next_after(finished);
+
+                            EventKind::Failed
+                        }
+                    }
+                }
+            };
+
+            events.push(DaemonEvent {
+                at: finished,
+                job: job.name.clone(),
+                event,
+                attempt,
+                error: match event {
+                    EventKind::Succeeded => None,
+                    _ => status.last_error.clone(),
+                },
+                next_run: Some(status.next_run),
+            });
+        }
+
+        events
+    }
+
+    /// run jobs as they come due until `until`, or forever without it.
+    pub async fn run(
+        &mut self,
+        clock: &impl Clock,
+        runner: &mut impl JobRunner,
+        until: Option<NaiveDateTime>,
+    ) -> Result<(), DaemonError> {
+        for (job, status) in &self.jobs {
+            log::info!(
+                "ℹ️  {} ({}) runs {}. next run at {}",
+                job.name,
+                status.job,
+                status.schedule,
+                status.next_run
+            );
+        }
+
+        self.write_status(clock.now())?;
+
+        while let Some(wake) = self.next_wake() {
+            if until.is_some_and(|until| wake > until) {
+                break;
+            }
+
+            clock.sleep_until(wake).await;
+
+            let events = self.tick(clock, runner).await;
+
+            events.iter().for_each(DaemonEvent::log);
+
+            self.write_status(clock.now())?;
+        }
+
+        Ok(())
+    }
+
+    fn write_status(&self, now: NaiveDateTime) -> Result<(), DaemonError> {
+        let Some(path) = &self.status_file else {
+            return Ok(());
+        };
+
+        let status = StatusFile {
+            updated_at: now,
+            jobs: self.statuses().collect(),
+        };
+
+        write_serializable_with_directory(path, &status)
+            .map_err(|e| DaemonError::StatusWriteError(path.clone(), e))
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DaemonError {
+    #[error("❌ {1}\n❌ failed to write the daemon status to {path}", path = .0.display())]
+    StatusWriteError(PathBuf, std::io::Error),
+}
+
+#[cfg(test)]
+mod test_daemon {
+    use super::{Clock, Daemon, EventKind, JobRunner, JobState};
+
+    use crate::daemon::jobs::{Job, JobKind};
+    use crate::daemon::schedule::Schedule;
+
+    use chrono::{Duration, NaiveDateTime};
+
+    use std::cell::Cell;
+    use std::collections::VecDeque;
+    use std::future::{ready, Future};
+
+    /// a clock that jumps to whenever the daemon waits for, and that each job takes a minute on.
+    struct TestClock(Cell<NaiveDateTime>);
+
+    impl Clock for TestClock {
+        fn now(&self) -> NaiveDateTime {
+            self.0.get()
+        }
+
+        fn sleep_until(&self, time: NaiveDateTime) -> impl Future<Output = ()> {
+            self.0.set(self.0.get().max(time));
+
+            ready(())
+        }
+    }
+
+    /// a runner that answers each run with the next scripted result and records what it ran.
+    struct ScriptedRunner<'a> {
+        clock: &'a TestClock,
+        results: VecDeque<Result<(), String>>,
+        ran: Vec<(NaiveDateTime, String)>,
+    }
+
+    impl JobRunner for ScriptedRunner<'_> {
+        fn run(&mut self, kind: &JobKind) -> impl Future<Output = Result<(), String>> {
+            self.ran.push((self.clock.now(), kind.to_string()));
+            self.clock.0.set(self.clock.now() + Duration::minutes(1));
+
+            ready(self.results.pop_front().unwrap_or(Ok(())))
+        }
+    }
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M").unwrap()
+    }
+
+    fn daily(name: &str, kind: JobKind, time: &str) -> Job {
+        Job::new(name, kind, Schedule::parse(Some(time), None).unwrap())
+    }
+
+    fn forecast() -> JobKind {
+        JobKind::Forecast {
+            model: "elo-v1".to_owned(),
+            args: Vec::new(),
+            days: 7,
+            save: true,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_jobs_run_in_schedule_order() {
+        let clock = TestClock(Cell::new(at("2024-01-15 05:00")));
+
+        let mut daemon = Daemon::new(
+            vec![
+                daily("forecast", forecast(), "07:00"),
+                daily("sync", JobKind::Sync, "06:00"),
+            ],
+            clock.now(),
+        );
+
+        let mut runner = ScriptedRunner {
+            clock: &clock,
+            results: VecDeque::new(),
+            ran: Vec::new(),
+        };
+
+        daemon
+            .run(&clock, &mut runner, Some(at("2024-01-16 12:00")))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            runner.ran,
+            vec![
+                (at("2024-01-15 06:00"), "sync".to_owned()),
+                (at("2024-01-15 07:00"), "forecast elo-v1 7 days".to_owned()),
+                (at("2024-01-16 06:00"), "sync".to_owned()),
+                (at("2024-01-16 07:00"), "forecast elo-v1 7 days".to_owned()),
+            ]
+        );
+        assert!(daemon.statuses().all(|s| s.state == JobState::Scheduled));
+    }
+
+    #[tokio::test]
+    async fn test_failures_are_retried_with_backoff() {
+        let clock = TestClock(Cell::new(at("2024-01-15 05:00")));
+
+        let mut job = daily("sync", JobKind::Sync, "06:00");
+        job.retries = 2;
+        job.retry_delay = Duration::minutes(5);
+
+        let mut daemon = Daemon::new(vec![job], clock.now());
+
+        let mut runner = ScriptedRunner {
+            clock: &clock,
+            results: VecDeque::from([Err("nba.com is down".to_owned()), Ok(())]),
+            ran: Vec::new(),
+        };
+
+        clock.sleep_until(daemon.next_wake().unwrap()).await;
+
+        let events = daemon.tick(&clock, &mut runner).await;
+
+        assert_eq!(events[1].event, EventKind::Retrying);
+        assert_eq!(events[1].error.as_deref(), Some("nba.com is down"));
+        assert_eq!(events[1].next_run, Some(at("2024-01-15 06:06")));
+
+        clock.sleep_until(daemon.next_wake().unwrap()).await;
+
+        let events = daemon.tick(&clock, &mut runner).await;
+
+        assert_eq!(events[1].event, EventKind::Succeeded);
+        assert_eq!(events[1].attempt, 2);
+        assert_eq!(events[1].next_run, Some(at("2024-01-16 06:00")));
+
+        let status = daemon.statuses().next().unwrap();
+
+        assert_eq!(status.attempts, 0);
+        assert_eq!(status.last_error, None);
+        assert_eq!(status.last_success, Some(at("2024-01-15 06:07")));
+    }
+
+    #[tokio::test]
+    async fn test_job_fails_after_its_last_retry() {
+        let clock = TestClock(Cell::new(at("2024-01-15 05:00")));
+
+        let mut job = daily("sync", JobKind::Sync, "06:00");
+        job.retries = 1;
+        job.retry_delay = Duration::minutes(5);
+
+        let mut daemon = Daemon::new(vec![job], clock.now());
+
+        let mut runner = ScriptedRunner {
+            clock: &clock,
+            results: VecDeque::from([Err("down".to_owned()), Err("still down".to_owned())]),
+            ran: Vec::new(),
+        };
+
+        for _ in 0..2 {
+            clock.sleep_until(daemon.next_wake().unwrap()).await;
+
+            daemon.tick(&clock, &mut runner).await;
+        }
+
+        let status = daemon.statuses().next().unwrap();
+
+        assert_eq!(status.state, JobState::Failed);
+        assert_eq!(status.last_error.as_deref(), Some("still down"));
+        assert_eq!(status.next_run, at("2024-01-16 06:00"));
+        assert_eq!(runner.ran.len(), 2);
+    }
+}
@@ -36,7 +36,7 @@ impl Archive for String {
 impl Archive for PathBuf {
     fn write(&mut self, new_content: String) -> Result<(), ()> {
         let _parseable: Value = serde_json::from_str(&new_content).map_err(|e| {
-            eprintln!("❌ failed to convert new data to JSON. writing to JSON file requires being parseable: {}", e);
+            log::error!("❌ failed to convert new data to JSON. writing to JSON file requires being parseable: {}", e);
 
             ()
         })?;
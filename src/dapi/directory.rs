@@ -0,0 +1,180 @@
+use crate::dapi::player_directory::PlayerDirectory;
+use crate::dapi::read_disk::read_nba_season;
+use crate::dapi::season_manager::nba_lifespan_period;
+use crate::dapi::team_directory::TeamDirectory;
+
+use crate::types::{GameDate, PlayerId, TeamId};
+
+use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// the players and franchises of every stored volume. seasons that can't be read are left out.
+pub fn build_directories() -> (PlayerDirectory, TeamDirectory) {
+    let mut players = PlayerDirectory::default();
+    let mut teams = TeamDirectory::new();
+
+    for season in nba_lifespan_period() {
+        match read_nba_season(season) {
+            Ok(games) => games.iter().for_each(|game| {
+                players.record_game(game);
+                teams.record_game(game);
+            }),
+            Err(e) => log::warn!("{e}\n⚠️ leaving {season} out of the directories"),
+        }
+    }
+
+    (players, teams)
+}
+
+/// the directories `chronicle_nba` saved, or if there are none yet, directories built from the
+/// stored volumes.
+pub fn load_directories() -> (PlayerDirectory, TeamDirectory) {
+    match (PlayerDirectory::load(), TeamDirectory::load()) {
+        (Ok(players), Ok(teams)) => (players, teams),
+        _ => {
+            log::info!("ℹ️  no saved directories. building them from the stored volumes...");
+
+            build_directories()
+        }
+    }
+}
+
+/// a player given by id, or by a name that matches one player better than any other.
+pub fn resolve_player(
+    directory: &PlayerDirectory,
+    player: &str,
+) -> Result<PlayerId, DirectoryError> {
+    if let Ok(id) = player.trim().parse::<u64>() {
+        return Ok(PlayerId(id));
+    }
+
+    match directory.find(player).as_slice() {
+        [] => Err(DirectoryError::UnknownPlayer(player.to_owned())),
+        [id] => Ok(*id),
+        ids => Err(DirectoryError::AmbiguousPlayer(
+            player.to_owned(),
+            ids.iter()
+                .map(|id| match directory.entry(*id) {
+                    Some(entry) => format!(
+                        "{} ({id}, {})",
+                        entry.name,
+                        career(entry.first_game, entry.last_game)
+                    ),
+                    None => id.to_string(),
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// a franchise given by id, or by any abbreviation or name it has played under.
+pub fn resolve_team(directory: &TeamDirectory, team: &str) -> Result<TeamId, DirectoryError> {
+    if let Ok(id) = team.trim().parse::<u64>() {
+        return Ok(TeamId(id));
+    }
+
+    match directory.find(team).as_slice() {
+        [] => Err(DirectoryError::UnknownTeam(team.to_owned())),
+        [id] => Ok(*id),
+        ids => Err(DirectoryError::AmbiguousTeam(
+            team.to_owned(),
+            ids.iter()
+                .map(|id| {
+                    match directory
+                        .franchise(*id)
+                        .and_then(|franchise| franchise.current())
+                    {
+                        Some(identity) => format!("{} ({id})", identity.name.0),
+                        None => id.to_string(),
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(", "),
+        )),
+    }
+}
+
+/// the years of a player's first and last games.
+pub fn career(first: Option<GameDate>, last: Option<GameDate>) -> String {
+    match (first, last) {
+        (Some(first), Some(last)) => format!("{}-{}", first.destructure().0, last.destructure().0),
+        _ => String::from("no games"),
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum DirectoryError {
+    #[error("{1}\n❌ failed to read the directory in {path}", path = .0.display())]
+    ReadError(PathBuf, io::Error),
+    #[error("{1}\n❌ failed to parse the directory in {path}", path = .0.display())]
+    ParseError(PathBuf, serde_json::Error),
+    #[error("{1}\n❌ failed to write the directory to {path}", path = .0.display())]
+    WriteError(PathBuf, io::Error),
+    #[error("❌ no player is named {0}.")]
+    UnknownPlayer(String),
+    #[error("❌ more than one player matches {0}: {1}\n❌ use a full name or a player id.")]
+    AmbiguousPlayer(String, String),
+    #[error("❌ no team has been called {0}.")]
+    UnknownTeam(String),
+    #[error(
+        "❌ more than one team matches {0}: {1}\n❌ use a full name, an abbreviation or a team id."
+    )]
+    AmbiguousTeam(String, String),
+}
+
+#[cfg(test)]
+mod test_directory {
+    use super::{resolve_player, DirectoryError};
+
+    use crate::dapi::player_directory::PlayerDirectory;
+
+    use crate::types::{PlayerId, PlayerName};
+
+    fn directory() -> PlayerDirectory {
+        let mut directory = PlayerDirectory::default();
+
+        directory.insert(PlayerId(2544), PlayerName("LeBron James".to_owned()));
+        directory.insert(PlayerId(201939), PlayerName("Stephen Curry".to_owned()));
+        directory.insert(PlayerId(1629652), PlayerName("Seth Curry".to_owned()));
+        directory.insert(PlayerId(203999), PlayerName("Nikola Jokić".to_owned()));
+
+        directory
+    }
+
+    #[test]
+    fn test_resolve_player() {
+        let directory = directory();
+
+        assert_eq!(
+            resolve_player(&directory, "lebron james").unwrap(),
+            PlayerId(2544)
+        );
+        assert_eq!(
+            resolve_player(&directory, "201939").unwrap(),
+            PlayerId(201939)
+        );
+        assert_eq!(
+            resolve_player(&directory, "steph").unwrap(),
+            PlayerId(201939)
+        );
+        assert_eq!(
+            resolve_player(&directory, "jokic").unwrap(),
+            PlayerId(203999)
+        );
+        assert_eq!(
+            resolve_player(&directory, "nikola jokc").unwrap(),
+            PlayerId(203999)
+        );
+        assert!(matches!(
+            resolve_player(&directory, "curry"),
+            Err(DirectoryError::AmbiguousPlayer(..))
+        ));
+        assert!(matches!(
+            resolve_player(&directory, "jordan"),
+            Err(DirectoryError::UnknownPlayer(_))
+        ));
+    }
+}
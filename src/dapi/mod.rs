@@ -1,6 +1,8 @@
 pub mod archive;
 pub mod box_score_stat;
+pub mod directory;
 pub mod from_value;
+pub mod name_search;
 pub mod player_box_score;
 pub mod player_directory;
 pub mod read_disk;
@@ -13,4 +15,5 @@ mod test_season_manager;
 mod tests;
 pub mod timeline;
 pub mod timeline_manager;
+pub mod volume;
 pub mod write;
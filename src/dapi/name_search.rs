@@ -0,0 +1,185 @@
+/// ## NameMatch
+///
+/// how closely a search matched a name, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum NameMatch {
+    /// the whole name, e.g. `lebron james`
+    Exact,
+    /// whole words of the name, e.g. `james` or `james lebron`
+    Words,
+    /// the start of words of the name, e.g. `steph cur`
+    Prefix,
+    /// any part of the name, e.g. `bron`
+    Contains,
+    /// the name, or one of its words, misspelled by this many letters, e.g. `jokic` for `jokić`
+    Typo(usize),
+}
+
+/// how `query` matches `name`, if it does at all. case, punctuation and accents are ignored.
+pub fn name_match(query: &str, name: &str) -> Option<NameMatch> {
+    let query = normalize(query);
+    let name = normalize(name);
+
+    if query.is_empty() {
+        return None;
+    }
+
+    if query == name {
+        return Some(NameMatch::Exact);
+    }
+
+    let query_words = query.split(' ').collect::<Vec<_>>();
+    let name_words = name.split(' ').collect::<Vec<_>>();
+
+    if query_words.iter().all(|q| name_words.contains(q)) {
+        return Some(NameMatch::Words);
+    }
+
+    if query_words
+        .iter()
+        .all(|q| name_words.iter().any(|n| n.starts_with(q)))
+    {
+        return Some(NameMatch::Prefix);
+    }
+
+    if name.contains(&query) {
+        return Some(NameMatch::Contains);
+    }
+
+    let letters = query.chars().filter(|c| *c != ' ').count();
+
+    if letters < 3 {
+        return None;
+    }
+
+    let distance = match query_words.len() {
+        1 => name_words
+            .iter()
+            .map(|word| levenshtein(&query, word))
+            .chain([levenshtein(&query, &name)])
+            .min()?,
+        _ => levenshtein(&query, &name),
+    };
+
+    match distance <= (letters / 4).max(1) {
+        true => Some(NameMatch::Typo(distance)),
+        false => None,
+    }
+}
+
+/// lowercase ascii letters and digits separated by single spaces. accented letters lose their
+/// accents and punctuation inside a word is dropped, so `J.J. Barea` is `jj barea`.
+pub fn normalize(s: &str) -> String {
+    let mut normalized = String::with_capacity(s.len());
+
+    for c in s.chars().flat_map(char::to_lowercase) {
+        match fold(c) {
+            c if c.is_ascii_alphanumeric() => normalized.push(c),
+            c if (c.is_whitespace() || c == '-')
+                && !normalized.is_empty()
+                && !normalized.ends_with(' ') =>
+            {
+                normalized.push(' ')
+            }
+            _ => {}
+        }
+    }
+
+    normalized.trim_end().to_owned()
+}
+
+/// the letter without its accent.
+fn fold(c: char) -> char {
+    match c {
+        'á' | 'à' | 'â' | 'ä' | 'ã' | 'å' | 'ā' | 'ą' => 'a',
+        'ç' | 'ć' | 'č' => 'c',
+        'ď' | 'đ' => 'd',
+        'é' | 'è' | 'ê' | 'ë' | 'ē' | 'ę' | 'ě' => 'e',
+        'ğ' | 'ģ' => 'g',
+        'í' | 'ì' | 'î' | 'ï' | 'ī' | 'ı' => 'i',
+        'ķ' => 'k',
+        'ļ' | 'ł' => 'l',
+        'ñ' | 'ń' | 'ņ' | 'ň' => 'n',
+        'ó' | 'ò' | 'ô' | 'ö' | 'õ' | 'ø' | 'ō' | 'ő' => 'o',
+        'ř' => 'r',
+        'ś' | 'š' | 'ş' => 's',
+        'ť' | 'ţ' => 't',
+        'ú' | 'ù' | 'û' | 'ü' | 'ū' | 'ů' | 'ű' => 'u',
+        'ý' | 'ÿ' => 'y',
+        'ź' | 'ż' | 'ž' => 'z',
+        c => c,
+    }
+}
+
+/// the number of letters that have to be added, removed or changed to turn `a` into `b`.
+fn levenshtein(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+    let mut row = vec![0; b.len() + 1];
+
+    for (i, ca) in a.chars().enumerate() {
+        row[0] = i + 1;
+
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + (ca != *cb) as usize;
+
+            row[j + 1] = substitution.min(prev[j + 1] + 1).min(row[j] + 1);
+        }
+
+        std::mem::swap(&mut prev, &mut row);
+    }
+
+    prev[b.len()]
+}
+
+#[cfg(test)]
+mod test_name_search {
+    use super::{levenshtein, name_match, normalize, NameMatch};
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(normalize("  Nikola  Jokić "), "nikola jokic");
+        assert_eq!(normalize("J.J. Barea"), "jj barea");
+        assert_eq!(normalize("Shaquille O'Neal"), "shaquille oneal");
+        assert_eq!(normalize("Karl-Anthony Towns"), "karl anthony towns");
+    }
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("curry", "curry"), 0);
+    }
+
+    #[test]
+    fn test_name_match() {
+        assert_eq!(
+            name_match("lebron james", "LeBron James"),
+            Some(NameMatch::Exact)
+        );
+        assert_eq!(name_match("james", "LeBron James"), Some(NameMatch::Words));
+        assert_eq!(
+            name_match("steph cur", "Stephen Curry"),
+            Some(NameMatch::Prefix)
+        );
+        assert_eq!(
+            name_match("bron", "LeBron James"),
+            Some(NameMatch::Contains)
+        );
+        assert_eq!(name_match("doncic", "Luka Dončić"), Some(NameMatch::Words));
+        assert_eq!(
+            name_match("giannis antetokounpo", "Giannis Antetokounmpo"),
+            Some(NameMatch::Typo(1))
+        );
+        assert_eq!(name_match("jordan", "LeBron James"), None);
+        assert_eq!(name_match("", "LeBron James"), None);
+    }
+
+    #[test]
+    fn test_better_matches_sort_first() {
+        assert!(NameMatch::Exact < NameMatch::Words);
+        assert!(NameMatch::Contains < NameMatch::Typo(0));
+        assert!(NameMatch::Typo(1) < NameMatch::Typo(2));
+    }
+}
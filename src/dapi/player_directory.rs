@@ -1,31 +1,147 @@
-use crate::types::PlayerId;
-use crate::types::PlayerName;
+use crate::dapi::directory::DirectoryError;
+use crate::dapi::name_search::{name_match, NameMatch};
+use crate::dapi::write::write_serializable_with_directory;
 
-use std::collections::HashMap;
+use crate::format::path_manager::nba_player_directory_file;
 
-#[derive(Debug, Clone)]
+use crate::stats::game_obj::GameObject;
+
+use crate::storage;
+
+use crate::types::{GameDate, PlayerId, PlayerName, TeamId};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
+
+/// ## PlayerEntry
+///
+/// a player's name and career as far as the stored volumes go.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PlayerEntry {
+    pub name: PlayerName,
+    pub first_game: Option<GameDate>,
+    pub last_game: Option<GameDate>,
+    pub games: u32,
+    /// every team the player appeared for, in the order they first did.
+    pub teams: Vec<TeamId>,
+}
+
+impl PlayerEntry {
+    fn new(name: PlayerName) -> Self {
+        PlayerEntry {
+            name,
+            first_game: None,
+            last_game: None,
+            games: 0,
+            teams: Vec::new(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct PlayerDirectory {
-    map: HashMap<PlayerId, PlayerName>,
+    players: BTreeMap<PlayerId, PlayerEntry>,
 }
 
 impl PlayerDirectory {
-    pub fn load() -> Self {
-        todo!();
+    /// the directory `chronicle_nba` saved.
+    pub fn load() -> Result<Self, DirectoryError> {
+        let path = nba_player_directory_file();
+
+        let contents = storage::read_to_string(&path)
+            .map_err(|e| DirectoryError::ReadError(path.clone(), e))?;
+
+        serde_json::from_str(&contents).map_err(|e| DirectoryError::ParseError(path, e))
+    }
+
+    pub fn save(&self) -> Result<(), DirectoryError> {
+        let path = nba_player_directory_file();
+
+        write_serializable_with_directory(&path, self)
+            .map_err(|e| DirectoryError::WriteError(path, e))
     }
 
     pub fn get(&self, pid: PlayerId) -> Option<&PlayerName> {
-        self.map.get(&pid)
+        self.players.get(&pid).map(|entry| &entry.name)
+    }
+
+    pub fn entry(&self, pid: PlayerId) -> Option<&PlayerEntry> {
+        self.players.get(&pid)
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+
+    /// every player whose name matches `query`, best match first and then by games played.
+    pub fn search(&self, query: &str) -> Vec<(PlayerId, NameMatch)> {
+        let mut matches = self
+            .players
+            .iter()
+            .filter_map(|(id, entry)| Some((*id, name_match(query, &entry.name.0)?)))
+            .collect::<Vec<_>>();
+
+        matches
+            .sort_by_key(|(id, name_match)| (*name_match, -(self.players[id].games as i64), *id));
+
+        matches
+    }
+
+    /// the players that match `name` best, e.g. everyone named `name` or, if nobody is, everyone
+    /// whose name starts with it.
+    pub fn find(&self, name: &str) -> Vec<PlayerId> {
+        let matches = self.search(name);
+
+        let Some((_, best)) = matches.first().copied() else {
+            return Vec::new();
+        };
+
+        let mut ids = matches
+            .into_iter()
+            .take_while(|(_, name_match)| *name_match == best)
+            .map(|(id, _)| id)
+            .collect::<Vec<_>>();
+
+        ids.sort();
+        ids
     }
 
     pub fn insert(&mut self, pid: PlayerId, name: PlayerName) {
-        let _ = self.map.insert(pid, name);
+        self.players
+            .entry(pid)
+            .and_modify(|entry| entry.name = name.clone())
+            .or_insert_with(|| PlayerEntry::new(name));
     }
-}
 
-impl Default for PlayerDirectory {
-    fn default() -> Self {
-        Self {
-            map: Default::default(),
+    /// add everyone who played in `game` to the directory, or extend their careers to it.
+    pub fn record_game(&mut self, game: &GameObject) {
+        let date = game.game_date();
+
+        for side in [game.away(), game.home()] {
+            for player in side.roster_box_scores() {
+                let entry = self
+                    .players
+                    .entry(player.player_id())
+                    .or_insert_with(|| PlayerEntry::new(player.player_name().clone()));
+
+                // the name of the latest game is the one a player is known by
+                if entry.last_game.is_none_or(|last| last <= date) {
+                    entry.name = player.player_name().clone();
+                }
+
+                entry.first_game = Some(entry.first_game.map_or(date, |first| first.min(date)));
+                entry.last_game = Some(entry.last_game.map_or(date, |last| last.max(date)));
+                entry.games += 1;
+
+                if !entry.teams.contains(&side.team_id()) {
+                    entry.teams.push(side.team_id());
+                }
+            }
         }
     }
 }
@@ -1,15 +1,29 @@
-use crate::format::path_manager::nba_storage_path;
+use crate::checksum::checksum_map::DataClass;
+use crate::checksum::sign::sign_file;
+
+use crate::dapi::volume::{
+    decode_volume, upgrade_legacy_volume, VolumeError, VolumeHeader, VOLUME_FORMAT_VERSION,
+    VOLUME_HEADER_LEN,
+};
+
+use crate::format::path_manager::{nba_storage_path, universal_nba_storage_path};
 
 use crate::stats::game_obj::GameObject;
 
 use crate::stats::season_period::minimum_spanning_era;
+
+use crate::storage;
+
 use crate::types::SeasonId;
 
 use NBAReadError::*;
 
+use once_cell::sync::Lazy;
+
+use std::collections::HashMap;
 use std::fmt::Display;
-use std::fs;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex, MutexGuard, Weak};
 
 use thiserror::Error;
 
@@ -31,20 +45,115 @@ pub fn read_nba_year(year: i32) -> Result<Vec<GameObject>, NBAReadError> {
 pub fn read_nba_season(season_id: SeasonId) -> Result<Vec<GameObject>, NBAReadError> {
     let path = nba_storage_path(season_id);
 
-    let content = fs::read(&path).map_err(|e| FileReadError(e, path.clone()))?;
+    let content = storage::read(&path).map_err(|e| FileReadError(e, path.clone()))?;
+
+    match decode_volume(&content) {
+        Err(VolumeError::Legacy) => migrate_legacy_volume(season_id, &path, &content),
+        games => games.map_err(|e| VolumeFormatError(e, path.clone())),
+    }
+}
+
+/// add a header to a volume written before volumes had one and save it, so it is only upgraded
+/// once. a volume that no longer reads as the current schema has to be regenerated by a sync.
+fn migrate_legacy_volume(
+    season_id: SeasonId,
+    path: &PathBuf,
+    content: &[u8],
+) -> Result<Vec<GameObject>, NBAReadError> {
+    let upgraded = upgrade_legacy_volume(content)
+        .ok_or_else(|| VolumeFormatError(VolumeError::Legacy, path.clone()))?;
+
+    match storage::write(path, &upgraded) {
+        Ok(_) => {
+            log::info!("✅ upgraded {season_id} volume to format v{VOLUME_FORMAT_VERSION}");
+
+            let signed = sign_file(
+                DataClass::Volumes,
+                universal_nba_storage_path(season_id),
+                path,
+            );
+
+            if let Err(e) = signed {
+                log::warn!("{e}\n⚠️ failed to re-sign the upgraded {season_id} volume");
+            }
+        }
+        Err(e) => log::warn!("⚠️ {e}\n⚠️ failed to save the upgraded {season_id} volume"),
+    }
+
+    decode_volume(&upgraded).map_err(|e| VolumeFormatError(e, path.clone()))
+}
+
+/// just the header of a stored volume, without reading the games. `None` if the volume predates
+/// headers.
+pub fn read_nba_volume_header(season_id: SeasonId) -> Result<Option<VolumeHeader>, NBAReadError> {
+    let path = nba_storage_path(season_id);
+
+    let bytes =
+        storage::read_prefix(&path, VOLUME_HEADER_LEN).map_err(|e| FileReadError(e, path))?;
+
+    match bytes.len() {
+        VOLUME_HEADER_LEN => Ok(VolumeHeader::from_bytes(&bytes)),
+        _ => Ok(None),
+    }
+}
 
-    let games = wincode::deserialize::<Vec<GameObject>>(&content)
-        .map_err(|e| WincodeParseError(e, path.clone()))?;
+/// volumes that are in use in this process. loads of a season while another copy is still in use
+/// (e.g. every iteration of a parameter search) share that copy instead of re-reading the file.
+static SEASON_CACHE: Lazy<Mutex<HashMap<SeasonId, CachedSeason>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// a season that was read, and the stamp of its volume when it was. only a weak reference is kept
+/// so long running processes don't hold on to every season they have ever read.
+struct CachedSeason {
+    stamp: String,
+    games: Weak<Vec<GameObject>>,
+}
+
+/// the games of a season, shared with any other copy still in use as long as the volume hasn't been
+/// rewritten since, e.g. by a sync in another process.
+pub fn read_nba_season_shared(season_id: SeasonId) -> Result<Arc<Vec<GameObject>>, NBAReadError> {
+    let path = nba_storage_path(season_id);
+
+    let stamp = storage::stamp(&path).map_err(|e| FileReadError(e, path.clone()))?;
+
+    let cached = season_cache()
+        .get(&season_id)
+        .filter(|cached| cached.stamp == stamp)
+        .and_then(|cached| cached.games.upgrade());
+
+    if let Some(games) = cached {
+        return Ok(games);
+    }
+
+    let games = Arc::new(read_nba_season(season_id)?);
+
+    season_cache().insert(
+        season_id,
+        CachedSeason {
+            stamp,
+            games: Arc::downgrade(&games),
+        },
+    );
 
     Ok(games)
 }
 
+/// drop a season from the cache once its volume has been rewritten.
+pub fn evict_nba_season(season_id: SeasonId) {
+    season_cache().remove(&season_id);
+}
+
+fn season_cache() -> MutexGuard<'static, HashMap<SeasonId, CachedSeason>> {
+    SEASON_CACHE.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
 #[derive(Error, Debug)]
 pub enum NBAReadError {
     DirectoryError(std::io::Error, PathBuf),
     FileReadError(std::io::Error, PathBuf),
     WincodeParseError(wincode::ReadError, PathBuf),
     FileEntryError(std::io::Error, PathBuf),
+    VolumeFormatError(VolumeError, PathBuf),
 }
 
 impl Display for NBAReadError {
@@ -78,6 +187,13 @@ impl Display for NBAReadError {
                     path.display()
                 )
             }
+            NBAReadError::VolumeFormatError(e, path) => {
+                write!(
+                    f,
+                    "{e}\n❌ file: {}\n❌ volume is out of date. run `warheads sync` to migrate it",
+                    path.display()
+                )
+            }
         }
     }
 }
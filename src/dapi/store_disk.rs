@@ -1,25 +1,27 @@
+use crate::dapi::read_disk::evict_nba_season;
+use crate::dapi::volume::encode_volume;
+
 use crate::format::path_manager::nba_storage_path;
 
 use crate::stats::game_obj::GameObject;
 
+use crate::storage;
+
 use crate::types::SeasonId;
 
 use std::fmt::Display;
-use std::fs;
 use thiserror::Error;
 
 pub fn save_nba_games(season_id: SeasonId, games: &[GameObject]) -> Result<(), SaveGameError> {
-    use SaveGameError::{CreateDirectoryError, FileWriteError, WincodeSerializationError};
+    use SaveGameError::{FileWriteError, WincodeSerializationError};
 
-    let contents = wincode::serialize(&games).map_err(|e| WincodeSerializationError(e))?;
+    let contents = encode_volume(games).map_err(|e| WincodeSerializationError(e))?;
 
     let path = nba_storage_path(season_id);
 
-    fs::create_dir_all(&path.parent().unwrap()).map_err(|e| CreateDirectoryError(e))?;
+    storage::write(&path, contents).map_err(|e| FileWriteError(e))?;
 
-    let path = nba_storage_path(season_id);
-
-    fs::write(&path, contents).map_err(|e| FileWriteError(e))?;
+    evict_nba_season(season_id);
 
     Ok(())
 }
@@ -27,7 +29,6 @@ pub fn save_nba_games(season_id: SeasonId, games: &[GameObject]) -> Result<(), S
 #[derive(Debug, Error)]
 pub enum SaveGameError {
     WincodeSerializationError(wincode::WriteError),
-    CreateDirectoryError(std::io::Error),
     FileWriteError(std::io::Error),
 }
 
@@ -37,9 +38,6 @@ impl Display for SaveGameError {
             SaveGameError::WincodeSerializationError(e) => {
                 write!(f, "❌ {}\n❌ failed to serialize TeamBoxScore as binary", e)
             }
-            SaveGameError::CreateDirectoryError(e) => {
-                write!(f, "❌ {}\n❌ failed to create directory", e)
-            }
             SaveGameError::FileWriteError(e) => {
                 write!(f, "❌ {}\n❌ failed to write file", e)
             }
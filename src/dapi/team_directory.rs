@@ -1,10 +1,98 @@
-use crate::types::{TeamAbbreviation, TeamId, TeamName};
+use crate::dapi::directory::DirectoryError;
+use crate::dapi::name_search::{name_match, NameMatch};
+use crate::dapi::write::write_serializable_with_directory;
 
-type Assoc = (TeamId, TeamAbbreviation, TeamName);
+use crate::format::path_manager::nba_team_directory_file;
 
-#[derive(Debug, Clone)]
+use crate::stats::game_obj::GameObject;
+
+use crate::storage;
+
+use crate::types::{GameDate, TeamAbbreviation, TeamId, TeamName};
+
+use serde::{Deserialize, Serialize};
+
+/// ## TeamIdentity
+///
+/// the abbreviation and name a franchise played under from its first to its last game with them.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct TeamIdentity {
+    pub abbr: TeamAbbreviation,
+    pub name: TeamName,
+    pub first_game: GameDate,
+    pub last_game: GameDate,
+}
+
+/// ## Franchise
+///
+/// a team and every identity it has had, e.g. the Seattle SuperSonics and the Oklahoma City
+/// Thunder.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Franchise {
+    pub team_id: TeamId,
+    /// oldest first.
+    pub identities: Vec<TeamIdentity>,
+}
+
+impl Franchise {
+    /// the identity the franchise played under on `date`, or the last one before it.
+    pub fn identity_on(&self, date: GameDate) -> Option<&TeamIdentity> {
+        self.identities
+            .iter()
+            .rev()
+            .find(|identity| identity.first_game <= date)
+    }
+
+    /// the identity the franchise played its latest game under.
+    pub fn current(&self) -> Option<&TeamIdentity> {
+        self.identities
+            .iter()
+            .max_by_key(|identity| identity.last_game)
+    }
+
+    /// extend the identity `abbr` and `name` to `date`, unless the franchise played under another
+    /// identity in between. then it's a new identity, e.g. the Charlotte Hornets after the Bobcats.
+    fn record(&mut self, abbr: &TeamAbbreviation, name: &TeamName, date: GameDate) {
+        let others = self
+            .identities
+            .iter()
+            .filter(|other| other.abbr != *abbr || other.name != *name)
+            .map(|other| (other.first_game, other.last_game))
+            .collect::<Vec<_>>();
+
+        let continues = |identity: &TeamIdentity| {
+            let (from, to) = match date < identity.first_game {
+                true => (date, identity.first_game),
+                false => (identity.last_game, date),
+            };
+
+            !others
+                .iter()
+                .any(|(first_game, last_game)| *first_game < to && *last_game > from)
+        };
+
+        match self.identities.iter_mut().find(|identity| {
+            identity.abbr == *abbr && identity.name == *name && continues(identity)
+        }) {
+            Some(identity) => {
+                identity.first_game = identity.first_game.min(date);
+                identity.last_game = identity.last_game.max(date);
+            }
+            None => self.identities.push(TeamIdentity {
+                abbr: abbr.clone(),
+                name: name.clone(),
+                first_game: date,
+                last_game: date,
+            }),
+        }
+
+        self.identities.sort_by_key(|identity| identity.first_game);
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
 pub struct TeamDirectory {
-    teams: Vec<Assoc>,
+    teams: Vec<Franchise>,
 }
 
 impl TeamDirectory {
@@ -12,21 +100,112 @@ impl TeamDirectory {
         Self { teams: vec![] }
     }
 
-    pub fn insert(&mut self, a: Assoc) {
-        match self.teams.binary_search_by_key(&a.0, |a| a.0) {
-            Ok(index) => {
-                self.teams[index] = a;
-            }
+    /// the directory `chronicle_nba` saved.
+    pub fn load() -> Result<Self, DirectoryError> {
+        let path = nba_team_directory_file();
+
+        let contents = storage::read_to_string(&path)
+            .map_err(|e| DirectoryError::ReadError(path.clone(), e))?;
+
+        serde_json::from_str(&contents).map_err(|e| DirectoryError::ParseError(path, e))
+    }
+
+    pub fn save(&self) -> Result<(), DirectoryError> {
+        let path = nba_team_directory_file();
+
+        write_serializable_with_directory(&path, self)
+            .map_err(|e| DirectoryError::WriteError(path, e))
+    }
+
+    pub fn franchise(&self, team_id: TeamId) -> Option<&Franchise> {
+        self.teams
+            .binary_search_by_key(&team_id, |franchise| franchise.team_id)
+            .ok()
+            .map(|index| &self.teams[index])
+    }
+
+    pub fn franchises(&self) -> impl Iterator<Item = &Franchise> {
+        self.teams.iter()
+    }
+
+    /// every franchise that has played as `query`, by abbreviation or name, best match first.
+    pub fn search(&self, query: &str) -> Vec<(TeamId, NameMatch)> {
+        let mut matches = self
+            .teams
+            .iter()
+            .filter_map(|franchise| {
+                let best = franchise
+                    .identities
+                    .iter()
+                    .filter_map(|identity| {
+                        match identity.abbr.0.eq_ignore_ascii_case(query.trim()) {
+                            true => Some(NameMatch::Exact),
+                            false => name_match(query, &identity.name.0),
+                        }
+                    })
+                    .min()?;
+
+                Some((franchise.team_id, best))
+            })
+            .collect::<Vec<_>>();
+
+        matches.sort_by_key(|(id, name_match)| (*name_match, *id));
+
+        matches
+    }
+
+    /// the franchises that match `query` best.
+    pub fn find(&self, query: &str) -> Vec<TeamId> {
+        let matches = self.search(query);
+
+        let Some((_, best)) = matches.first().copied() else {
+            return Vec::new();
+        };
+
+        matches
+            .into_iter()
+            .take_while(|(_, name_match)| *name_match == best)
+            .map(|(id, _)| id)
+            .collect()
+    }
+
+    /// add the identity `team_id` played under on `date`, or extend it to `date`.
+    pub fn record(
+        &mut self,
+        team_id: TeamId,
+        abbr: &TeamAbbreviation,
+        name: &TeamName,
+        date: GameDate,
+    ) {
+        let index = match self
+            .teams
+            .binary_search_by_key(&team_id, |franchise| franchise.team_id)
+        {
+            Ok(index) => index,
             Err(index) => {
-                self.teams.insert(index, a);
+                self.teams.insert(
+                    index,
+                    Franchise {
+                        team_id,
+                        identities: Vec::new(),
+                    },
+                );
+                index
             }
-        }
+        };
+
+        self.teams[index].record(abbr, name, date);
     }
-}
 
-impl Default for TeamDirectory {
-    fn default() -> Self {
-        Self::new()
+    pub fn record_game(&mut self, game: &GameObject) {
+        for side in [game.away(), game.home()] {
+            self.record(
+                side.team_id(),
+                &side.team_abbr(),
+                &side.team_name(),
+                game.game_date(),
+            );
+        }
     }
 }
 
@@ -38,22 +217,111 @@ fn gen_dict() {
 
     let mut directory = TeamDirectory::new();
 
-    for (card, _) in games {
-        let home = card.home();
-        let away = card.away();
+    for (_, game) in games {
+        directory.record_game(&game);
+    }
+}
+
+#[cfg(test)]
+mod test_team_directory {
+    use super::TeamDirectory;
+
+    use crate::types::{GameDate, TeamAbbreviation, TeamId, TeamName};
+
+    use std::str::FromStr;
+
+    fn directory() -> TeamDirectory {
+        let mut directory = TeamDirectory::new();
+
+        let mut play = |abbr: &str, name: &str, date: &str| {
+            directory.record(
+                TeamId(1610612760),
+                &TeamAbbreviation(abbr.to_owned()),
+                &TeamName(name.to_owned()),
+                GameDate::from_str(date).unwrap(),
+            )
+        };
+
+        play("OKC", "Oklahoma City Thunder", "2008-10-29");
+        play("SEA", "Seattle SuperSonics", "1967-10-13");
+        play("SEA", "Seattle SuperSonics", "2008-04-16");
+        play("OKC", "Oklahoma City Thunder", "2024-04-14");
 
-        let a = (
-            home.team_id(),
-            home.team_abbr().clone(),
-            home.team_name().clone(),
+        directory.record(
+            TeamId(1610612738),
+            &TeamAbbreviation("BOS".to_owned()),
+            &TeamName("Boston Celtics".to_owned()),
+            GameDate::from_str("2024-04-14").unwrap(),
         );
-        let b = (
-            away.team_id(),
-            away.team_abbr().clone(),
-            away.team_name().clone(),
+
+        directory
+    }
+
+    #[test]
+    fn test_franchise_keeps_every_identity() {
+        let directory = directory();
+
+        let thunder = directory.franchise(TeamId(1610612760)).unwrap();
+
+        assert_eq!(thunder.identities.len(), 2);
+        assert_eq!(thunder.identities[0].abbr.0, "SEA");
+        assert_eq!(
+            thunder.identities[0].last_game,
+            GameDate::from_str("2008-04-16").unwrap()
+        );
+        assert_eq!(thunder.current().unwrap().abbr.0, "OKC");
+
+        let in_2000 = thunder.identity_on(GameDate::from_str("2000-01-01").unwrap());
+
+        assert_eq!(in_2000.unwrap().name.0, "Seattle SuperSonics");
+    }
+
+    #[test]
+    fn test_returning_identity_is_a_new_identity() {
+        let mut directory = TeamDirectory::new();
+
+        let mut play = |name: &str, date: &str| {
+            directory.record(
+                TeamId(1610612766),
+                &TeamAbbreviation("CHA".to_owned()),
+                &TeamName(name.to_owned()),
+                GameDate::from_str(date).unwrap(),
+            )
+        };
+
+        play("Charlotte Hornets", "1988-11-04");
+        play("Charlotte Hornets", "2002-04-17");
+        play("Charlotte Bobcats", "2004-11-04");
+        play("Charlotte Bobcats", "2014-04-16");
+        play("Charlotte Hornets", "2014-10-29");
+        play("Charlotte Hornets", "2024-04-14");
+
+        let hornets = directory.franchise(TeamId(1610612766)).unwrap();
+
+        assert_eq!(hornets.identities.len(), 3);
+
+        let name_on = |date: &str| {
+            hornets
+                .identity_on(GameDate::from_str(date).unwrap())
+                .map(|identity| identity.name.0.clone())
+        };
+
+        assert_eq!(name_on("1995-01-01").as_deref(), Some("Charlotte Hornets"));
+        assert_eq!(name_on("2010-01-01").as_deref(), Some("Charlotte Bobcats"));
+        assert_eq!(name_on("2020-01-01").as_deref(), Some("Charlotte Hornets"));
+        assert_eq!(
+            hornets.current().unwrap().first_game,
+            GameDate::from_str("2014-10-29").unwrap()
         );
+    }
+
+    #[test]
+    fn test_find_by_old_abbreviation_or_name() {
+        let directory = directory();
 
-        directory.insert(a);
-        directory.insert(b);
+        assert_eq!(directory.find("sea"), vec![TeamId(1610612760)]);
+        assert_eq!(directory.find("supersonics"), vec![TeamId(1610612760)]);
+        assert_eq!(directory.find("celtics"), vec![TeamId(1610612738)]);
+        assert!(directory.find("lakers").is_empty());
     }
 }
@@ -0,0 +1,236 @@
+use crate::stats::game_obj::GameObject;
+
+use thiserror::Error;
+
+/// the first bytes of every volume written with a header.
+pub const VOLUME_MAGIC: [u8; 4] = *b"WHVL";
+
+/// the layout of the header. bump this when the header itself changes.
+pub const VOLUME_FORMAT_VERSION: u16 = 1;
+
+/// magic, format version, schema version and record count.
+pub const VOLUME_HEADER_LEN: usize = 4 + 2 + 8 + 8;
+
+/// the layout of `GameObject` and everything inside it, as written into volumes. bump this whenever
+/// a stored type adds, removes, reorders or retypes a field, so old volumes are regenerated instead
+/// of misread. `test_schema_version_matches_layout` fails until it is bumped.
+pub const SCHEMA_VERSION: u64 = 1;
+
+/// the header at the start of a volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeHeader {
+    pub version: u16,
+    pub schema: u64,
+    pub count: u64,
+}
+
+impl VolumeHeader {
+    pub fn current(count: usize) -> Self {
+        VolumeHeader {
+            version: VOLUME_FORMAT_VERSION,
+            schema: SCHEMA_VERSION,
+            count: count as u64,
+        }
+    }
+
+    /// whether a volume with this header can be read without migrating it.
+    pub fn is_current(&self) -> bool {
+        self.version == VOLUME_FORMAT_VERSION && self.schema == SCHEMA_VERSION
+    }
+
+    pub fn to_bytes(&self) -> [u8; VOLUME_HEADER_LEN] {
+        let mut bytes = [0u8; VOLUME_HEADER_LEN];
+
+        bytes[0..4].copy_from_slice(&VOLUME_MAGIC);
+        bytes[4..6].copy_from_slice(&self.version.to_le_bytes());
+        bytes[6..14].copy_from_slice(&self.schema.to_le_bytes());
+        bytes[14..22].copy_from_slice(&self.count.to_le_bytes());
+
+        bytes
+    }
+
+    /// the header at the start of `bytes`, or `None` if the volume predates headers.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        if bytes.len() < VOLUME_HEADER_LEN || bytes[0..4] != VOLUME_MAGIC {
+            return None;
+        }
+
+        Some(VolumeHeader {
+            version: u16::from_le_bytes(bytes[4..6].try_into().ok()?),
+            schema: u64::from_le_bytes(bytes[6..14].try_into().ok()?),
+            count: u64::from_le_bytes(bytes[14..22].try_into().ok()?),
+        })
+    }
+}
+
+/// the bytes of a volume: a header followed by the wincode games.
+pub fn encode_volume(games: &[GameObject]) -> Result<Vec<u8>, wincode::WriteError> {
+    let payload = wincode::serialize(&games)?;
+
+    let mut bytes = Vec::with_capacity(VOLUME_HEADER_LEN + payload.len());
+
+    bytes.extend_from_slice(&VolumeHeader::current(games.len()).to_bytes());
+    bytes.extend_from_slice(&payload);
+
+    Ok(bytes)
+}
+
+/// the games in a volume, checking that it was written in the current format and schema.
+pub fn decode_volume(bytes: &[u8]) -> Result<Vec<GameObject>, VolumeError> {
+    let header = VolumeHeader::from_bytes(bytes).ok_or(VolumeError::Legacy)?;
+
+    if header.version > VOLUME_FORMAT_VERSION {
+        return Err(VolumeError::UnsupportedVersion(header.version));
+    }
+
+    if header.schema != SCHEMA_VERSION {
+        return Err(VolumeError::SchemaMismatch {
+            found: header.schema,
+            expected: SCHEMA_VERSION,
+        });
+    }
+
+    let games = wincode::deserialize::<Vec<GameObject>>(&bytes[VOLUME_HEADER_LEN..])
+        .map_err(VolumeError::ParseError)?;
+
+    if games.len() as u64 != header.count {
+        return Err(VolumeError::CountMismatch {
+            found: games.len() as u64,
+            expected: header.count,
+        });
+    }
+
+    Ok(games)
+}
+
+/// rewrite a volume from before headers were added with a header, if it still deserializes as the
+/// current schema. a volume only counts as current if writing its games back out reproduces the
+/// original bytes exactly; anything else has to be regenerated from source.
+pub fn upgrade_legacy_volume(bytes: &[u8]) -> Option<Vec<u8>> {
+    let games = wincode::deserialize::<Vec<GameObject>>(bytes).ok()?;
+
+    let reserialized = wincode::serialize(&games).ok()?;
+
+    if reserialized != bytes {
+        return None;
+    }
+
+    encode_volume(&games).ok()
+}
+
+#[derive(Debug, Error)]
+pub enum VolumeError {
+    #[error("❌ volume was written before volumes had headers")]
+    Legacy,
+    #[error("❌ volume format version {0} is newer than this version of warheads supports")]
+    UnsupportedVersion(u16),
+    #[error(
+        "❌ volume schema version {found} does not match the current schema version {expected}"
+    )]
+    SchemaMismatch { found: u64, expected: u64 },
+    #[error("❌ volume header records {expected} games but {found} were read")]
+    CountMismatch { found: u64, expected: u64 },
+    #[error("❌ {0}\n❌ failed to parse volume as wincode binary")]
+    ParseError(wincode::ReadError),
+}
+
+impl VolumeError {
+    /// whether the volume can be rebuilt from source by this version of warheads.
+    pub fn is_regenerable(&self) -> bool {
+        !matches!(self, VolumeError::UnsupportedVersion(_))
+    }
+}
+
+#[cfg(test)]
+mod test_volume {
+    use super::*;
+
+    #[test]
+    fn test_header_round_trip() {
+        let header = VolumeHeader::current(1230);
+
+        assert_eq!(VolumeHeader::from_bytes(&header.to_bytes()), Some(header));
+    }
+
+    #[test]
+    fn test_empty_volume_round_trip() {
+        let bytes = encode_volume(&[]).unwrap();
+
+        assert_eq!(decode_volume(&bytes).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_legacy_volume_upgrade() {
+        let legacy = wincode::serialize(&Vec::<GameObject>::new()).unwrap();
+
+        assert!(matches!(decode_volume(&legacy), Err(VolumeError::Legacy)));
+
+        let upgraded = upgrade_legacy_volume(&legacy).unwrap();
+
+        assert_eq!(decode_volume(&upgraded).unwrap().len(), 0);
+    }
+
+    #[test]
+    fn test_schema_mismatch() {
+        let mut header = VolumeHeader::current(0);
+        header.schema ^= 1;
+
+        let mut bytes = header.to_bytes().to_vec();
+        bytes.extend(wincode::serialize(&Vec::<GameObject>::new()).unwrap());
+
+        assert!(matches!(
+            decode_volume(&bytes),
+            Err(VolumeError::SchemaMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_schema_version_matches_layout() {
+        use crate::checksum::checksum::sha256;
+
+        use serde_json::json;
+
+        let box_score = |wl: &str, pts: u64| {
+            json!({
+                "wl": wl, "min": 240, "fgm": 40, "fga": 80, "fg3m": 12, "fg3a": 30, "ftm": 20,
+                "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8, "blk": 5,
+                "tov": 12, "pf": 20, "pts": pts, "plus_minus": -6
+            })
+        };
+
+        let team = |id: u64, abbr: &str, visiting: &str, wl: &str, pts: u64| {
+            json!({
+                "team_id": id,
+                "team_abbreviation": abbr,
+                "team_name": format!("{abbr} Team"),
+                "visiting": visiting,
+                "roster": [{
+                    "player_id": id * 10,
+                    "player_name": "One",
+                    "box_score": box_score(wl, pts),
+                }],
+                "box_score": box_score(wl, pts),
+            })
+        };
+
+        let game: GameObject = serde_json::from_value(json!({
+            "season_id": "22015",
+            "game_date": "2016-01-10",
+            "game_id": "0021500001",
+            "home": team(1, "BOS", "Home", "L", 118),
+            "away": team(2, "LAL", "Away", "W", 124),
+        }))
+        .unwrap();
+
+        let layout = sha256(&wincode::serialize(&vec![game]).unwrap());
+
+        assert_eq!(
+            (SCHEMA_VERSION, layout.as_str()),
+            (
+                1,
+                "beddf4742db338418971b12a279233d092a63ee6ca355ddb2dd2394ab2a74ca2"
+            ),
+            "💀 the volume layout changed. bump SCHEMA_VERSION and record the new layout here"
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::dapi::from_value::FromValue;
 
 use crate::edit::edit_builder::EditBuilder;
 use crate::edit::edit_loader::load_edit_list;
+use crate::edit::provenance::Provenance;
 
 use crate::format::language::Columnizable;
 
@@ -42,6 +43,9 @@ pub struct Edit {
     pub delete: bool,
 
     pub corrections: HashMap<StatColumn, Value>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provenance: Vec<Provenance>,
 }
 
 impl Edit {
@@ -134,7 +138,7 @@ impl Edit {
                 Team,
             ) => apply_corrections(&mut columns, &self.corrections, team_column_index).unwrap(),
             (_ls, k) => {
-                eprintln!("{k} columns string was not formatted correctly");
+                log::warn!("{k} columns string was not formatted correctly");
 
                 game.to_string()
             }
@@ -146,7 +150,7 @@ impl Edit {
             .retain(|col, val| match game.try_set_col(col, val) {
                 Ok(_) => false, //remove entries that are not successfully applied
                 Err(stat_column) => {
-                    println!("Error setting column {stat_column}");
+                    log::error!("Error setting column {stat_column}");
                     true
                 }
             });
@@ -300,6 +304,52 @@ impl Edit {
         for (k, v) in other_fields {
             self.corrections.insert(k, v);
         }
+
+        for p in other.provenance {
+            if !self.provenance.contains(&p) {
+                self.provenance.push(p);
+            }
+        }
+    }
+
+    /// attach `provenance` to the edit's audit trail unless the same record is already present.
+    pub fn attribute(&mut self, provenance: Provenance) {
+        if !provenance.is_empty() && !self.provenance.contains(&provenance) {
+            self.provenance.push(provenance);
+        }
+    }
+
+    /// a multi-line description of the edit, each corrected column and every provenance record
+    /// that contributed to it.
+    pub fn blame(&self) -> String {
+        let mut s = format!("{self}");
+
+        let mut columns = self.corrections.keys().collect::<Vec<_>>();
+
+        columns.sort();
+
+        for col in columns {
+            let corrected = &self.corrections[col];
+
+            let original = self
+                .provenance
+                .iter()
+                .find_map(|p| p.original.get(col))
+                .map(|v| v.to_string())
+                .unwrap_or_else(|| "?".to_string());
+
+            s.push_str(&format!("\n\t{col}: {original} → {corrected}"));
+        }
+
+        if self.provenance.is_empty() {
+            s.push_str("\n\t🚫 no provenance recorded");
+        }
+
+        for p in &self.provenance {
+            s.push_str(&format!("\n\t{}", p.to_string().replace('\n', "\n\t")));
+        }
+
+        s
     }
 
     pub fn correct_matchup(&mut self, visiting: &mut Visiting, team_abbr: &TeamAbbreviation) {
@@ -1,4 +1,5 @@
 use crate::edit::edit::Edit;
+use crate::edit::provenance::Provenance;
 
 use crate::format::percent::PercentGeneric;
 
@@ -10,6 +11,7 @@ use crate::stats::types::BoolInt;
 use crate::tui::prompter::prompt_and_delete;
 use crate::tui::prompter::prompt_and_select;
 use crate::tui::prompter::prompt_and_validate;
+use crate::tui::prompter::prompt_optional;
 use crate::tui::prompter::prompt_with_options;
 
 use crate::types::GameDate;
@@ -51,6 +53,7 @@ impl EditBuilder {
                 team_abbr: team_abbr.clone(),
                 delete: false,
                 corrections: HashMap::new(),
+                provenance: Vec::new(),
             },
             display: None,
         }
@@ -64,6 +67,10 @@ impl EditBuilder {
         self.edit.corrections.insert(col, val);
     }
 
+    pub fn attribute(&mut self, provenance: Provenance) {
+        self.edit.attribute(provenance);
+    }
+
     pub fn remove(&mut self, col: StatColumn) {
         self.edit.corrections.remove(&col);
     }
@@ -89,8 +96,10 @@ impl EditBuilder {
 
         println!("{}", display_string);
 
+        let mut provenance = Provenance::local();
+
         if self.edit.delete {
-            println!("🗑️ deleting {}", self.edit.identity());
+            log::info!("🗑️ deleting {}", self.edit.identity());
 
             return;
         } else {
@@ -99,7 +108,10 @@ impl EditBuilder {
             self.edit.set_delete(delete);
 
             if delete {
-                println!("🗑️ deleting {}", self.edit.identity());
+                log::info!("🗑️ deleting {}", self.edit.identity());
+
+                provenance.reason = prompt_optional("reason for deleting (optional)");
+                self.edit.attribute(provenance);
 
                 return;
             }
@@ -118,6 +130,8 @@ impl EditBuilder {
                 println!("\x1b[90m{}: {}\x1b[0m", col, val);
                 stdout.flush().unwrap();
 
+                provenance.record_original(col, val.clone());
+
                 /*
 
                 this will be separated by both section and type
@@ -151,7 +165,7 @@ impl EditBuilder {
                                 (display.matchup(), tm),
                             )
                         } else {
-                            println!("❌ cannot correct matchup. assigning new matchup to Null");
+                            log::error!("❌ cannot correct matchup. assigning new matchup to Null");
 
                             Value::Null
                         }
@@ -218,6 +232,11 @@ impl EditBuilder {
                 println!("{s}");
             }
         }
+
+        provenance.reason = prompt_optional("reason for correction (optional)");
+        provenance.source = prompt_optional("source url (optional)");
+
+        self.edit.attribute(provenance);
     }
 
     /// Returns whether the correction builder has any corrections to apply. It does not specify whether the record should be deleted.
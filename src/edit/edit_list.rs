@@ -3,7 +3,7 @@ use std::fs;
 use serde::Deserialize;
 
 use crate::{
-    edit::edit::Edit,
+    edit::{edit::Edit, provenance::Provenance},
     format::path_manager,
     stats::identity::{Identifiable, Identity},
     types::{GameId, TeamAbbreviation},
@@ -66,6 +66,28 @@ impl EditList {
         None
     }
 
+    /// all edits made to the game with `game_id`, team edits before player edits.
+    pub fn game(&self, game_id: GameId) -> Vec<&Edit> {
+        let mut edits = self
+            .edits
+            .iter()
+            .filter(|edit| edit.game_id == game_id)
+            .collect::<Vec<_>>();
+
+        edits.sort_by_key(|edit| edit.player_id.is_some());
+
+        edits
+    }
+
+    /// attribute every edit that has no provenance of its own to `provenance`.
+    pub fn attribute(&mut self, provenance: &Provenance) {
+        for edit in self.edits.iter_mut() {
+            if edit.provenance.is_empty() {
+                edit.attribute(provenance.clone());
+            }
+        }
+    }
+
     pub(crate) fn merge(&mut self, new: EditList) {
         for edit in new.into_edits() {
             self.insert(edit);
@@ -98,7 +120,7 @@ mod test_edit_list {
     use crate::edit::edit_loader::{load_edit_list, save_edit_list};
     use crate::stats::stat_column::StatColumn;
 
-    use crate::types::{GameDate, GameId, SeasonId, TeamAbbreviation, TeamId};
+    use crate::types::{GameDate, GameId, PlayerId, SeasonId, TeamAbbreviation, TeamId};
 
     use super::*;
 
@@ -118,6 +140,7 @@ mod test_edit_list {
                     (StatColumn::PTS, Value::from(110)),
                     (StatColumn::REB, Value::from(45)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100003),
@@ -132,6 +155,7 @@ mod test_edit_list {
                     (StatColumn::PTS, Value::from(120)),
                     (StatColumn::REB, Value::from(48)),
                 ]),
+                provenance: Vec::new(),
             },
         ];
 
@@ -150,6 +174,7 @@ mod test_edit_list {
                 (StatColumn::PTS, Value::from(115)),
                 (StatColumn::REB, Value::from(52)),
             ]),
+            provenance: Vec::new(),
         };
 
         edits.insert(x);
@@ -157,6 +182,36 @@ mod test_edit_list {
         assert!(edits.edits.is_sorted())
     }
 
+    #[test]
+    fn test_game_team_edits_first() {
+        let edit = |team_id: u64, abbr: &str, player_id: Option<u64>| Edit {
+            game_id: GameId(0020100001),
+            game_date: GameDate::ymd(2022, 1, 15).expect("Failed to create GameDate"),
+            season: SeasonId::from(22021),
+            player_id: player_id.map(PlayerId),
+            team_id: TeamId(team_id),
+            team_abbr: TeamAbbreviation::from_str(abbr).expect("Failed to create TeamAbbreviation"),
+            delete: false,
+            corrections: HashMap::from([(StatColumn::PTS, Value::from(10))]),
+            provenance: Vec::new(),
+        };
+
+        let mut edits = EditList::new(Vec::new());
+
+        edits.insert(edit(1, "LAL", Some(2544)));
+        edits.insert(edit(1, "LAL", None));
+        edits.insert(edit(3, "BOS", Some(1628369)));
+        edits.insert(edit(3, "BOS", None));
+
+        let kinds = edits
+            .game(GameId(0020100001))
+            .iter()
+            .map(|edit| edit.player_id.is_some())
+            .collect::<Vec<_>>();
+
+        assert_eq!(kinds, vec![false, false, true, true]);
+    }
+
     #[test]
     fn test_edit_list_lifecycle() {
         let result = load_edit_list();
@@ -98,6 +98,6 @@ mod load_edits {
             .map(|e| (e.identity(), e.clone()))
             .collect::<HashMap<_, _>>();
 
-        println!("edits loading v2: {:?}", start_new.elapsed());
+        log::debug!("edits loading v2: {:?}", start_new.elapsed());
     }
 }
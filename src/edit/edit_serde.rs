@@ -2,16 +2,18 @@ use crate::stats::stat_column::StatColumn;
 #[allow(unused_imports)]
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
+/// serializes the columns in `StatColumn` order so that the same map always produces the same
+/// json. keys use the same names as the derived `Deserialize` so the map round trips.
 #[allow(dead_code)] // required for serialize
 pub fn serialize<S>(cs: &HashMap<StatColumn, Value>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
 {
     cs.iter()
-        .map(|(key, value)| (format!("{}", key), value.clone()))
-        .collect::<HashMap<String, Value>>()
+        .map(|(&key, value)| (key, value.clone()))
+        .collect::<BTreeMap<StatColumn, Value>>()
         .serialize(serializer)
 }
 
@@ -9,4 +9,5 @@ mod edit_serde;
 pub mod editor;
 pub mod ord;
 mod overwrite;
+pub mod provenance;
 mod tests;
@@ -68,6 +68,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(110)),
                     (StatColumn::REB, Value::from(45)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100001),
@@ -82,6 +83,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(28)),
                     (StatColumn::AST, Value::from(7)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100001),
@@ -96,6 +98,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(22)),
                     (StatColumn::REB, Value::from(11)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100001),
@@ -110,6 +113,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(108)),
                     (StatColumn::AST, Value::from(28)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100001),
@@ -124,6 +128,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(32)),
                     (StatColumn::FG3M, Value::from(6)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100001),
@@ -138,6 +143,7 @@ mod test_edit_order {
                     (StatColumn::AST, Value::from(9)),
                     (StatColumn::STL, Value::from(3)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -152,6 +158,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(115)),
                     (StatColumn::REB, Value::from(52)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -166,6 +173,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(30)),
                     (StatColumn::REB, Value::from(8)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -180,6 +188,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(25)),
                     (StatColumn::AST, Value::from(6)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -194,6 +203,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(112)),
                     (StatColumn::BLK, Value::from(8)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -208,6 +218,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(27)),
                     (StatColumn::AST, Value::from(8)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100002),
@@ -222,6 +233,7 @@ mod test_edit_order {
                     (StatColumn::BLK, Value::from(4)),
                     (StatColumn::REB, Value::from(13)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100003),
@@ -236,6 +248,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(120)),
                     (StatColumn::REB, Value::from(48)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100003),
@@ -250,6 +263,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(35)),
                     (StatColumn::REB, Value::from(9)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100003),
@@ -264,6 +278,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(125)),
                     (StatColumn::BLK, Value::from(9)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100003),
@@ -278,6 +293,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(30)),
                     (StatColumn::AST, Value::from(9)),
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100004),
@@ -292,6 +308,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(150)), // Changed PTS value for team statistics
                     (StatColumn::REB, Value::from(40)),  // Changed REB value for team statistics
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100004),
@@ -306,6 +323,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(40)), // Changed PTS value for player statistics
                     (StatColumn::AST, Value::from(25)), // Changed AST value for player statistics
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100004),
@@ -320,6 +338,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(160)), // Changed PTS value for team statistics
                     (StatColumn::REB, Value::from(45)),  // Changed REB value for team statistics
                 ]),
+                provenance: Vec::new(),
             },
             Edit {
                 game_id: GameId(0020100004),
@@ -334,6 +353,7 @@ mod test_edit_order {
                     (StatColumn::PTS, Value::from(30)), // Changed PTS value for player statistics
                     (StatColumn::AST, Value::from(15)), // Changed AST value for player statistics
                 ]),
+                provenance: Vec::new(),
             },
         ];
 
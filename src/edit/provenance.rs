@@ -0,0 +1,97 @@
+use crate::stats::stat_column::StatColumn;
+
+use chrono::{DateTime, Utc};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// ## Provenance
+///
+/// `Provenance` records where the values in an `Edit` came from. every field is optional so that
+/// edits written before provenance existed (and the remote annotation file) still deserialize.
+///
+/// an `Edit` keeps a trail of these, one per change, in the order they were applied. the trail is
+/// carried through `Edit::merge` so that merging the remote annotations into the local list does
+/// not lose who made a correction.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct Provenance {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<DateTime<Utc>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+
+    /// the value of each corrected column before the edit was applied.
+    #[serde(
+        default,
+        with = "super::edit_serde",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub original: HashMap<StatColumn, Value>,
+}
+
+impl Provenance {
+    /// provenance for an edit made by hand on this machine, stamped with the current user and time.
+    pub fn local() -> Self {
+        Provenance {
+            author: std::env::var("USER").ok(),
+            timestamp: Some(Utc::now()),
+            ..Default::default()
+        }
+    }
+
+    /// provenance for edits that were downloaded from `url`. there is no timestamp so that
+    /// attributing the same remote file twice produces the same record.
+    pub fn remote(url: &str) -> Self {
+        Provenance {
+            source: Some(url.to_owned()),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_reason(mut self, reason: &str) -> Self {
+        self.reason = Some(reason.to_owned());
+
+        self
+    }
+
+    pub fn record_original(&mut self, col: StatColumn, val: Value) {
+        self.original.entry(col).or_insert(val);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == Provenance::default()
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "👤 {}\t🕒 {}",
+            self.author.as_deref().unwrap_or("unknown"),
+            self.timestamp
+                .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+                .unwrap_or_else(|| "unknown".to_string())
+        )?;
+
+        if let Some(reason) = &self.reason {
+            write!(f, "\n📝 {reason}")?;
+        }
+
+        if let Some(source) = &self.source {
+            write!(f, "\n🔗 {source}")?;
+        }
+
+        Ok(())
+    }
+}
@@ -369,6 +369,7 @@ mod correct_columns {
             team_abbr: TeamAbbreviation("LOL".to_string()),
             delete: false,
             corrections: HashMap::new(),
+            provenance: Vec::new(),
         }
     }
 
@@ -408,6 +409,7 @@ mod serialize_corrections {
 
                 cs
             },
+            provenance: Vec::new(),
         };
 
         let serialized = serde_json::to_string(&correction).unwrap();
@@ -417,3 +419,88 @@ mod serialize_corrections {
         pretty_assertions::assert_eq!(serialized, expected.trim_end());
     }
 }
+
+#[cfg(test)]
+mod edit_provenance {
+    use crate::edit::edit::Edit;
+    use crate::edit::edit_list::EditList;
+    use crate::edit::provenance::Provenance;
+
+    use crate::stats::stat_column::StatColumn;
+
+    use crate::types::{GameId, SeasonId, TeamId};
+
+    use std::collections::HashMap;
+
+    use serde_json::{json, Value};
+
+    fn sample_edit(provenance: Vec<Provenance>) -> Edit {
+        Edit {
+            game_id: GameId::from("0029600001"),
+            game_date: "1996-11-01".parse().unwrap(),
+            season: SeasonId::from(21996),
+            player_id: None,
+            team_id: TeamId::from(1610612760),
+            team_abbr: "SEA".parse().unwrap(),
+            delete: false,
+            corrections: HashMap::from([(StatColumn::PTS, json!(101))]),
+            provenance,
+        }
+    }
+
+    fn sample_provenance() -> Provenance {
+        Provenance {
+            author: Some("daus".to_string()),
+            timestamp: Some("2025-12-31T12:00:00Z".parse().unwrap()),
+            reason: Some("box score total did not match play by play".to_string()),
+            source: Some("https://www.basketball-reference.com".to_string()),
+            original: HashMap::from([(StatColumn::PTS, Value::Null)]),
+        }
+    }
+
+    #[test]
+    fn test_provenance_round_trip() {
+        let edit = sample_edit(vec![sample_provenance()]);
+
+        let serialized = serde_json::to_string(&edit).unwrap();
+        let deserialized: Edit = serde_json::from_str(&serialized).unwrap();
+
+        pretty_assertions::assert_eq!(deserialized.provenance, edit.provenance);
+    }
+
+    #[test]
+    fn test_deserialize_without_provenance() {
+        let json = r#"{"game_id":"0029600001","game_date":"1996-11-01","season":"21996","team_id":1610612760,"player_id":null,"team_abbr":"SEA","delete":false,"corrections":{"PTS":101}}"#;
+
+        let edit: Edit = serde_json::from_str(json).unwrap();
+
+        assert!(edit.provenance.is_empty());
+    }
+
+    #[test]
+    fn test_merge_keeps_provenance() {
+        let remote = Provenance::remote("https://example.com/edits.json");
+
+        let mut list = EditList::new(vec![sample_edit(vec![sample_provenance()])]);
+
+        let mut new = EditList::new(vec![sample_edit(vec![])]);
+        new.attribute(&remote);
+
+        list.merge(new.clone());
+        list.merge(new);
+
+        let edit = &list.list()[0];
+
+        pretty_assertions::assert_eq!(edit.provenance, vec![sample_provenance(), remote]);
+    }
+
+    #[test]
+    fn test_blame_shows_original_value() {
+        let edit = sample_edit(vec![sample_provenance()]);
+
+        let blame = edit.blame();
+
+        assert!(blame.contains("pts: null → 101"));
+        assert!(blame.contains("👤 daus"));
+    }
+}
@@ -0,0 +1,90 @@
+use crate::dapi::directory::career;
+use crate::dapi::name_search::NameMatch;
+use crate::dapi::player_directory::PlayerDirectory;
+use crate::dapi::team_directory::{Franchise, TeamDirectory};
+
+use crate::format::bar;
+
+use crate::types::{GameDate, PlayerId, TeamId};
+
+/// the players matching `query`, with their careers and the teams they played for.
+pub fn player_matches(
+    query: &str,
+    matches: &[(PlayerId, NameMatch)],
+    players: &PlayerDirectory,
+    teams: &TeamDirectory,
+) -> String {
+    let mut s = format!("📖 players matching {query}\n");
+
+    s.push_str(&format!(
+        "  {:<28}{:>10}{:>12}{:>8}  {}\n",
+        "player", "id", "career", "games", "teams"
+    ));
+    s.push_str(&bar(80));
+
+    for (player_id, _) in matches {
+        let Some(entry) = players.entry(*player_id) else {
+            continue;
+        };
+
+        let played_for = entry
+            .teams
+            .iter()
+            .map(|team_id| team_abbr(teams, *team_id, entry.last_game))
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        s.push_str(&format!(
+            "\n  {:<28}{:>10}{:>12}{:>8}  {}",
+            entry.name.to_string(),
+            player_id.to_string(),
+            career(entry.first_game, entry.last_game),
+            entry.games,
+            played_for
+        ));
+    }
+
+    s
+}
+
+/// every name and abbreviation a franchise has played under, oldest first.
+pub fn franchise_history(franchise: &Franchise) -> String {
+    let title = franchise
+        .current()
+        .map(|identity| identity.name.0.clone())
+        .unwrap_or_default();
+
+    let mut s = format!("📜 {title} ({})\n", franchise.team_id);
+
+    s.push_str(&format!(
+        "  {:<8}{:<32}{:>12}{:>12}\n",
+        "abbr", "name", "first game", "last game"
+    ));
+    s.push_str(&bar(64));
+
+    for identity in &franchise.identities {
+        s.push_str(&format!(
+            "\n  {:<8}{:<32}{:>12}{:>12}",
+            identity.abbr.0,
+            identity.name.0,
+            format!("{:?}", identity.first_game),
+            format!("{:?}", identity.last_game)
+        ));
+    }
+
+    s
+}
+
+/// the abbreviation a team played under at a player's last game, or its id if it isn't in the
+/// directory.
+fn team_abbr(teams: &TeamDirectory, team_id: TeamId, last_game: Option<GameDate>) -> String {
+    let Some(franchise) = teams.franchise(team_id) else {
+        return team_id.to_string();
+    };
+
+    last_game
+        .and_then(|date| franchise.identity_on(date))
+        .or_else(|| franchise.current())
+        .map(|identity| identity.abbr.0.clone())
+        .unwrap_or_else(|| team_id.to_string())
+}
@@ -0,0 +1,37 @@
+use crate::format::bar;
+
+use crate::proc::grade::{ModelGrade, Scorecard};
+
+/// a model's live record, overall, by run and by month.
+pub fn grade_table(grade: &ModelGrade) -> String {
+    let mut s = format!("📖 {}\n", grade.model_name);
+
+    s.push_str(&format!(
+        "  {:<22}{:>8}{:>12}{:>12}{:>12}\n",
+        "", "games", "accuracy", "log loss", "brier"
+    ));
+    s.push_str(&bar(68));
+
+    s.push_str(&row("overall", &grade.overall));
+
+    for (run_id, scorecard) in &grade.runs {
+        s.push_str(&row(&format!("run {run_id}"), scorecard));
+    }
+
+    for ((year, month), scorecard) in &grade.months {
+        s.push_str(&row(&format!("{year}-{month:02}"), scorecard));
+    }
+
+    s
+}
+
+fn row(label: &str, scorecard: &Scorecard) -> String {
+    format!(
+        "\n  {:<22}{:>8}{:>11.1}%{:>12.4}{:>12.4}",
+        label,
+        scorecard.games(),
+        scorecard.accuracy() * 100.0,
+        scorecard.log_loss(),
+        scorecard.brier()
+    )
+}
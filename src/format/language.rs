@@ -126,7 +126,7 @@ pub fn box_score_value_to_string(value: &Value) -> String {
              )
         }
         _ => {
-            eprintln!("⚠️couldn't parse box score! unrecognized JSON format.");
+            log::warn!("⚠️couldn't parse box score! unrecognized JSON format.");
 
             String::new()
         }
@@ -1,6 +1,8 @@
 pub mod box_score_formatter;
+pub mod directory_formatter;
 pub mod extract;
 pub mod game_object_formatter;
+pub mod grade_formatter;
 pub mod language;
 pub mod parse;
 pub mod path_manager;
@@ -10,6 +12,10 @@ pub use space::*;
 pub mod evaluation;
 pub use evaluation::evaluation;
 pub mod percent;
+pub mod prediction_formatter;
+pub mod query_formatter;
+pub mod rating_formatter;
+pub mod registry_formatter;
 pub mod stat_path_formatter;
 mod tests;
 pub mod url_format;
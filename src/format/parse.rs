@@ -18,7 +18,7 @@ pub fn value_to_date(value: &Value) -> Option<NaiveDate> {
     match value {
         Value::String(s) => NaiveDate::parse_from_str(&*s, "%Y-%m-%d").ok(),
         _ => {
-            eprintln!("⚠️ JSON Value to parse GameDate from is not a String. ");
+            log::warn!("⚠️ JSON Value to parse GameDate from is not a String. ");
 
             None
         }
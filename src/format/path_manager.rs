@@ -1,4 +1,4 @@
-use crate::constants::paths::data;
+use crate::constants::config::config;
 
 use crate::format::season::season_path;
 use crate::format::stat_path_formatter::StatPathFormatter as SPF;
@@ -9,19 +9,9 @@ use crate::stats::nba_kind::NBAStatKind;
 
 use crate::types::{GameDate, SeasonId};
 
-use once_cell::sync::Lazy;
-use std::path::PathBuf;
+use chrono::{DateTime, Utc};
 
-/// this is the environment variable for the data path as per system
-///
-/// an example is:
-///
-///     let path = "/Users/daus/Documents/warheads/data";
-///
-/// is functionally equivalent to accessing *DATA when in scope
-///
-
-static DATA: Lazy<String> = Lazy::new(data);
+use std::path::PathBuf;
 
 /// `nba_source_path` returns the PathBuf to the location of the raw nba data for its relevant domain.
 ///
@@ -29,9 +19,8 @@ static DATA: Lazy<String> = Lazy::new(data);
 ///
 /// `data/nba/source/{team or players}/{year}/{period}_{team or player}.json`
 pub fn nba_source_path(season: SeasonId, kind: NBAStatKind) -> PathBuf {
-    PathBuf::from(format!(
-        "{}/nba/source/{}/{}/{}_{}",
-        *DATA,
+    config().data.join(format!(
+        "nba/source/{}/{}/{}_{}",
         kind.path_specifier(),
         season_path(season),
         season.period().path_specifier(),
@@ -50,55 +39,156 @@ pub fn universal_nba_source_path(season: SeasonId, kind: NBAStatKind) -> PathBuf
 }
 
 pub fn nba_edit_file() -> PathBuf {
-    PathBuf::from(format!("{}/nba/edits.json", *DATA))
+    config().data.join(universal_nba_edit_file())
+}
+
+pub fn universal_nba_edit_file() -> PathBuf {
+    PathBuf::from("nba/edits.json")
 }
 
 /// `nba_storage_path` returns the PathBuf to the location of the processed nba data for storage on
 /// disk.
 pub fn nba_storage_path(season_id: SeasonId) -> PathBuf {
-    let s = format!(
-        "{}/nba/volumes/{}_{}.vol",
-        *DATA,
+    config().data.join(universal_nba_storage_path(season_id))
+}
+
+pub fn universal_nba_storage_path(season_id: SeasonId) -> PathBuf {
+    PathBuf::from(format!(
+        "nba/volumes/{}_{}.vol",
         season_id.year(),
         season_id.period().path_specifier()
-    );
+    ))
+}
 
-    PathBuf::from(s)
+/// `nba_lineage_path` is the manifest of the inputs a volume was built from, next to the volume.
+pub fn nba_lineage_path(season_id: SeasonId) -> PathBuf {
+    nba_storage_path(season_id).with_extension("lineage.json")
 }
 
 pub fn nba_checksum_file() -> PathBuf {
-    PathBuf::from(format!("{}/nba/checksums.json", *DATA))
+    config().data.join("nba/checksums.json")
+}
+
+/// `nba_player_directory_file` is every player's name and career, saved by `chronicle_nba`.
+pub fn nba_player_directory_file() -> PathBuf {
+    config().data.join(universal_nba_player_directory_file())
+}
+
+pub fn universal_nba_player_directory_file() -> PathBuf {
+    PathBuf::from("nba/directory/players.json")
+}
+
+/// `nba_team_directory_file` is every franchise's names and abbreviations, saved by `chronicle_nba`.
+pub fn nba_team_directory_file() -> PathBuf {
+    config().data.join(universal_nba_team_directory_file())
+}
+
+pub fn universal_nba_team_directory_file() -> PathBuf {
+    PathBuf::from("nba/directory/teams.json")
+}
+
+/// `daemon_jobs_file` is the schedule of jobs `warheads daemon` runs.
+pub fn daemon_jobs_file() -> PathBuf {
+    config().data.join("daemon.toml")
+}
+
+/// `daemon_status_file` is where `warheads daemon` reports how each job has gone.
+pub fn daemon_status_file() -> PathBuf {
+    config().data.join("daemon/status.json")
+}
+
+/// `nba_export_path` is where `warheads export` writes the `kind` box scores of the seasons
+/// starting from `first` through `last`.
+pub fn nba_export_path(kind: NBAStatKind, first: i32, last: i32, ext: &str) -> PathBuf {
+    config().data.join(format!(
+        "exports/nba_{}_{first}_{last}.{ext}",
+        kind.path_specifier()
+    ))
+}
+
+/// `cache_dir` is scratch space for downloaded and intermediate files that can be thrown away.
+pub fn cache_dir() -> PathBuf {
+    config().cache.clone()
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////
 //// Model Paths ///////////////////////////////////////////////////////////////////////////
 ////////////////////////////////////////////////////////////////////////////////////////////
 
+/// `models_root` is the directory every model's directory is created in.
+pub fn models_root() -> PathBuf {
+    config().models.clone()
+}
+
+/// `model_home` is the directory of every run of a model.
+pub fn model_home(model_name: &str) -> PathBuf {
+    config().models.join(model_name)
+}
+
+/// `model_dir` is where a model's artifacts are read and written: the directory of the model's run,
+/// or `model_home` for models trained before runs.
 pub fn model_dir<M: Model>(model: &M) -> PathBuf {
-    static DATA: Lazy<String> = Lazy::new(data);
+    let model_name = model.model_name();
 
-    PathBuf::from(format!("{}/nba/{}", *DATA, model.model_name()))
+    match model.run() {
+        Some(run_id) => model_run_dir(&model_name, run_id),
+        None => model_home(&model_name),
+    }
 }
 
-pub fn records_path<M: Model>(model: &M) -> PathBuf {
-    static DATA: Lazy<String> = Lazy::new(data);
+/// `data/nba/{model}/runs/{run_id}`
+pub fn model_run_dir(model_name: &str, run_id: &str) -> PathBuf {
+    model_home(model_name).join("runs").join(run_id)
+}
 
-    PathBuf::from(format!("{}/nba/{}/records.csv", *DATA, model.model_name()))
+/// the metadata of a training run.
+pub fn model_run_file(model_name: &str, run_id: &str) -> PathBuf {
+    model_run_dir(model_name, run_id).join("run.json")
+}
+
+/// the run `eval` and `forecast` use by default.
+pub fn promoted_run_file(model_name: &str) -> PathBuf {
+    model_home(model_name).join("promoted.json")
+}
+
+pub fn records_path<M: Model>(model: &M) -> PathBuf {
+    model_dir(model).join("records.csv")
 }
 
 /// results_path generates the path to where the model accuracy is stored.
 pub fn results_path<M: Model>(model: &M) -> PathBuf {
-    static DATA: Lazy<String> = Lazy::new(data);
-
-    PathBuf::from(format!("{}/nba/{}/results.json", *DATA, model.model_name()))
+    model_dir(model).join("results.json")
 }
 
-pub fn nba_prediction_file<M: Model>(model: &M, date: GameDate) -> PathBuf {
+/// every forecast a model made for games on `date`, from any run.
+pub fn nba_prediction_dir(model_name: &str, date: GameDate) -> PathBuf {
     let d = date.to_filename();
 
-    let mut path = PathBuf::from(format!("{}/nba/{}/predictions", *DATA, model.model_name()));
+    let mut path = nba_predictions_root(model_name);
 
     path.push(d);
 
     path
 }
+
+/// `data/nba/{model}/predictions`
+pub fn nba_predictions_root(model_name: &str) -> PathBuf {
+    model_home(model_name).join("predictions")
+}
+
+/// one forecast for games on `date`, named by when it was made and the run that made it so later
+/// forecasts don't overwrite earlier ones.
+///
+/// `data/nba/{model}/predictions/{yyyy_mm_dd}/{generated_at}_{run_id}.json`
+pub fn nba_prediction_file(
+    model_name: &str,
+    date: GameDate,
+    run_id: Option<&str>,
+    generated_at: DateTime<Utc>,
+) -> PathBuf {
+    nba_prediction_dir(model_name, date).join(format!(
+        "{}_{}.json",
+        generated_at.format("%Y%m%d-%H%M%S"),
+        run_id.unwrap_or("unversioned")
+    ))
+}
@@ -0,0 +1,86 @@
+use crate::format::bar;
+
+use crate::stats::prediction::Prediction;
+
+/// the probability bars `forecast` has always printed.
+pub fn predictions_table(predictions: &[Prediction]) -> String {
+    let mut s = bar(80);
+
+    for prediction in predictions {
+        s.push_str(&format!("\n{prediction}"));
+    }
+
+    s
+}
+
+/// predictions as csv, one row per game with both teams' win probability.
+pub fn predictions_csv(predictions: &[Prediction]) -> Result<String, csv::Error> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    wtr.write_record([
+        "GAME_DATE",
+        "GAME_ID",
+        "AWAY",
+        "HOME",
+        "AWAY_WIN_PROBABILITY",
+        "HOME_WIN_PROBABILITY",
+        "FAVORITE",
+    ])?;
+
+    for prediction in predictions {
+        let card = prediction.card();
+
+        wtr.write_record([
+            format!("{:?}", prediction.date()),
+            card.game_id().to_string(),
+            card.away().team_abbr().emphasize(),
+            card.home().team_abbr().emphasize(),
+            format!("{:.4}", 1.0 - prediction.probability()),
+            format!("{:.4}", prediction.probability()),
+            favorite(prediction),
+        ])?;
+    }
+
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// predictions as a json array.
+pub fn predictions_json(predictions: &[Prediction]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(predictions)
+}
+
+/// predictions as a markdown table, for pasting into an issue or a chat.
+pub fn predictions_markdown(predictions: &[Prediction]) -> String {
+    let mut s = String::from(
+        "| date | matchup | away | home | favorite |\n| --- | --- | ---: | ---: | --- |",
+    );
+
+    for prediction in predictions {
+        let card = prediction.card();
+
+        s.push_str(&format!(
+            "\n| {:?} | {} @ {} | {:.1}% | {:.1}% | {} |",
+            prediction.date(),
+            card.away().team_abbr().emphasize(),
+            card.home().team_abbr().emphasize(),
+            (1.0 - prediction.probability()) * 100.0,
+            prediction.probability() * 100.0,
+            favorite(prediction)
+        ));
+    }
+
+    s
+}
+
+fn favorite(prediction: &Prediction) -> String {
+    let card = prediction.card();
+
+    match prediction.probability() >= 0.5 {
+        true => card.home().team_abbr().emphasize(),
+        false => card.away().team_abbr().emphasize(),
+    }
+}
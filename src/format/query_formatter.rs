@@ -0,0 +1,121 @@
+use crate::format::bar;
+
+use crate::stats::game_query::{QueryRow, StatPredicate};
+use crate::stats::nba_schema::SCHEMA_COLUMNS;
+use crate::stats::stat_column::StatColumn::{self, *};
+
+/// a fixed width table of query results. shows points and result, plus every column a predicate
+/// filtered on.
+pub fn query_table(rows: &[QueryRow], predicates: &[StatPredicate]) -> String {
+    let mut columns = vec![WL, PTS];
+
+    for predicate in predicates {
+        if !columns.contains(&predicate.column) {
+            columns.push(predicate.column);
+        }
+    }
+
+    let players = rows.iter().any(|row| row.player_name.is_some());
+
+    let mut s = format!("{:<12}{:<22}{:<14}", "date", "season", "matchup");
+
+    if players {
+        s.push_str(&format!("{:<26}", "player"));
+    }
+
+    for col in &columns {
+        s.push_str(&format!("{:>8}", col.column_name()));
+    }
+
+    s.push('\n');
+    s.push_str(&bar(48 + if players { 26 } else { 0 } + 8 * columns.len()));
+
+    for row in rows {
+        s.push_str(&format!(
+            "\n{:<12}{:<22}{:<14}",
+            format!("{:?}", row.game_date),
+            row.season_id.to_string(),
+            row.matchup()
+        ));
+
+        if players {
+            let name = row.player_name.as_ref().map(|n| n.0.as_str());
+
+            s.push_str(&format!("{:<26}", name.unwrap_or_default()));
+        }
+
+        for col in &columns {
+            s.push_str(&format!("{:>8}", cell(row, *col)));
+        }
+    }
+
+    s
+}
+
+/// query results as csv, one row per team (or player) game, named by `StatColumn::column_name`.
+pub fn query_csv(rows: &[QueryRow]) -> Result<String, csv::Error> {
+    let players = rows.iter().any(|row| row.player_name.is_some());
+
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    let mut header = vec![
+        SEASON_ID.column_name(),
+        GAME_ID.column_name(),
+        GAME_DATE.column_name(),
+        TEAM_ABBREVIATION.column_name(),
+        MATCHUP.column_name(),
+    ];
+
+    if players {
+        header.push(PLAYER_NAME.column_name());
+    }
+
+    header.extend(SCHEMA_COLUMNS.iter().map(|(_, col)| col.column_name()));
+
+    wtr.write_record(&header)?;
+
+    for row in rows {
+        let mut record = vec![
+            (row.season_id.period().get_offset() + row.season_id.year()).to_string(),
+            row.game_id.to_string(),
+            format!("{:?}", row.game_date),
+            row.team.emphasize(),
+            row.matchup(),
+        ];
+
+        if players {
+            record.push(
+                row.player_name
+                    .as_ref()
+                    .map(|n| n.0.clone())
+                    .unwrap_or_default(),
+            );
+        }
+
+        record.extend(SCHEMA_COLUMNS.iter().map(|(_, col)| cell(row, *col)));
+
+        wtr.write_record(&record)?;
+    }
+
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// query results as a json array.
+pub fn query_json(rows: &[QueryRow]) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(rows)
+}
+
+fn cell(row: &QueryRow, col: StatColumn) -> String {
+    match col {
+        WL => row.box_score.wl().to_string(),
+        col => row
+            .box_score
+            .stat(col)
+            .map(|v| v.to_string())
+            .unwrap_or_default(),
+    }
+}
@@ -0,0 +1,200 @@
+use serde::Serialize;
+
+use crate::dapi::player_directory::PlayerDirectory;
+
+use crate::format::bar;
+use crate::format::season::season_fmt;
+
+use crate::ml::elo::rating_history::{RatedPlayer, RatingPoint};
+
+use crate::types::{GameDate, PlayerId};
+
+const SPARKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// how many characters wide a rating history's sparkline is.
+const SPARKLINE_WIDTH: usize = 72;
+
+/// `values` as a sparkline at most `width` wide. when there are more values than characters each
+/// character is the mean of the values it covers.
+pub fn sparkline(values: &[i64], width: usize) -> String {
+    if values.is_empty() || width == 0 {
+        return String::new();
+    }
+
+    let buckets = values.len().min(width);
+
+    let means = (0..buckets)
+        .map(|i| {
+            let bucket = &values[i * values.len() / buckets..(i + 1) * values.len() / buckets];
+
+            bucket.iter().sum::<i64>() as f64 / bucket.len() as f64
+        })
+        .collect::<Vec<_>>();
+
+    let low = means.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = means.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    means
+        .iter()
+        .map(|mean| {
+            if high == low {
+                return SPARKS[SPARKS.len() / 2];
+            }
+
+            SPARKS[(((mean - low) / (high - low)) * (SPARKS.len() - 1) as f64).round() as usize]
+        })
+        .collect()
+}
+
+/// a rating history as a sparkline, its peak and low, and where each season started and ended.
+pub fn rating_chart(title: &str, history: &[RatingPoint]) -> String {
+    let mut s = format!("📈 {title}\n");
+
+    let ratings = history.iter().map(|point| point.rating).collect::<Vec<_>>();
+
+    s.push_str(&sparkline(&ratings, SPARKLINE_WIDTH));
+
+    let (Some(peak), Some(low), Some(last)) = (
+        history.iter().max_by_key(|point| point.rating),
+        history.iter().min_by_key(|point| point.rating),
+        history.last(),
+    ) else {
+        return s;
+    };
+
+    s.push_str(&format!(
+        "\n  games: {}  peak: {} ({:?})  low: {} ({:?})  latest: {} ({:?})\n",
+        history.len(),
+        peak.rating,
+        peak.date,
+        low.rating,
+        low.date,
+        last.rating,
+        last.date
+    ));
+
+    s.push_str(&format!(
+        "  {:<12}{:>8}{:>8}{:>8}{:>8}{:>8}\n",
+        "season", "games", "start", "end", "high", "low"
+    ));
+    s.push_str(&bar(54));
+
+    for season in history.chunk_by(|a, b| a.season.year() == b.season.year()) {
+        let ratings = season.iter().map(|point| point.rating);
+
+        s.push_str(&format!(
+            "\n  {:<12}{:>8}{:>8}{:>8}{:>8}{:>8}",
+            season_fmt(season[0].season.year()),
+            season.len(),
+            season[0].rating,
+            season[season.len() - 1].rating,
+            ratings.clone().max().unwrap_or_default(),
+            ratings.min().unwrap_or_default()
+        ));
+    }
+
+    s
+}
+
+/// a rating history as csv, one row per game.
+pub fn rating_history_csv(history: &[RatingPoint]) -> Result<String, csv::Error> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    wtr.write_record(["GAME_DATE", "SEASON_ID", "GAME_ID", "RATING"])?;
+
+    for point in history {
+        wtr.write_record([
+            format!("{:?}", point.date),
+            (point.season.period().get_offset() + point.season.year()).to_string(),
+            point.game_id.to_string(),
+            point.rating.to_string(),
+        ])?;
+    }
+
+    into_string(wtr)
+}
+
+#[derive(Serialize)]
+struct RankedPlayer {
+    rank: usize,
+    player_id: PlayerId,
+    player_name: Option<String>,
+    rating: i64,
+    last_played: GameDate,
+}
+
+fn ranked<'a>(
+    top: &'a [RatedPlayer],
+    directory: &PlayerDirectory,
+) -> impl Iterator<Item = RankedPlayer> + 'a {
+    let names = top
+        .iter()
+        .map(|rated| directory.get(rated.player_id).map(|name| name.to_string()))
+        .collect::<Vec<_>>();
+
+    top.iter()
+        .zip(names)
+        .enumerate()
+        .map(|(i, (rated, player_name))| RankedPlayer {
+            rank: i + 1,
+            player_id: rated.player_id,
+            player_name,
+            rating: rated.rating,
+            last_played: rated.last_played,
+        })
+}
+
+/// the best rated players on `date`.
+pub fn top_table(date: GameDate, top: &[RatedPlayer], directory: &PlayerDirectory) -> String {
+    let mut s = format!("🏆 best rated players on {date:?}\n");
+
+    s.push_str(&format!(
+        "  {:<6}{:<28}{:>8}{:>14}\n",
+        "#", "player", "elo", "last played"
+    ));
+    s.push_str(&bar(56));
+
+    for player in ranked(top, directory) {
+        s.push_str(&format!(
+            "\n  {:<6}{:<28}{:>8}{:>14}",
+            player.rank,
+            player
+                .player_name
+                .unwrap_or_else(|| player.player_id.to_string()),
+            player.rating,
+            format!("{:?}", player.last_played)
+        ));
+    }
+
+    s
+}
+
+pub fn top_csv(top: &[RatedPlayer], directory: &PlayerDirectory) -> Result<String, csv::Error> {
+    let mut wtr = csv::Writer::from_writer(vec![]);
+
+    wtr.write_record(["RANK", "PLAYER_ID", "PLAYER_NAME", "RATING", "LAST_PLAYED"])?;
+
+    for player in ranked(top, directory) {
+        wtr.write_record([
+            player.rank.to_string(),
+            player.player_id.to_string(),
+            player.player_name.unwrap_or_default(),
+            player.rating.to_string(),
+            format!("{:?}", player.last_played),
+        ])?;
+    }
+
+    into_string(wtr)
+}
+
+pub fn top_json(top: &[RatedPlayer], directory: &PlayerDirectory) -> serde_json::Result<String> {
+    serde_json::to_string_pretty(&ranked(top, directory).collect::<Vec<_>>())
+}
+
+fn into_string(wtr: csv::Writer<Vec<u8>>) -> Result<String, csv::Error> {
+    let bytes = wtr
+        .into_inner()
+        .map_err(|e| csv::Error::from(e.into_error()))?;
+
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
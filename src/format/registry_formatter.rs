@@ -0,0 +1,93 @@
+use crate::format::bar;
+
+use crate::ml::registry::RunMetadata;
+
+/// a table of a model's runs, oldest first. the promoted run is marked with a `*`.
+pub fn runs_table(model_name: &str, runs: &[RunMetadata], promoted: Option<&str>) -> String {
+    let metrics =
+        runs.iter()
+            .flat_map(|run| run.metrics.keys())
+            .fold(Vec::new(), |mut metrics, metric| {
+                if !metrics.contains(&metric) {
+                    metrics.push(metric);
+                }
+                metrics
+            });
+
+    let mut s = format!("📖 {model_name}\n");
+
+    s.push_str(&format!(
+        "  {:<22}{:<22}{:>10}",
+        "run", "trained", "seconds"
+    ));
+
+    for metric in &metrics {
+        s.push_str(&format!("{:>14}", metric));
+    }
+
+    s.push('\n');
+    s.push_str(&bar(56 + 14 * metrics.len()));
+
+    for run in runs {
+        let marker = match promoted == Some(run.run_id.as_str()) {
+            true => "*",
+            false => " ",
+        };
+
+        s.push_str(&format!(
+            "\n{marker} {:<22}{:<22}{:>10.1}",
+            run.run_id,
+            run.trained_at.format("%Y-%m-%d %H:%M:%S").to_string(),
+            run.duration_ms as f64 / 1000.0
+        ));
+
+        for metric in &metrics {
+            match run.metrics.get(*metric) {
+                Some(value) => s.push_str(&format!("{:>14.4}", value)),
+                None => s.push_str(&format!("{:>14}", "")),
+            }
+        }
+    }
+
+    s
+}
+
+/// everything recorded about a run.
+pub fn run_summary(run: &RunMetadata, promoted: bool) -> String {
+    let mut s = format!("📖 {} run {}", run.model_name, run.run_id);
+
+    if promoted {
+        s.push_str(" (promoted)");
+    }
+
+    s.push_str(&format!(
+        "\n  trained:  {}\n  duration: {:.1}s\n  warheads: {}\n  volumes:  {}\n  edits:    {}",
+        run.trained_at.format("%Y-%m-%d %H:%M:%S UTC"),
+        run.duration_ms as f64 / 1000.0,
+        run.warheads_version,
+        run.data.volumes,
+        run.data.edits
+    ));
+
+    if let Some(last_game) = run.last_game {
+        s.push_str(&format!("\n  learned:  through {last_game}"));
+    }
+
+    if let Some(parent_run_id) = &run.parent_run_id {
+        s.push_str(&format!("\n  parent:   {parent_run_id}"));
+    }
+
+    s.push_str("\n  params:");
+
+    for (param, value) in &run.params {
+        s.push_str(&format!("\n    {param}: {value}"));
+    }
+
+    s.push_str("\n  metrics:");
+
+    for (metric, value) in &run.metrics {
+        s.push_str(&format!("\n    {metric}: {value}"));
+    }
+
+    s
+}
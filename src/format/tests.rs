@@ -1,11 +1,10 @@
 #[cfg(test)]
 mod test_path_manager {
     use crate::constants::paths::data;
-    use crate::format::path_manager::{nba_prediction_file, nba_storage_path};
+    use crate::format::path_manager::{nba_prediction_dir, nba_prediction_file, nba_storage_path};
     use crate::ml::model::{Model, TrainingError};
     use crate::stats::chronology::Chronology;
     use crate::types::{GameDate, SeasonId};
-    use once_cell::sync::Lazy;
     use std::collections::HashMap;
     use std::path::PathBuf;
 
@@ -13,9 +12,9 @@ mod test_path_manager {
 
     #[test]
     fn test_nba_storage_path() {
-        static DATA: Lazy<String> = Lazy::new(data);
+        let data = data().unwrap();
 
-        let expected_path = PathBuf::from(format!("{}/nba/volumes/2025_regularseason.vol", *DATA));
+        let expected_path = PathBuf::from(format!("{}/nba/volumes/2025_regularseason.vol", data.display()));
 
         let szn = SeasonId::from(22025);
 
@@ -26,36 +25,60 @@ mod test_path_manager {
 
     #[test]
     fn test_nba_prediction_file() {
-        static DATA: Lazy<String> = Lazy::new(data);
+        let data = data().unwrap();
 
         let expected_file =
-            PathBuf::from(format!("{}/nba/{}/predictions/2025_04_30", *DATA, MODEL));
+            PathBuf::from(format!("{}/nba/{}/predictions/2025_04_30", data.display(), MODEL));
 
-        let actual_file = nba_prediction_file(&TestModel, GameDate::ymd(2025, 4, 30).unwrap());
+        let actual_file = nba_prediction_dir(&TestModel.model_name(), GameDate::ymd(2025, 4, 30).unwrap());
+
+        assert_eq!(expected_file, actual_file);
+    }
+
+    #[test]
+    fn test_nba_prediction_file_is_versioned() {
+        use chrono::{TimeZone, Utc};
+
+        let data = data().unwrap();
+
+        let expected_file = PathBuf::from(format!(
+            "{}/nba/{}/predictions/2025_04_30/20250429-120000_20250101-000000.json",
+            data.display(),
+            MODEL
+        ));
+
+        let generated_at = Utc.with_ymd_and_hms(2025, 4, 29, 12, 0, 0).unwrap();
+
+        let actual_file = nba_prediction_file(
+            MODEL,
+            GameDate::ymd(2025, 4, 30).unwrap(),
+            Some("20250101-000000"),
+            generated_at,
+        );
 
         assert_eq!(expected_file, actual_file);
     }
 
     #[test]
     fn test_nba_prediction_file_short_day() {
-        static DATA: Lazy<String> = Lazy::new(data);
+        let data = data().unwrap();
 
         let expected_file =
-            PathBuf::from(format!("{}/nba/{}/predictions/2025_04_09", *DATA, MODEL));
+            PathBuf::from(format!("{}/nba/{}/predictions/2025_04_09", data.display(), MODEL));
 
-        let actual_file = nba_prediction_file(&TestModel, GameDate::ymd(2025, 4, 9).unwrap());
+        let actual_file = nba_prediction_dir(MODEL, GameDate::ymd(2025, 4, 9).unwrap());
 
         assert_eq!(expected_file, actual_file);
     }
 
     #[test]
     fn test_nba_prediction_file_short_month() {
-        static DATA: Lazy<String> = Lazy::new(data);
+        let data = data().unwrap();
 
         let expected_file =
-            PathBuf::from(format!("{}/nba/{}/predictions/2025_01_09", *DATA, MODEL));
+            PathBuf::from(format!("{}/nba/{}/predictions/2025_01_09", data.display(), MODEL));
 
-        let actual_file = nba_prediction_file(&TestModel, GameDate::ymd(2025, 1, 9).unwrap());
+        let actual_file = nba_prediction_dir(MODEL, GameDate::ymd(2025, 1, 9).unwrap());
 
         assert_eq!(expected_file, actual_file);
     }
@@ -84,3 +107,79 @@ mod test_path_manager {
         }
     }
 }
+
+#[cfg(test)]
+mod test_prediction_formatter {
+    use crate::format::prediction_formatter::{predictions_csv, predictions_markdown};
+    use crate::stats::gamecard::GameCard;
+    use crate::stats::prediction::Prediction;
+    use crate::stats::record::Record;
+    use crate::stats::season_period::SeasonPeriod;
+    use crate::stats::teamcard::TeamCard;
+    use crate::types::{GameDate, GameId, SeasonId, TeamAbbreviation, TeamId, TeamName};
+    use std::str::FromStr;
+
+    fn prediction(probability: f64) -> Prediction {
+        let team = |id: u64, name: &str, abbr: &str| {
+            TeamCard::new(
+                TeamId(id),
+                TeamName(name.to_owned()),
+                TeamAbbreviation::from_str(abbr).unwrap(),
+                Record::new(),
+            )
+        };
+
+        let card = GameCard::new(
+            GameId::from("0022400001"),
+            SeasonId::from((2024, SeasonPeriod::RegularSeason)),
+            GameDate::from_str("2024-10-22").unwrap(),
+            team(1610612738, "Boston Celtics", "BOS"),
+            team(1610612752, "New York Knicks", "NYK"),
+        );
+
+        Prediction::new(&card, probability)
+    }
+
+    #[test]
+    fn test_predictions_csv() {
+        let csv = predictions_csv(&[prediction(0.75)]).unwrap();
+
+        assert_eq!(
+            csv,
+            "GAME_DATE,GAME_ID,AWAY,HOME,AWAY_WIN_PROBABILITY,HOME_WIN_PROBABILITY,FAVORITE\n\
+             2024-10-22,0022400001,NYK,BOS,0.2500,0.7500,BOS\n"
+        );
+    }
+
+    #[test]
+    fn test_predictions_markdown() {
+        let markdown = predictions_markdown(&[prediction(0.4)]);
+
+        assert_eq!(
+            markdown.lines().last(),
+            Some("| 2024-10-22 | NYK @ BOS | 60.0% | 40.0% | NYK |")
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_rating_formatter {
+    use crate::format::rating_formatter::sparkline;
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[1, 2, 3, 4, 5, 6, 7, 8], 80), "▁▂▃▄▅▆▇█");
+        assert_eq!(sparkline(&[3000, 3000], 80), "▅▅");
+        assert_eq!(sparkline(&[], 80), "");
+    }
+
+    #[test]
+    fn test_sparkline_buckets_long_histories() {
+        let values = (0..100).collect::<Vec<i64>>();
+
+        let line = sparkline(&values, 10);
+
+        assert_eq!(line.chars().count(), 10);
+        assert!(line.starts_with('▁') && line.ends_with('█'));
+    }
+}
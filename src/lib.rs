@@ -1,13 +1,19 @@
 pub mod constants;
 
+pub mod daemon;
+
 pub mod edit;
 
 pub mod dapi;
 
 pub mod format;
 
+pub mod logging;
+
 pub mod stats;
 
+pub mod storage;
+
 pub mod tui;
 
 pub mod types;
@@ -17,3 +23,5 @@ pub mod ml;
 pub mod checksum;
 
 pub mod proc;
+
+pub mod serve;
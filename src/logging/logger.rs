@@ -0,0 +1,262 @@
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+use chrono::{SecondsFormat, Utc};
+
+use clap::ValueEnum;
+
+use log::kv::{self, Key, Value, VisitSource};
+use log::{Level, LevelFilter, Log, Metadata, Record};
+
+use serde_json::{Map, Number};
+
+use crate::tui::progress;
+
+/// the format of the installed logger.
+static FORMAT: OnceLock<LogFormat> = OnceLock::new();
+
+/// ## LogFormat
+///
+/// how log messages are written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum LogFormat {
+    /// the message with its emoji
+    #[default]
+    Human,
+    /// a line of json per message, for log aggregation
+    Json,
+}
+
+/// ## Logger
+///
+/// writes messages at `level` or above to stderr, so they stay out of the way of a command's
+/// output on stdout.
+pub struct Logger {
+    level: LevelFilter,
+    format: LogFormat,
+}
+
+impl Logger {
+    pub fn new(level: LevelFilter, format: LogFormat) -> Self {
+        Logger { level, format }
+    }
+
+    /// the line a record is written as.
+    pub fn render(&self, record: &Record) -> String {
+        match self.format {
+            LogFormat::Human => human(record),
+            LogFormat::Json => json(record),
+        }
+    }
+}
+
+impl Log for Logger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        metadata.level() <= self.level
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+
+        let line = self.render(record);
+
+        progress::suspend(|| {
+            let _ = writeln!(io::stderr().lock(), "{line}");
+        });
+    }
+
+    fn flush(&self) {
+        let _ = io::stderr().flush();
+    }
+}
+
+/// log through a `Logger` from now on. only the first call takes effect.
+pub fn init(level: LevelFilter, format: LogFormat) {
+    if log::set_boxed_logger(Box::new(Logger::new(level, format))).is_ok() {
+        log::set_max_level(level);
+
+        let _ = FORMAT.set(format);
+    }
+}
+
+/// the format messages are logged in, or `None` before the logger is installed.
+pub fn format() -> Option<LogFormat> {
+    FORMAT.get().copied()
+}
+
+/// the most detailed level logged given how many times `-v` and `-q` were passed. info by default.
+pub fn level_filter(verbose: u8, quiet: u8) -> LevelFilter {
+    match verbose as i16 - quiet as i16 {
+        ..=-3 => LevelFilter::Off,
+        -2 => LevelFilter::Error,
+        -1 => LevelFilter::Warn,
+        0 => LevelFilter::Info,
+        1 => LevelFilter::Debug,
+        _ => LevelFilter::Trace,
+    }
+}
+
+/// the emoji of a message at `level` that doesn't start with its own.
+fn level_emoji(level: Level) -> &'static str {
+    match level {
+        Level::Error => "❌",
+        Level::Warn => "⚠️",
+        Level::Info => "ℹ️ ",
+        Level::Debug => "🔍",
+        Level::Trace => "🔬",
+    }
+}
+
+/// whether `c` decorates the start of a message rather than being part of its text.
+fn is_decoration(c: char) -> bool {
+    c == 'ℹ' || (!c.is_ascii() && !c.is_alphanumeric())
+}
+
+/// the message as warheads has always printed it, with the emoji of its level if it has none.
+fn human(record: &Record) -> String {
+    let message = record.args().to_string();
+
+    match message.starts_with(is_decoration) {
+        true => message,
+        false => format!("{} {message}", level_emoji(record.level())),
+    }
+}
+
+/// a message without the emoji at the start of each of its lines.
+pub fn plain(message: &str) -> String {
+    message
+        .lines()
+        .map(|line| line.trim_start_matches(|c: char| is_decoration(c) || c.is_whitespace()))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// the record as a json object of its time, level, target, plain message and key values.
+fn json(record: &Record) -> String {
+    let mut line = Map::new();
+
+    line.insert(
+        "ts".to_owned(),
+        Utc::now()
+            .to_rfc3339_opts(SecondsFormat::Millis, true)
+            .into(),
+    );
+    line.insert(
+        "level".to_owned(),
+        record.level().as_str().to_ascii_lowercase().into(),
+    );
+    line.insert("target".to_owned(), record.target().into());
+    line.insert("msg".to_owned(), plain(&record.args().to_string()).into());
+
+    let _ = record.key_values().visit(&mut Fields(&mut line));
+
+    serde_json::Value::Object(line).to_string()
+}
+
+/// collects the key values of a record into a json object.
+struct Fields<'a>(&'a mut Map<String, serde_json::Value>);
+
+impl<'kvs> VisitSource<'kvs> for Fields<'_> {
+    fn visit_pair(&mut self, key: Key<'kvs>, value: Value<'kvs>) -> Result<(), kv::Error> {
+        self.0.insert(key.to_string(), json_value(&value));
+
+        Ok(())
+    }
+}
+
+fn json_value(value: &Value) -> serde_json::Value {
+    if let Some(b) = value.to_bool() {
+        return b.into();
+    }
+
+    if let Some(n) = value.to_u64() {
+        return n.into();
+    }
+
+    if let Some(n) = value.to_i64() {
+        return n.into();
+    }
+
+    match value.to_f64().and_then(Number::from_f64) {
+        Some(n) => n.into(),
+        None => value.to_string().into(),
+    }
+}
+
+#[cfg(test)]
+mod test_logger {
+    use super::{level_filter, plain, LogFormat, Logger};
+
+    use log::{Level, LevelFilter, Record};
+
+    #[test]
+    fn test_level_filter() {
+        assert_eq!(level_filter(0, 0), LevelFilter::Info);
+        assert_eq!(level_filter(1, 0), LevelFilter::Debug);
+        assert_eq!(level_filter(3, 0), LevelFilter::Trace);
+        assert_eq!(level_filter(0, 1), LevelFilter::Warn);
+        assert_eq!(level_filter(0, 2), LevelFilter::Error);
+        assert_eq!(level_filter(0, 5), LevelFilter::Off);
+        assert_eq!(level_filter(1, 1), LevelFilter::Info);
+    }
+
+    #[test]
+    fn test_human_format_keeps_emoji() {
+        let logger = Logger::new(LevelFilter::Trace, LogFormat::Human);
+
+        assert_eq!(
+            logger.render(
+                &Record::builder()
+                    .args(format_args!("✅ successfully trained elo-v1"))
+                    .level(Level::Info)
+                    .build()
+            ),
+            "✅ successfully trained elo-v1"
+        );
+        assert_eq!(
+            logger.render(
+                &Record::builder()
+                    .args(format_args!("failed to read 2015 regular season"))
+                    .level(Level::Warn)
+                    .build()
+            ),
+            "⚠️ failed to read 2015 regular season"
+        );
+    }
+
+    #[test]
+    fn test_json_format() {
+        let logger = Logger::new(LevelFilter::Trace, LogFormat::Json);
+
+        let kvs = [
+            ("job", log::kv::Value::from("sync")),
+            ("attempt", 2u32.into()),
+        ];
+
+        let line = logger.render(
+            &Record::builder()
+                .args(format_args!("❌ offline\n❌ sync failed"))
+                .level(Level::Error)
+                .target("warheads::daemon")
+                .key_values(&kvs)
+                .build(),
+        );
+
+        let line = serde_json::from_str::<serde_json::Value>(&line).unwrap();
+
+        assert_eq!(line["level"], "error");
+        assert_eq!(line["target"], "warheads::daemon");
+        assert_eq!(line["msg"], "offline\nsync failed");
+        assert_eq!(line["job"], "sync");
+        assert_eq!(line["attempt"], 2);
+        assert!(line["ts"].is_string());
+    }
+
+    #[test]
+    fn test_plain() {
+        assert_eq!(plain("ℹ️  using run 20250114"), "using run 20250114");
+        assert_eq!(plain("Nikola Jokić"), "Nikola Jokić");
+    }
+}
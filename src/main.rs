@@ -4,10 +4,10 @@ use warheads::proc::dispatch::Dispatch;
 async fn main() {
     match Dispatch::new().dispatch().await {
         Ok(_) => {
-            println!("✅ successfully completed command.\n👋 goodbye!")
+            log::info!("✅ successfully completed command.\n👋 goodbye!")
         }
         Err(e) => {
-            eprintln!("{}\n❌ failed to run command", e);
+            log::error!("{}\n❌ failed to run command", e);
         }
     }
 }
@@ -1,5 +1,6 @@
 use crate::ml::vector::Vector;
 
+#[derive(Clone)]
 pub(crate) struct EloParams {
     k: i64, //default step size
     f: f64,
@@ -1,40 +1,46 @@
 use EloWriterError::*;
 
 use std::fmt::Display;
-use std::fs::{self, File};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use csv::Writer;
 
 use crate::ml::elo::Elo;
 
+use crate::storage;
+
+/// writes elo records as csv. the records are buffered and written to storage by `finish`.
 pub struct EloWriter {
-    file: Writer<File>,
+    file: Writer<Vec<u8>>,
+    path: PathBuf,
 }
 
 impl EloWriter {
     pub fn new<P: AsRef<Path>>(path: P) -> Result<Self, EloWriterError> {
-        let filename = path.as_ref();
-
-        if !filename.is_file() {
-            fs::create_dir_all(filename.parent().unwrap_or(Path::new("/")))
-                .map_err(|e| DirectoryCreationError(e))?;
-        }
-
-        let writer = Writer::from_path(path).map_err(|e| WriterError(e))?;
-
-        Ok(EloWriter { file: writer })
+        Ok(EloWriter {
+            file: Writer::from_writer(Vec::new()),
+            path: path.as_ref().to_path_buf(),
+        })
     }
 
     pub fn serialize_elo(&mut self, elo: &Elo) -> Result<(), EloWriterError> {
-        self.file.serialize(elo).map_err(|e| SerializerError(e))?;
+        self.file.serialize(elo).map_err(SerializerError)?;
         Ok(())
     }
+
+    pub fn finish(self) -> Result<(), EloWriterError> {
+        let bytes = self
+            .file
+            .into_inner()
+            .map_err(|e| WriterError(csv::Error::from(e.into_error())))?;
+
+        storage::write(&self.path, bytes).map_err(FileWriteError)
+    }
 }
 
 #[derive(Debug)]
 pub enum EloWriterError {
-    DirectoryCreationError(std::io::Error),
+    FileWriteError(std::io::Error),
     WriterError(csv::Error),
     SerializerError(csv::Error),
 }
@@ -42,8 +48,8 @@ pub enum EloWriterError {
 impl Display for EloWriterError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            EloWriterError::DirectoryCreationError(error) => {
-                write!(f, "{error}\n❌ failed to create directory for EloWriter.")
+            EloWriterError::FileWriteError(error) => {
+                write!(f, "❌ {error}\n❌ failed to write file for EloWriter.")
             }
             EloWriterError::WriterError(error) => {
                 write!(
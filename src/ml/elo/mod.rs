@@ -3,3 +3,4 @@ pub use elo::Elo;
 
 pub mod elo_params;
 pub mod elo_writer;
+pub mod rating_history;
@@ -0,0 +1,268 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::Days;
+
+use serde::Serialize;
+
+use thiserror::Error;
+
+use crate::ml::elo::Elo;
+
+use crate::stats::game_obj::GameObject;
+
+use crate::types::{GameDate, GameId, PlayerId, SeasonId, TeamId};
+
+/// players who haven't played in this many days before a date aren't ranked on it.
+const ACTIVE_DAYS: u64 = 365;
+
+/// the season and date of every game, to put ratings on a timeline.
+pub type GameDates = HashMap<GameId, (SeasonId, GameDate)>;
+
+pub fn game_dates<'a>(games: impl Iterator<Item = &'a GameObject>) -> GameDates {
+    games
+        .map(|game| (game.game_id(), (game.season(), game.game_date())))
+        .collect()
+}
+
+/// a rating after a game.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RatingPoint {
+    pub season: SeasonId,
+    pub date: GameDate,
+    pub game_id: GameId,
+    pub rating: i64,
+}
+
+/// a player's rating as of a date, and the last game they played before it.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct RatedPlayer {
+    pub player_id: PlayerId,
+    pub rating: i64,
+    pub last_played: GameDate,
+}
+
+/// a player's rating after each game they played, oldest first. games missing from `dates` (i.e.
+/// not in the loaded volumes) are left out.
+pub fn player_history(ratings: &[Elo], dates: &GameDates, player_id: PlayerId) -> Vec<RatingPoint> {
+    let mut history = ratings
+        .iter()
+        .filter(|elo| elo.player_id == player_id)
+        .filter_map(|elo| {
+            let (season, date) = dates.get(&elo.game_id)?;
+
+            Some(RatingPoint {
+                season: *season,
+                date: *date,
+                game_id: elo.game_id,
+                rating: elo.rating,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    history.sort_by_key(|point| point.date);
+
+    history
+}
+
+/// the mean rating of a franchise's players after each game it played in the season starting in
+/// `year`, oldest first.
+pub fn team_history<'a>(
+    ratings: &[Elo],
+    games: impl Iterator<Item = &'a GameObject>,
+    team_id: TeamId,
+    year: i32,
+) -> Vec<RatingPoint> {
+    let rosters = games
+        .filter(|game| game.season().year() == year)
+        .filter_map(|game| {
+            let side = [game.home(), game.away()]
+                .into_iter()
+                .find(|side| side.team_id() == team_id)?;
+
+            Some((game, side.roster()))
+        })
+        .collect::<Vec<_>>();
+
+    let game_ids = rosters
+        .iter()
+        .map(|(game, _)| game.game_id())
+        .collect::<HashSet<_>>();
+
+    let after_game = ratings
+        .iter()
+        .filter(|elo| game_ids.contains(&elo.game_id))
+        .map(|elo| ((elo.player_id, elo.game_id), elo.rating))
+        .collect::<HashMap<_, _>>();
+
+    let mut history = rosters
+        .into_iter()
+        .filter_map(|(game, roster)| {
+            let rated = roster
+                .iter()
+                .filter_map(|player_id| after_game.get(&(*player_id, game.game_id())))
+                .collect::<Vec<_>>();
+
+            if rated.is_empty() {
+                return None;
+            }
+
+            let mean = rated.iter().copied().sum::<i64>() as f64 / rated.len() as f64;
+
+            Some(RatingPoint {
+                season: game.season(),
+                date: game.game_date(),
+                game_id: game.game_id(),
+                rating: mean.round() as i64,
+            })
+        })
+        .collect::<Vec<_>>();
+
+    history.sort_by_key(|point| point.date);
+
+    history
+}
+
+/// the `limit` highest rated players on `date`, counting only players who played in the year
+/// before it.
+pub fn top_rated(
+    ratings: &[Elo],
+    dates: &GameDates,
+    date: GameDate,
+    limit: usize,
+) -> Vec<RatedPlayer> {
+    let mut latest = HashMap::<PlayerId, RatedPlayer>::new();
+
+    for elo in ratings {
+        let Some((_, played)) = dates.get(&elo.game_id) else {
+            continue;
+        };
+
+        if *played > date {
+            continue;
+        }
+
+        let rated = RatedPlayer {
+            player_id: elo.player_id,
+            rating: elo.rating,
+            last_played: *played,
+        };
+
+        latest
+            .entry(elo.player_id)
+            .and_modify(|kept| {
+                if rated.last_played >= kept.last_played {
+                    *kept = rated;
+                }
+            })
+            .or_insert(rated);
+    }
+
+    let active_since = date
+        .0
+        .checked_sub_days(Days::new(ACTIVE_DAYS))
+        .map(GameDate)
+        .unwrap_or(date);
+
+    let mut top = latest
+        .into_values()
+        .filter(|rated| rated.last_played >= active_since)
+        .collect::<Vec<_>>();
+
+    top.sort_by_key(|rated| (-rated.rating, rated.player_id));
+    top.truncate(limit);
+
+    top
+}
+
+#[derive(Debug, Error)]
+pub enum RatingsError {
+    #[error("❌ {0} has no rated games.")]
+    NoRatings(String),
+    #[error("❌ {0} played no games in {1}.")]
+    NoGames(String, String),
+}
+
+#[cfg(test)]
+mod test_rating_history {
+    use super::{top_rated, GameDates};
+
+    use crate::ml::elo::rating_history::player_history;
+    use crate::ml::elo::Elo;
+
+    use crate::stats::season_period::SeasonPeriod;
+
+    use crate::types::{GameDate, GameId, PlayerId, SeasonId};
+
+    use std::str::FromStr;
+
+    fn dates() -> GameDates {
+        let season = SeasonId::from((2015, SeasonPeriod::RegularSeason));
+
+        GameDates::from([
+            (
+                GameId(1),
+                (season, GameDate::from_str("2015-11-01").unwrap()),
+            ),
+            (
+                GameId(2),
+                (season, GameDate::from_str("2016-01-01").unwrap()),
+            ),
+            (
+                GameId(3),
+                (season, GameDate::from_str("2016-03-01").unwrap()),
+            ),
+        ])
+    }
+
+    fn ratings() -> Vec<Elo> {
+        vec![
+            Elo::new(PlayerId(10), GameId(1), 3010),
+            Elo::new(PlayerId(20), GameId(1), 2990),
+            Elo::new(PlayerId(10), GameId(2), 3020),
+            Elo::new(PlayerId(20), GameId(2), 3050),
+            Elo::new(PlayerId(10), GameId(3), 3000),
+            // a game that isn't in the loaded volumes
+            Elo::new(PlayerId(10), GameId(4), 2000),
+        ]
+    }
+
+    #[test]
+    fn test_player_history() {
+        let history = player_history(&ratings(), &dates(), PlayerId(10));
+
+        assert_eq!(
+            history.iter().map(|p| p.rating).collect::<Vec<_>>(),
+            vec![3010, 3020, 3000]
+        );
+    }
+
+    #[test]
+    fn test_top_rated_as_of_date() {
+        let ratings = ratings();
+        let dates = dates();
+
+        let top = top_rated(
+            &ratings,
+            &dates,
+            GameDate::from_str("2016-02-01").unwrap(),
+            10,
+        );
+
+        assert_eq!(
+            top.iter()
+                .map(|r| (r.player_id, r.rating))
+                .collect::<Vec<_>>(),
+            vec![(PlayerId(20), 3050), (PlayerId(10), 3020)]
+        );
+
+        let later = top_rated(
+            &ratings,
+            &dates,
+            GameDate::from_str("2017-02-01").unwrap(),
+            1,
+        );
+
+        assert_eq!(later.len(), 1);
+        assert_eq!(later[0].player_id, PlayerId(10));
+    }
+}
@@ -10,6 +10,7 @@ pub mod model;
 pub mod models;
 pub mod nelder_mead;
 pub mod observation;
+pub mod registry;
 pub mod simplex;
 mod tests;
 pub mod vector;
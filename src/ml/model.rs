@@ -6,15 +6,40 @@ use thiserror::Error;
 use crate::ml::models::elo_tracker::EloTrackerError;
 
 use crate::stats::chronology::{Chronology, ChronologyError};
+use crate::stats::game_obj::GameObject;
 use crate::stats::gamecard::GameCard;
+use crate::stats::stat_column::StatColumn;
 
 pub trait Model {
     fn model_name(&self) -> String;
     fn initialize(&mut self) -> Result<(), ()>;
 
     fn train(&mut self, data: Chronology) -> Result<(), TrainingError>;
+
+    /// learn `new_games`, oldest first, on top of what the model already knows instead of
+    /// replaying history, and save the result. models that can only learn from all of history
+    /// don't support it.
+    fn update(&mut self, _new_games: &[(GameCard, GameObject)]) -> Result<(), TrainingError> {
+        Err(TrainingError::UpdateUnsupported(self.model_name()))
+    }
+
     fn evaluate(&self) -> HashMap<String, f64>; // this could return a vec of measurements or a like structure
     fn predict(&mut self, obj: &GameCard) -> f64;
+
+    /// the box score columns the model needs to train. box scores missing any of them are left
+    /// out of the training data, so an empty list trains on all of history.
+    fn required_fields(&self) -> Vec<StatColumn> {
+        Vec::new()
+    }
+
+    /// the training run whose directory the model's artifacts are read from and written to, or
+    /// `None` for the model's home directory.
+    fn run(&self) -> Option<&str> {
+        None
+    }
+
+    /// read and write the model's artifacts in the directory of `run_id` from now on.
+    fn set_run(&mut self, _run_id: &str) {}
 }
 // the idea is
 // model name is
@@ -35,6 +60,8 @@ pub enum TrainingError {
     ArtifactSaveError(io::Error),
     #[error("{0}\n❌ failed to save elo artifacts after training")]
     EloSaveError(EloTrackerError),
+    #[error("❌ {0} can't learn new games on its own. train it on all of history instead")]
+    UpdateUnsupported(String),
 }
 
 impl Model for Box<dyn Model> {
@@ -50,6 +77,10 @@ impl Model for Box<dyn Model> {
         (**self).train(data)
     }
 
+    fn update(&mut self, new_games: &[(GameCard, GameObject)]) -> Result<(), TrainingError> {
+        (**self).update(new_games)
+    }
+
     fn evaluate(&self) -> HashMap<String, f64> {
         (**self).evaluate()
     }
@@ -57,4 +88,16 @@ impl Model for Box<dyn Model> {
     fn predict(&mut self, obj: &GameCard) -> f64 {
         (**self).predict(obj)
     }
+
+    fn required_fields(&self) -> Vec<StatColumn> {
+        (**self).required_fields()
+    }
+
+    fn run(&self) -> Option<&str> {
+        (**self).run()
+    }
+
+    fn set_run(&mut self, run_id: &str) {
+        (**self).set_run(run_id)
+    }
 }
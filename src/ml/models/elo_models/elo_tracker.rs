@@ -22,10 +22,14 @@ use crate::dapi::read_disk::NBAReadError;
 
 use crate::dapi::write::write_serializable_with_directory;
 
+use crate::storage;
+
+use crate::tui::progress::Progress;
+
 use crate::types::{GameId, PlayerId};
 
 use std::collections::HashMap;
-use std::{fs, io};
+use std::io;
 
 const ELO_VERSION: &str = "elo-v1";
 
@@ -34,6 +38,7 @@ pub struct EloTracker {
     current_ratings: HashMap<PlayerId, i64>,
     log_loss: LogLossTracker,
     params: EloParams,
+    run: Option<String>,
 }
 
 impl EloTracker {
@@ -43,6 +48,7 @@ impl EloTracker {
             current_ratings: HashMap::new(),
             log_loss: LogLossTracker::new(),
             params: EloParams::default(),
+            run: None,
         }
     }
 
@@ -52,15 +58,28 @@ impl EloTracker {
             current_ratings: HashMap::new(),
             log_loss: LogLossTracker::new(),
             params,
+            run: None,
         }
     }
 
-    pub fn from_csv() -> Result<Self, EloTrackerError> {
+    /// the tracker with the ratings recorded by `run_id`, or by the model's home directory.
+    pub fn from_run(run_id: Option<&str>) -> Result<Self, EloTrackerError> {
         let mut tracker = Self::new();
 
+        if let Some(run_id) = run_id {
+            tracker.set_run(run_id);
+        }
+
+        tracker.with_records()
+    }
+
+    /// the tracker with the ratings in the records of its model directory.
+    fn with_records(self) -> Result<Self, EloTrackerError> {
+        let mut tracker = self;
+
         let path = records_path(&tracker);
 
-        let contents = fs::read_to_string(path).map_err(|e| EloTrackerError::CSVError(e))?;
+        let contents = storage::read_to_string(&path).map_err(|e| EloTrackerError::CSVError(e))?;
 
         for line in contents.lines().skip(1) {
             let fields: Vec<&str> = line.split(',').collect();
@@ -112,10 +131,14 @@ impl EloTracker {
         // maybe assert ordered on the basis. no frick u man
         assert!(games.is_sorted_by_key(|(c, _g)| c.date()));
 
+        let progress = Progress::new(games.len() as u64, "🧮 rating games");
+
         for (slip, box_score) in games {
             // remember there is now way to predict
             // the first event other than fiftEE-fiftEE
             self.update_ratings(slip, box_score);
+
+            progress.inc(1);
         }
     }
 
@@ -213,6 +236,16 @@ impl EloTracker {
         self.log_loss.add_observation(obs);
     }
 
+    /// every player's rating after every game they played, in the order the games were played.
+    pub fn historical_ratings(&self) -> &[Elo] {
+        &self.historical_ratings
+    }
+
+    /// every rated player's rating after the last game they played.
+    pub fn current_ratings(&self) -> &HashMap<PlayerId, i64> {
+        &self.current_ratings
+    }
+
     pub(crate) fn freq(&self) -> f64 {
         self.log_loss.freq()
     }
@@ -227,7 +260,7 @@ impl EloTracker {
 
     pub(crate) fn crit(&self) -> f64 {
         if self.log_loss() == 0.0 {
-            println!("❌ Cannot evaluate: log_loss is zero");
+            log::error!("❌ Cannot evaluate: log_loss is zero");
 
             f64::NAN
         } else {
@@ -257,7 +290,7 @@ impl EloTracker {
                 .map_err(|e| EloTrackerError::WriteEloError(e))?;
         }
 
-        Ok(())
+        writer.finish().map_err(EloTrackerError::WriteEloError)
     }
 
     fn save_results(&self) -> Result<(), EloTrackerError> {
@@ -318,9 +351,13 @@ pub enum EloTrackerError {
 
 impl Model for EloTracker {
     fn initialize(&mut self) -> Result<(), ()> {
-        let mut tracker = EloTracker::from_csv().map_err(|_| ())?;
+        let mut tracker = EloTracker::params(self.params.clone());
+
+        tracker.run = self.run.clone();
 
-        match fs::read_to_string(results_path(self)) {
+        let mut tracker = tracker.with_records().map_err(|_| ())?;
+
+        match storage::read_to_string(&results_path(self)) {
             Ok(contents) => match serde_json::from_str::<serde_json::Value>(&contents) {
                 Ok(parsed) => {
                     let freq = parsed["freq"].as_f64().unwrap_or(0.0);
@@ -336,12 +373,12 @@ impl Model for EloTracker {
                     tracker.log_loss = axis;
                 }
                 Err(e) => {
-                    println!("{e}\n❌ Failed to parse results JSON");
+                    log::error!("{e}\n❌ Failed to parse results JSON");
                     return Err(());
                 }
             },
             Err(e) => {
-                println!("{e}\n❌ model has not yet been trained. no results file was found for this model: {}", self.model_name());
+                log::error!("{e}\n❌ model has not yet been trained. no results file was found for this model: {}", self.model_name());
                 return Err(());
             }
         };
@@ -400,6 +437,20 @@ impl Model for EloTracker {
 
         self.save().map_err(|e| TrainingError::EloSaveError(e))
     }
+
+    fn update(&mut self, new_games: &[(GameCard, GameObject)]) -> Result<(), TrainingError> {
+        self.process_elo(new_games);
+
+        self.save().map_err(TrainingError::EloSaveError)
+    }
+
+    fn run(&self) -> Option<&str> {
+        self.run.as_deref()
+    }
+
+    fn set_run(&mut self, run_id: &str) {
+        self.run = Some(run_id.to_owned());
+    }
 }
 
 inventory::submit!(Registration {
@@ -479,9 +530,6 @@ mod test_elo_tracker {
 
         let mut pairs = chronology
             .games()
-            .as_ref()
-            .expect("chrono")
-            .iter()
             .map(|game| (game.card(), game.clone()))
             .collect::<Vec<_>>();
 
@@ -12,6 +12,8 @@ use crate::ml::vector::Vector;
 
 use crate::stats::chronology::Chronology;
 
+use crate::tui::progress::Progress;
+
 /// this elo algorithm is optimized on (k, f) pairs,
 /// the algorithm also uses a initial rating of 0 for symmetry.
 pub struct NelderMeadEloTracker {
@@ -41,7 +43,12 @@ impl Model for NelderMeadEloTracker {
         todo!()
     }
 
-    fn train(&mut self, chrono: Chronology) -> Result<(), TrainingError> {
+    fn train(&mut self, mut chrono: Chronology) -> Result<(), TrainingError> {
+        // every evaluation trains on a clone, so load history once and share it between them
+        chrono
+            .load_all()
+            .map_err(TrainingError::VolumeLoadingError)?;
+
         let mut simplex = Simplex::from(&vec![
             Vector::from(vec![32., 400.]),
             Vector::from(vec![24., 400.]),
@@ -53,7 +60,7 @@ impl Model for NelderMeadEloTracker {
 
             match tracker.train(chrono.clone()) {
                 Ok(_) => {
-                    println!(
+                    log::debug!(
                         "{}/{}=>{}",
                         tracker.freq(),
                         tracker.log_loss(),
@@ -68,6 +75,8 @@ impl Model for NelderMeadEloTracker {
 
         let baseline = 0.46304378813918995;
 
+        let progress = Progress::unbounded("🎯 optimizing elo params");
+
         while self.performance < baseline * 0.7 {
             nelder_mead(cost, &mut simplex);
 
@@ -84,9 +93,13 @@ impl Model for NelderMeadEloTracker {
                     self.performance = new_performance;
                 }
             }
+
+            progress.inc(1);
         }
 
-        println!(
+        progress.finish();
+
+        log::info!(
             "Score: {}\nBaseline: {}\nstep: {}\tscale factor: {}",
             self.performance,
             baseline,
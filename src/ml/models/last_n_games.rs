@@ -1,11 +1,10 @@
 use std::collections::HashMap;
-use std::fs;
 
 use clap::Arg;
 
 use wincode::{SchemaRead, SchemaWrite};
 
-use crate::format::path_manager::{model_dir, records_path, results_path};
+use crate::format::path_manager::{records_path, results_path};
 
 use crate::ml::log_loss::LogLossTracker;
 use crate::ml::model::{Model, TrainingError};
@@ -13,8 +12,13 @@ use crate::ml::models::registration::Registration;
 use crate::ml::observation::Observation;
 
 use crate::stats::chronology::Chronology;
+use crate::stats::game_obj::GameObject;
 use crate::stats::gamecard::GameCard;
 
+use crate::storage;
+
+use crate::tui::progress::Progress;
+
 use crate::types::{GameResult, TeamId};
 
 const LAST_N_GAMES: &str = "last-n-games";
@@ -28,6 +32,7 @@ pub struct LastNGames {
     /// outcomes is a vector of game id, result pairs, the newest
     ll: LogLossTracker,
     map: HashMap<TeamId, CircularBuffer>,
+    run: Option<String>,
 }
 
 impl LastNGames {
@@ -36,31 +41,65 @@ impl LastNGames {
             n,
             map: HashMap::new(),
             ll: LogLossTracker::new(),
+            run: None,
         }
     }
 
     pub fn save(&self) -> Result<(), TrainingError> {
-        fs::create_dir_all(model_dir(self)).ok();
-
         let records_result = wincode::serialize(&self.map);
         let results_result = serde_json::to_string(&self.evaluate());
 
         if let Err(e) = records_result {
             return Err(TrainingError::WincodeSerializationError(e));
         }
-        if let Err(e) = fs::write(records_path(self), &records_result.unwrap()) {
-            println!("❌ {e}\n❌ failed to save records map to file for last_n_games model. you will not be able to load this model from file.");
+        if let Err(e) = storage::write(&records_path(self), records_result.unwrap()) {
+            log::error!("❌ {e}\n❌ failed to save records map to file for last_n_games model. you will not be able to load this model from file.");
         }
 
         if let Err(e) = results_result {
             return Err(TrainingError::JsonSerializationError(e));
         }
-        if let Err(e) = fs::write(results_path(self), &results_result.unwrap()) {
-            println!("❌ {e}\n❌ failed to save model performance to file for last_n_games model. this model cannot be evaluated from file.");
+        if let Err(e) = storage::write(&results_path(self), results_result.unwrap()) {
+            log::error!("❌ {e}\n❌ failed to save model performance to file for last_n_games model. this model cannot be evaluated from file.");
         }
         Ok(())
     }
 
+    /// score each game by the records of its teams going in, then add its result to them.
+    fn observe(&mut self, games: &[(GameCard, GameObject)]) {
+        let progress = Progress::new(games.len() as u64, "🧮 scoring games");
+
+        for (_card, game) in games {
+            let prob_home = self.rolling_avg(game.home_team_id());
+            let prob_away = self.rolling_avg(game.away_team_id());
+
+            let home_result = game.home().box_score().wl(); //record relative to home team
+            let away_result = game.away().box_score().wl(); //record relative to away team
+
+            let obs = Observation::new(
+                if home_result == &GameResult::Win {
+                    1
+                } else {
+                    0
+                },
+                conditioned_probability(prob_home, prob_away),
+            );
+
+            self.ll.add_observation(obs);
+
+            self.map
+                .entry(game.home_team_id())
+                .or_insert_with(|| CircularBuffer::new(self.n))
+                .insert(*home_result);
+            self.map
+                .entry(game.away_team_id())
+                .or_insert_with(|| CircularBuffer::new(self.n))
+                .insert(*away_result);
+
+            progress.inc(1);
+        }
+    }
+
     //mutable incase we need to insert a new team entry to map
     fn rolling_avg(&mut self, team_id: TeamId) -> f64 {
         let rolling_count = self
@@ -135,7 +174,30 @@ impl Model for LastNGames {
     }
 
     fn initialize(&mut self) -> Result<(), ()> {
-        todo!()
+        let bytes = storage::read(&records_path(self)).map_err(|e| {
+            log::error!("{e}\n❌ model has not yet been trained. no records file was found for this model: {}", self.model_name())
+        })?;
+
+        self.map =
+            wincode::deserialize::<HashMap<TeamId, CircularBuffer>>(&bytes).map_err(|e| {
+                log::error!(
+                    "❌ {e}\n❌ failed to read the records map of {}",
+                    self.model_name()
+                )
+            })?;
+
+        let results = storage::read_to_string(&results_path(self))
+            .ok()
+            .and_then(|contents| serde_json::from_str::<HashMap<String, f64>>(&contents).ok())
+            .unwrap_or_default();
+
+        let count = results.get("count").copied().unwrap_or(0.0);
+        let freq = results.get("freq").copied().unwrap_or(0.0) * count;
+        let log_loss = results.get("log_loss").copied().unwrap_or(0.0) * count;
+
+        self.ll = LogLossTracker::from_data(log_loss, freq.round() as u64, count as u64);
+
+        Ok(())
     }
 
     fn train(&mut self, chrono: Chronology) -> Result<(), TrainingError> {
@@ -143,33 +205,13 @@ impl Model for LastNGames {
             .as_training_data()
             .map_err(|e| TrainingError::VolumeLoadingError(e))?;
 
-        for (_card, game) in data {
-            let prob_home = self.rolling_avg(game.home_team_id());
-            let prob_away = self.rolling_avg(game.away_team_id());
-
-            let home_result = game.home().box_score().wl(); //record relative to home team
-            let away_result = game.away().box_score().wl(); //record relative to away team
-
-            let obs = Observation::new(
-                if home_result == &GameResult::Win {
-                    1
-                } else {
-                    0
-                },
-                conditioned_probability(prob_home, prob_away),
-            );
+        self.observe(&data);
 
-            self.ll.add_observation(obs);
+        self.save()
+    }
 
-            self.map
-                .entry(game.home_team_id())
-                .or_insert_with(|| CircularBuffer::new(self.n))
-                .insert(*home_result);
-            self.map
-                .entry(game.away_team_id())
-                .or_insert_with(|| CircularBuffer::new(self.n))
-                .insert(*away_result);
-        }
+    fn update(&mut self, new_games: &[(GameCard, GameObject)]) -> Result<(), TrainingError> {
+        self.observe(new_games);
 
         self.save()
     }
@@ -194,6 +236,13 @@ impl Model for LastNGames {
             self.rolling_avg(card.away().team_id()),
         )
     }
+    fn run(&self) -> Option<&str> {
+        self.run.as_deref()
+    }
+
+    fn set_run(&mut self, run_id: &str) {
+        self.run = Some(run_id.to_owned());
+    }
 }
 
 #[derive(Debug, Clone, SchemaRead, SchemaWrite)]
@@ -232,9 +281,8 @@ impl CircularBuffer {
 
 #[cfg(test)]
 mod test_last_n_games_model {
-    use once_cell::sync::Lazy;
-
     use crate::constants::paths::data;
+    use crate::format::path_manager::model_dir;
 
     use super::*;
 
@@ -271,17 +319,24 @@ mod test_last_n_games_model {
 
     #[test]
     fn test_dir_name() {
-        static DATA: Lazy<String> = Lazy::new(data);
+        let data = data().unwrap();
 
         let model = LastNGames::new(DEFAULT_WINDOW_SIZE);
         assert_eq!(
             model_dir(&model).display().to_string(),
-            format!("{}/nba/last-n-games", *DATA)
+            format!("{}/nba/last-n-games", data.display())
         );
         let model = LastNGames::new(10);
         assert_eq!(
             model_dir(&model).display().to_string(),
-            format!("{}/nba/last-n-games(days=10)", *DATA)
+            format!("{}/nba/last-n-games(days=10)", data.display())
+        );
+
+        let mut model = LastNGames::new(DEFAULT_WINDOW_SIZE);
+        model.set_run("20250114-183502");
+        assert_eq!(
+            model_dir(&model).display().to_string(),
+            format!("{}/nba/last-n-games/runs/20250114-183502", data.display())
         );
     }
 }
@@ -1,6 +1,5 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
-use std::fs;
 
 use serde_json::Value;
 
@@ -19,6 +18,10 @@ use crate::ml::vector::Vector;
 use crate::stats::chronology::Chronology;
 use crate::stats::gamecard::GameCard;
 use crate::stats::nba_kind::NBAStatKind;
+use crate::stats::nba_schema::{schema_columns, MODERN_NBA_SCHEMA};
+use crate::stats::stat_column::StatColumn;
+
+use crate::storage;
 
 inventory::submit!(Registration {
     model_name: SIGMA_VERSION,
@@ -28,6 +31,7 @@ inventory::submit!(Registration {
 
 pub struct SigmaChadModel {
     model: LogisticRegression,
+    run: Option<String>,
 }
 
 const SIGMA_VERSION: &'static str = "sigmachad-v1";
@@ -43,7 +47,7 @@ impl model::Model for SigmaChadModel {
 
     fn train(&mut self, chrono: Chronology) -> Result<(), model::TrainingError> {
         let (training_data, testing_data) = chrono
-            .as_pure_regression_data(NBAStatKind::Team, 0.7)
+            .as_regression_data(NBAStatKind::Team, 0.7, &self.required_fields())
             .map_err(|e| TrainingError::VolumeLoadingError(e))?;
 
         let mut sums = Vector::origin(18); //this will included ignored features
@@ -98,7 +102,7 @@ impl model::Model for SigmaChadModel {
     }
 
     fn evaluate(&self) -> HashMap<String, f64> {
-        if let Ok(file) = fs::read_to_string(results_path(self)) {
+        if let Ok(file) = storage::read_to_string(&results_path(self)) {
             if let Ok(json) = serde_json::from_str::<HashMap<Value, Value>>(&file) {
                 return json
                     .into_iter()
@@ -113,12 +117,25 @@ impl model::Model for SigmaChadModel {
     fn predict(&mut self, _obj: &GameCard) -> f64 {
         todo!()
     }
+
+    fn required_fields(&self) -> Vec<StatColumn> {
+        // every feature of the box score vector is used, so only fully modern box scores qualify
+        schema_columns(MODERN_NBA_SCHEMA)
+    }
+    fn run(&self) -> Option<&str> {
+        self.run.as_deref()
+    }
+
+    fn set_run(&mut self, run_id: &str) {
+        self.run = Some(run_id.to_owned());
+    }
 }
 
 impl SigmaChadModel {
     fn new() -> Self {
         Self {
             model: LogisticRegression::new(Vector::origin(15), 0.0),
+            run: None,
         }
     }
 
@@ -158,12 +175,11 @@ impl SigmaChadModel {
     fn save(&self, tracker: &LogLossTracker) -> Result<(), TrainingError> {
         let mut model_path = path_manager::model_dir(self);
 
-        let _ = fs::create_dir_all(&model_path);
         model_path.push("model.md");
 
         let model_file = model_path;
 
-        fs::write(model_file, format!("{:?}", self.named_model()))
+        storage::write(&model_file, format!("{:?}", self.named_model()))
             .map_err(|e| TrainingError::ArtifactSaveError(e))?;
 
         write_serializable_with_directory(results_path(self), &tracker)
@@ -0,0 +1,466 @@
+use crate::checksum::checksum::{Algorithm, Checksum, Hasher};
+use crate::checksum::lineage::WARHEADS_VERSION;
+use crate::checksum::read_checksum::read_checksum;
+
+use crate::dapi::season_manager::nba_lifespan_period;
+
+use crate::format::path_manager::{
+    model_home, model_run_file, models_root, nba_edit_file, nba_storage_path, promoted_run_file,
+};
+
+use crate::storage;
+
+use crate::types::GameDate;
+
+use chrono::{DateTime, Utc};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use thiserror::Error;
+
+/// ## RunMetadata
+///
+/// what a training run was trained on and how it did. written to `run.json` in the run's
+/// directory next to the model's artifacts.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct RunMetadata {
+    pub run_id: String,
+    pub model_name: String,
+    pub trained_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// the model's arguments, including defaults.
+    pub params: BTreeMap<String, String>,
+    pub data: DataFingerprint,
+    pub metrics: BTreeMap<String, f64>,
+    pub warheads_version: String,
+    /// the date of the newest game the run has learned.
+    #[serde(default)]
+    pub last_game: Option<GameDate>,
+    /// the run this one learned new games on top of, for runs made by `update`.
+    #[serde(default)]
+    pub parent_run_id: Option<String>,
+}
+
+impl RunMetadata {
+    pub fn load(model_name: &str, run_id: &str) -> Result<Self, RegistryError> {
+        let path = model_run_file(model_name, run_id);
+
+        let contents = storage::read_to_string(&path).map_err(|e| match e.kind() {
+            io::ErrorKind::NotFound => {
+                RegistryError::RunNotFound(model_name.to_owned(), run_id.to_owned())
+            }
+            _ => RegistryError::ReadError(path.clone(), e),
+        })?;
+
+        serde_json::from_str(&contents).map_err(|e| RegistryError::ParseError(path, e))
+    }
+
+    pub fn save(&self) -> Result<(), RegistryError> {
+        let path = model_run_file(&self.model_name, &self.run_id);
+
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| RegistryError::ParseError(path.clone(), e))?;
+
+        storage::write(&path, contents).map_err(|e| RegistryError::WriteError(path, e))
+    }
+
+    /// the date of the newest game the run has learned. runs recorded before it was tracked are
+    /// taken to have learned every game before the day they were trained.
+    pub fn learned_through(&self) -> GameDate {
+        self.last_game.unwrap_or_else(|| {
+            let trained_on = self.trained_at.date_naive();
+
+            GameDate(trained_on.pred_opt().unwrap_or(trained_on))
+        })
+    }
+}
+
+/// checksums of the data a run was trained on, to tell whether two runs saw the same history.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct DataFingerprint {
+    /// a checksum over the checksum of every stored volume.
+    pub volumes: Checksum,
+    pub edits: Checksum,
+}
+
+impl DataFingerprint {
+    pub fn current() -> Self {
+        let mut volumes = Hasher::new(Algorithm::Sha256);
+
+        // volumes are streamed through the hasher rather than read into memory whole
+        for era in nba_lifespan_period() {
+            if let Ok(Checksum::Sha256(digest)) = read_checksum(&nba_storage_path(era)) {
+                volumes.update(format!("{era}:{digest}\n").as_bytes());
+            }
+        }
+
+        let edits = read_checksum(&nba_edit_file())
+            .unwrap_or_else(|_| Checksum::compute(Algorithm::Sha256, &[]));
+
+        DataFingerprint {
+            volumes: volumes.finish(),
+            edits,
+        }
+    }
+}
+
+/// ## Run
+///
+/// a training run in progress. the model being trained is pointed at the run's directory (see
+/// `Model::set_run`), so everything `train` saves lands in the run instead of overwriting the last
+/// one.
+pub struct Run {
+    run_id: String,
+    model_name: String,
+    params: BTreeMap<String, String>,
+    trained_at: DateTime<Utc>,
+    start: Instant,
+    parent_run_id: Option<String>,
+}
+
+impl Run {
+    pub fn start(model_name: &str, params: BTreeMap<String, String>) -> Self {
+        let trained_at = Utc::now();
+
+        let run_id = new_run_id(trained_at, |id| {
+            storage::exists(&model_run_file(model_name, id))
+        });
+
+        Run {
+            run_id,
+            model_name: model_name.to_owned(),
+            params,
+            trained_at,
+            start: Instant::now(),
+            parent_run_id: None,
+        }
+    }
+
+    /// a run that learns new games on top of `parent`, with the parent's params. the parent's
+    /// artifacts and metadata are left as they were.
+    pub fn update(parent: &RunMetadata) -> Self {
+        let mut run = Run::start(&parent.model_name, parent.params.clone());
+
+        run.parent_run_id = Some(parent.run_id.clone());
+
+        run
+    }
+
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// record the run with the metrics the model reports after training and the date of the
+    /// newest game it learned.
+    pub fn finish(
+        self,
+        metrics: HashMap<String, f64>,
+        last_game: Option<GameDate>,
+    ) -> Result<RunMetadata, RegistryError> {
+        let metadata = RunMetadata {
+            run_id: self.run_id,
+            model_name: self.model_name,
+            trained_at: self.trained_at,
+            duration_ms: self.start.elapsed().as_millis() as u64,
+            params: self.params,
+            data: DataFingerprint::current(),
+            metrics: metrics.into_iter().collect(),
+            warheads_version: WARHEADS_VERSION.to_owned(),
+            last_game,
+            parent_run_id: self.parent_run_id,
+        };
+
+        metadata.save()?;
+
+        Ok(metadata)
+    }
+}
+
+/// a run id from the time training started, e.g. `20250114-183502`. a suffix is added if a run
+/// with that id is `taken`.
+pub fn new_run_id(trained_at: DateTime<Utc>, taken: impl Fn(&str) -> bool) -> String {
+    let id = trained_at.format("%Y%m%d-%H%M%S").to_string();
+
+    (1..)
+        .map(|n| match n {
+            1 => id.clone(),
+            n => format!("{id}-{n}"),
+        })
+        .find(|id| !taken(id))
+        .unwrap()
+}
+
+/// every argument of a model as it was given (or defaulted), e.g. `step = 32`.
+pub fn model_params(matches: &clap::ArgMatches) -> BTreeMap<String, String> {
+    matches
+        .ids()
+        .filter_map(|id| {
+            let values = matches.get_raw(id.as_str())?;
+
+            let value = values
+                .map(|v| v.to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .join(",");
+
+            Some((id.to_string(), value))
+        })
+        .collect()
+}
+
+/// the arguments that give a model `params` again, e.g. `--step 32`. every model argument's long
+/// flag is its id.
+pub fn model_args(params: &BTreeMap<String, String>) -> Vec<String> {
+    params
+        .iter()
+        .flat_map(|(param, value)| [format!("--{param}"), value.clone()])
+        .collect()
+}
+
+/// every run of a model, oldest first.
+pub fn model_runs(model_name: &str) -> Result<Vec<RunMetadata>, RegistryError> {
+    let runs_dir = model_home(model_name).join("runs");
+
+    let files = storage::list(&runs_dir).map_err(|e| RegistryError::ReadError(runs_dir, e))?;
+
+    let mut runs = files
+        .iter()
+        .filter(|path| path.file_name().is_some_and(|name| name == "run.json"))
+        .filter_map(|path| path.parent()?.file_name()?.to_str())
+        .map(|run_id| RunMetadata::load(model_name, run_id))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    runs.sort_by_key(|run| run.trained_at);
+
+    Ok(runs)
+}
+
+/// the names of every model with at least one run.
+pub fn registered_models() -> Result<Vec<String>, RegistryError> {
+    let root = models_root();
+
+    let files = storage::list(&root).map_err(|e| RegistryError::ReadError(root.clone(), e))?;
+
+    let mut models = files
+        .iter()
+        .filter_map(|path| registered_model(&root, path))
+        .collect::<Vec<_>>();
+
+    models.sort();
+    models.dedup();
+
+    Ok(models)
+}
+
+/// the model a `{model}/runs/{run_id}/run.json` file belongs to.
+fn registered_model(root: &Path, path: &Path) -> Option<String> {
+    let parts = path
+        .strip_prefix(root)
+        .ok()?
+        .iter()
+        .map(|part| part.to_str())
+        .collect::<Option<Vec<_>>>()?;
+
+    match parts.as_slice() {
+        [model, "runs", _, "run.json"] => Some(model.to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+struct Promotion {
+    run_id: String,
+    promoted_at: DateTime<Utc>,
+}
+
+/// the run `eval` and `forecast` use unless told otherwise.
+pub fn promoted_run(model_name: &str) -> Option<String> {
+    let contents = storage::read_to_string(&promoted_run_file(model_name)).ok()?;
+
+    serde_json::from_str::<Promotion>(&contents)
+        .ok()
+        .map(|promotion| promotion.run_id)
+}
+
+pub fn promote(model_name: &str, run_id: &str) -> Result<(), RegistryError> {
+    // a run can only be promoted once it has finished training
+    RunMetadata::load(model_name, run_id)?;
+
+    let path = promoted_run_file(model_name);
+
+    let promotion = Promotion {
+        run_id: run_id.to_owned(),
+        promoted_at: Utc::now(),
+    };
+
+    let contents = serde_json::to_string_pretty(&promotion)
+        .map_err(|e| RegistryError::ParseError(path.clone(), e))?;
+
+    storage::write(&path, contents).map_err(|e| RegistryError::WriteError(path, e))
+}
+
+/// delete a run and its artifacts. the promoted run can't be removed, promote another one first.
+pub fn remove_run(model_name: &str, run_id: &str) -> Result<usize, RegistryError> {
+    RunMetadata::load(model_name, run_id)?;
+
+    if promoted_run(model_name).as_deref() == Some(run_id) {
+        return Err(RegistryError::RunIsPromoted(
+            model_name.to_owned(),
+            run_id.to_owned(),
+        ));
+    }
+
+    let dir = model_home(model_name).join("runs").join(run_id);
+
+    storage::remove_all(&dir).map_err(|e| RegistryError::WriteError(dir, e))
+}
+
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    #[error("❌ {0} has no run {1}. try `warheads models list {0}`")]
+    RunNotFound(String, String),
+    #[error("❌ run {1} is promoted for {0}. promote another run before removing it")]
+    RunIsPromoted(String, String),
+    #[error("❌ {1}\n❌ failed to read model runs from {path}", path = .0.display())]
+    ReadError(PathBuf, io::Error),
+    #[error("❌ {1}\n❌ failed to parse run metadata in {path}", path = .0.display())]
+    ParseError(PathBuf, serde_json::Error),
+    #[error("❌ {1}\n❌ failed to write {path}", path = .0.display())]
+    WriteError(PathBuf, io::Error),
+}
+
+#[cfg(test)]
+mod test_registry {
+    use super::*;
+
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_run_ids_are_unique() {
+        let trained_at = Utc.with_ymd_and_hms(2025, 1, 14, 18, 35, 2).unwrap();
+
+        assert_eq!(new_run_id(trained_at, |_| false), "20250114-183502");
+        assert_eq!(
+            new_run_id(trained_at, |id| id == "20250114-183502"),
+            "20250114-183502-2"
+        );
+    }
+
+    #[test]
+    fn test_model_params_include_defaults() {
+        let command = clap::Command::new("elo")
+            .arg(clap::Arg::new("step").long("step").default_value("32"))
+            .arg(clap::Arg::new("scale").long("scale"));
+
+        let matches = command
+            .try_get_matches_from(["elo", "--scale", "400"])
+            .unwrap();
+
+        let params = model_params(&matches);
+
+        assert_eq!(params.get("step").map(String::as_str), Some("32"));
+        assert_eq!(params.get("scale").map(String::as_str), Some("400"));
+    }
+
+    #[test]
+    fn test_model_args_give_back_params() {
+        let command = || {
+            clap::Command::new("elo")
+                .arg(clap::Arg::new("step").long("step").default_value("32"))
+                .arg(clap::Arg::new("scale").long("scale"))
+        };
+
+        let params = model_params(
+            &command()
+                .try_get_matches_from(["elo", "--step", "40", "--scale", "400"])
+                .unwrap(),
+        );
+
+        let mut args = vec!["elo".to_string()];
+        args.extend(model_args(&params));
+
+        let matches = command().try_get_matches_from(args).unwrap();
+
+        assert_eq!(model_params(&matches), params);
+    }
+
+    #[test]
+    fn test_registered_models_are_found_by_run_file() {
+        let root = Path::new("/models");
+
+        assert_eq!(
+            registered_model(
+                root,
+                Path::new("/models/elo-v1/runs/20250114-183502/run.json")
+            ),
+            Some("elo-v1".to_string())
+        );
+        assert_eq!(
+            registered_model(root, Path::new("/models/elo-v1/results.json")),
+            None
+        );
+        assert_eq!(
+            registered_model(root, Path::new("/models/elo-v1/runs/x/records.csv")),
+            None
+        );
+    }
+
+    fn sample_run() -> RunMetadata {
+        RunMetadata {
+            run_id: "20250114-183502".to_string(),
+            model_name: "elo-v1".to_string(),
+            trained_at: Utc.with_ymd_and_hms(2025, 1, 14, 18, 35, 2).unwrap(),
+            duration_ms: 1234,
+            params: BTreeMap::from([("step".to_string(), "32".to_string())]),
+            data: DataFingerprint {
+                volumes: Checksum::compute(Algorithm::Sha256, b"volumes"),
+                edits: Checksum::compute(Algorithm::Sha256, b"edits"),
+            },
+            metrics: BTreeMap::from([("log_loss".to_string(), 0.61)]),
+            warheads_version: WARHEADS_VERSION.to_string(),
+            last_game: GameDate::ymd(2025, 1, 12),
+            parent_run_id: None,
+        }
+    }
+
+    #[test]
+    fn test_update_is_a_new_run_of_its_parent() {
+        let parent = sample_run();
+
+        let run = Run::update(&parent);
+
+        assert_ne!(run.run_id(), parent.run_id);
+        assert_eq!(run.parent_run_id.as_deref(), Some(parent.run_id.as_str()));
+        assert_eq!(run.params, parent.params);
+    }
+
+    #[test]
+    fn test_run_metadata_round_trip() {
+        let run = sample_run();
+
+        let json = serde_json::to_string(&run).unwrap();
+
+        assert_eq!(serde_json::from_str::<RunMetadata>(&json).unwrap(), run);
+    }
+
+    #[test]
+    fn test_runs_before_last_game_learned_through_the_day_before_training() {
+        let mut json = serde_json::to_value(sample_run()).unwrap();
+
+        json.as_object_mut().unwrap().remove("last_game");
+        json.as_object_mut().unwrap().remove("parent_run_id");
+
+        let run = serde_json::from_value::<RunMetadata>(json).unwrap();
+
+        assert_eq!(run.last_game, None);
+        assert_eq!(run.learned_through(), GameDate::ymd(2025, 1, 13).unwrap());
+        assert_eq!(
+            sample_run().learned_through(),
+            GameDate::ymd(2025, 1, 12).unwrap()
+        );
+    }
+}
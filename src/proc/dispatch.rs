@@ -1,23 +1,87 @@
-use std::time::Instant;
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
 
-use clap::{Parser, Subcommand};
+use clap::{ArgAction, Parser, Subcommand, ValueEnum};
 
 use thiserror::Error;
 
-use crate::checksum::checksum_map::{ChecksumMap, ChecksumMapError};
-use crate::checksum::generate::generate_checksums;
+use crate::checksum::checksum_map::{
+    ChecksumMap, ChecksumMapError, DataClass, CHECKSUM_MAP_VERSION,
+};
+use crate::checksum::generate::generate_checksums_parallel;
+use crate::checksum::sign::sign;
+
+use crate::constants::config::{configure, Config, ConfigError, ConfigLayer, StorageKind};
+use crate::constants::header_manager::{header_manager, HeaderError};
+use crate::constants::paths::data;
+
+use crate::daemon::jobs::{default_jobs, load_jobs, JobConfigError, JobKind};
+use crate::daemon::scheduler::{Clock, Daemon, DaemonError, JobRunner, SystemClock};
+
+use crate::dapi::directory::{load_directories, resolve_player, resolve_team, DirectoryError};
+use crate::dapi::season_manager::nba_lifespan;
+
+use crate::edit::edit_loader::{load_edit_list, save_edit_list, EditLoadingError};
 
 use crate::format;
-use crate::format::path_manager::nba_checksum_file;
+use crate::format::directory_formatter::{franchise_history, player_matches};
+use crate::format::grade_formatter::grade_table;
+use crate::format::path_manager::{
+    daemon_jobs_file, daemon_status_file, nba_checksum_file, nba_edit_file, nba_export_path,
+};
+use crate::format::prediction_formatter::{
+    predictions_csv, predictions_json, predictions_markdown, predictions_table,
+};
+use crate::format::query_formatter::{query_csv, query_json, query_table};
+use crate::format::rating_formatter::{
+    rating_chart, rating_history_csv, top_csv, top_json, top_table,
+};
+use crate::format::registry_formatter::{run_summary, runs_table};
+use crate::format::season::season_fmt;
 
+use crate::logging::logger::{self, level_filter, LogFormat};
+
+use crate::ml::elo::rating_history::{
+    game_dates, player_history, team_history, top_rated, RatingsError,
+};
 use crate::ml::model::{Model, TrainingError};
+use crate::ml::models::elo_models::elo_tracker::EloTracker;
 use crate::ml::models::registration::Registration;
+use crate::ml::registry::{
+    self, model_args, model_params, model_runs, promoted_run, remove_run, RegistryError, Run,
+    RunMetadata,
+};
 
+use crate::proc::export::{export_nba, ExportError, ExportFormat};
 use crate::proc::forecast::{forecast_nba, ForecastError};
-use crate::proc::historian::{annotate_nba, chronicle_nba, observe_nba};
+use crate::proc::grade::{grade_nba, GradeError};
+use crate::proc::historian::{
+    annotate_nba, catalog_nba, chronicle_nba, lint_nba, observe_nba, sign_volumes, stale_nba,
+    survey_nba,
+};
+use crate::proc::prophet::write_predictions;
 use crate::proc::refresher::update_source_data;
+use crate::proc::remote::{mirror, Direction, RemoteError};
+use crate::proc::store::inscribe;
+
+use crate::serve::server::{serve, ApiState, ServeError};
+
+use crate::stats::chronology::{last_game_date, Chronology, ChronologyError};
+use crate::stats::game_query::{parse_season_range, GameQuery, StatPredicate};
+use crate::stats::nba_kind::NBAStatKind;
+use crate::stats::nba_schema::schema_column;
+use crate::stats::prediction::Prediction;
+use crate::stats::schema_report::compatible_seasons;
+use crate::stats::season_period::SeasonPeriod;
+use crate::stats::stat_column::StatColumn;
+use crate::stats::visiting::Visiting;
 
-use crate::stats::chronology::{Chronology, ChronologyError};
+use crate::storage;
+use crate::storage::s3::{S3Error, S3Storage};
+
+use crate::tui::dashboard::{Dashboard, DashboardData, DashboardError};
+
+use crate::types::{GameDate, GameId, GameResult};
 
 #[derive(Parser)]
 #[command(name = "warheads")]
@@ -28,6 +92,42 @@ use crate::stats::chronology::{Chronology, ChronologyError};
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+
+    /// path to a warheads.toml config file
+    #[arg(long, global = true)]
+    config: Option<PathBuf>,
+
+    /// root directory of the nba data [env: WARHEADS_DATA]
+    #[arg(long, global = true)]
+    data: Option<PathBuf>,
+
+    /// http headers file used for nba requests [env: WARHEADS_HEADERS]
+    #[arg(long, global = true)]
+    headers: Option<PathBuf>,
+
+    /// directory models are stored in [env: WARHEADS_MODELS]
+    #[arg(long, global = true)]
+    models: Option<PathBuf>,
+
+    /// directory for cached files [env: WARHEADS_CACHE]
+    #[arg(long, global = true)]
+    cache: Option<PathBuf>,
+
+    /// where data files are stored: local or s3 [env: WARHEADS_STORAGE]
+    #[arg(long, global = true)]
+    storage: Option<StorageKind>,
+
+    /// log more detail. -vv logs everything
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    verbose: u8,
+
+    /// log only warnings and errors. -qq logs only errors and -qqq nothing
+    #[arg(short, long, action = ArgAction::Count, global = true)]
+    quiet: u8,
+
+    /// how messages are logged to stderr
+    #[arg(long, value_enum, global = true, default_value_t = LogFormat::Human)]
+    log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
@@ -38,18 +138,171 @@ enum Commands {
         #[command(subcommand)]
         action: ChecksumCommand,
     },
+    Edits {
+        #[command(subcommand)]
+        action: EditsCommand,
+    },
+    /// show where warheads reads and writes its data
+    Config,
+    /// show which stat schemas and fields are recorded in each season
+    SchemaReport {
+        /// comma separated box score columns (e.g. `fga,fg3m,tov`) a model needs. reports the
+        /// seasons in which every box score records them.
+        #[arg(long, value_delimiter = ',', value_parser = parse_schema_column)]
+        require: Vec<StatColumn>,
+    },
+    /// check stored volumes for box scores that don't add up
+    Lint {
+        /// prompt for a correction to each violation and save it to the edit list
+        #[arg(long)]
+        fix: bool,
+    },
+    /// find team (or player) games matching every filter given
+    Query {
+        /// team abbreviation at the time of the game (e.g. LAL)
+        #[arg(long)]
+        team: Option<String>,
+        /// opponent abbreviation at the time of the game
+        #[arg(long)]
+        opponent: Option<String>,
+        /// only games this player (name or id) appeared in. stat filters apply to the player's
+        /// box score
+        #[arg(long)]
+        player: Option<String>,
+        /// seasons by the year they start, e.g. `2015` or `2010..2024`
+        #[arg(long, value_parser = parse_season_range)]
+        seasons: Option<RangeInclusive<i32>>,
+        /// RegularSeason, Playoffs, PlayIn or PreSeason
+        #[arg(long, value_parser = parse_season_period)]
+        period: Option<SeasonPeriod>,
+        /// home or away
+        #[arg(long, value_parser = parse_visiting)]
+        venue: Option<Visiting>,
+        /// first date to include (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<GameDate>,
+        /// last date to include (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<GameDate>,
+        /// W or L
+        #[arg(long)]
+        result: Option<GameResult>,
+        /// box score condition such as `pts>120` or `fg3m>=15`. may be repeated
+        #[arg(long = "stat")]
+        stats: Vec<StatPredicate>,
+        #[arg(long, value_enum, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+    /// write stored box scores to a file for use outside warheads
+    Export {
+        #[arg(long, value_enum, default_value_t = ExportFormat::Csv)]
+        format: ExportFormat,
+        /// team or player box scores
+        #[arg(long, value_parser = parse_stat_kind, default_value = "team")]
+        kind: NBAStatKind,
+        /// seasons by the year they start, e.g. `2015` or `2010..2024`. defaults to every season
+        #[arg(long, value_parser = parse_season_range)]
+        seasons: Option<RangeInclusive<i32>>,
+        /// file to write. defaults to `exports/` in the data directory
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+    /// list, inspect, promote and remove training runs
+    Models {
+        #[command(subcommand)]
+        action: ModelsCommand,
+    },
+    /// copy data files and model artifacts between the data directory and the s3 bucket
+    Remote {
+        #[command(subcommand)]
+        action: RemoteCommand,
+    },
+    /// score saved forecasts against the games since played: accuracy, log loss and brier score
+    Grade {
+        /// grade only this model. defaults to every model with saved forecasts
+        #[arg(long)]
+        model: Option<String>,
+        /// only grade games on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<GameDate>,
+    },
+    /// look up players and franchises by name
+    Directory {
+        #[command(subcommand)]
+        action: DirectoryCommand,
+    },
+    /// elo rating history of a player or team, or the best rated players on a date
+    Ratings {
+        /// read ratings from this run of the elo model instead of the promoted one
+        #[arg(long, global = true)]
+        run: Option<String>,
+        #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+        #[command(subcommand)]
+        action: RatingsCommand,
+    },
     Train {
+        /// promote the new run even if the model already has a promoted run
+        #[arg(long)]
+        promote: bool,
+        model_name: String,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// learn the games played since a model was trained or last updated, without retraining it
+    Update {
+        /// update this run instead of the promoted one
+        #[arg(long)]
+        run: Option<String>,
         model_name: String,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
     #[command(name = "eval")]
     Evaluate {
+        /// evaluate this run instead of the promoted one
+        #[arg(long)]
+        run: Option<String>,
         model_name: String,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         args: Vec<String>,
     },
     Forecast {
+        /// forecast with this run instead of the promoted one
+        #[arg(long)]
+        run: Option<String>,
+        #[arg(long, value_enum, default_value_t = ForecastFormat::Table)]
+        format: ForecastFormat,
+        /// keep the predictions so they can be graded once the games are played
+        #[arg(long)]
+        save: bool,
+        model_name: String,
+        #[arg(default_value = "7")]
+        days: usize,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
+    /// run jobs such as sync, train and forecast on a schedule until stopped
+    Daemon {
+        /// toml file of jobs. defaults to `daemon.toml` in the data directory, or without it, sync,
+        /// update elo-v1 and save its forecasts every morning
+        #[arg(long)]
+        jobs: Option<PathBuf>,
+    },
+    /// answer json requests for forecasts, games, records and ratings on localhost
+    Serve {
+        #[arg(long, default_value = "8080")]
+        port: u16,
+        /// keep this model loaded. may be repeated. defaults to every model with a promoted run
+        #[arg(long = "model")]
+        models: Vec<String>,
+    },
+    /// full screen dashboard of upcoming games, standings and player ratings
+    Tui {
+        /// forecast with this run instead of the promoted one
+        #[arg(long)]
+        run: Option<String>,
+        #[arg(default_value = "elo-v1")]
         model_name: String,
         #[arg(default_value = "7")]
         days: usize,
@@ -61,12 +314,100 @@ enum Commands {
 #[derive(Subcommand)]
 enum TrainCommand {}
 
+#[derive(Clone, Copy, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Csv,
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ForecastFormat {
+    Table,
+    Json,
+    Csv,
+    Markdown,
+}
+
 #[derive(Subcommand)]
 enum ChecksumCommand {
     Fingerprint,
     Verify,
 }
 
+#[derive(Subcommand)]
+enum ModelsCommand {
+    /// every run of a model, or of every model
+    List { model: Option<String> },
+    /// the params, data and metrics of a run. defaults to the promoted run
+    Show { model: String, run: Option<String> },
+    /// use a run for `eval` and `forecast` by default
+    Promote { model: String, run: String },
+    /// delete a run and its artifacts
+    Rm { model: String, run: String },
+}
+
+#[derive(Subcommand)]
+enum RatingsCommand {
+    /// a player's rating after every game they played
+    Player {
+        /// player name or id
+        player: String,
+    },
+    /// the mean rating of a team's players in every game of a season
+    Team {
+        /// team name, abbreviation or id. old names find the franchise (e.g. SEA for OKC)
+        team: String,
+        /// the season by the year it starts, e.g. `2015`
+        #[arg(long)]
+        season: i32,
+    },
+    /// the best rated players on a date
+    Top {
+        /// defaults to today (YYYY-MM-DD)
+        #[arg(long)]
+        date: Option<GameDate>,
+        #[arg(long, default_value = "25")]
+        limit: usize,
+    },
+}
+
+#[derive(Subcommand)]
+enum DirectoryCommand {
+    /// players whose names match, with their careers and teams
+    Player {
+        name: String,
+        #[arg(long, default_value = "10")]
+        limit: usize,
+    },
+    /// every name and abbreviation of the franchises that match
+    Team { name: String },
+    /// rebuild the directories from the stored volumes
+    Build,
+}
+
+#[derive(Subcommand)]
+enum RemoteCommand {
+    /// upload local files to the bucket
+    Push {
+        /// only files under this path in the data directory (e.g. `nba/volumes`)
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+    /// download files from the bucket into the data directory
+    Pull {
+        /// only files under this path in the data directory (e.g. `nba/volumes`)
+        #[arg(default_value = "")]
+        prefix: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum EditsCommand {
+    /// show who corrected each field of a game, when, why and from what
+    Blame { game_id: u64 },
+}
+
 /// Dispatch models to be evaluated and return results
 pub struct Dispatch {
     cli: Cli,
@@ -76,10 +417,54 @@ impl Dispatch {
     pub fn new() -> Self {
         let cli = Cli::parse();
 
+        logger::init(level_filter(cli.verbose, cli.quiet), cli.log_format);
+
         Dispatch { cli }
     }
 
     pub async fn dispatch(&self) -> Result<(), DispatchError> {
+        let flags = ConfigLayer {
+            data: self.cli.data.clone(),
+            headers: self.cli.headers.clone(),
+            models: self.cli.models.clone(),
+            cache: self.cli.cache.clone(),
+            storage: self.cli.storage,
+            ..Default::default()
+        };
+
+        let config =
+            Config::load(&flags, self.cli.config.as_deref()).map_err(DispatchError::ConfigError)?;
+
+        let storage = config.storage;
+
+        configure(config).map_err(DispatchError::ConfigError)?;
+
+        // only commands that reach the nba need headers, and everything but `init` needs data
+        if matches!(
+            self.cli.command,
+            Commands::Init
+                | Commands::Sync
+                | Commands::Forecast { .. }
+                | Commands::Serve { .. }
+                | Commands::Daemon { .. }
+        ) {
+            header_manager().map_err(DispatchError::HeaderError)?;
+        }
+
+        // data in a bucket doesn't need a local data directory
+        if !matches!(
+            self.cli.command,
+            Commands::Init | Commands::Config | Commands::Remote { .. }
+        ) && storage == StorageKind::Local
+        {
+            data().map_err(DispatchError::ConfigError)?;
+        }
+
+        // a bucket without credentials is a configuration error, caught before any storage is used
+        if storage == StorageKind::S3 && !matches!(self.cli.command, Commands::Config) {
+            S3Storage::from_config().map_err(DispatchError::StorageError)?;
+        }
+
         match &self.cli.command {
             // data procedures
             Commands::Init => initialize().await,
@@ -90,25 +475,59 @@ impl Dispatch {
                 ChecksumCommand::Verify => {
                     let expected =
                         ChecksumMap::load().map_err(|e| DispatchError::ChecksumLoadError(e))?;
-                    let actual = generate_checksums();
+                    let actual = generate_checksums_parallel(&expected).await;
+
+                    let mismatches = expected.diff(&actual);
+
+                    for class in DataClass::ALL {
+                        if !expected.covers(class) {
+                            log::warn!(
+                                "⚠️ checksum file has no {class} checksums, so {class} files were not verified."
+                            );
+                        }
+                    }
 
-                    if expected != actual {
-                        let mismatched_era_paths = expected.diff(&actual);
+                    if !mismatches.is_empty() {
                         let mut f_str = String::new();
 
-                        for path in mismatched_era_paths {
-                            f_str.push_str(&format!("\n📄 {}", path.display()));
+                        for mismatch in &mismatches {
+                            f_str.push_str(&format!("\n{mismatch}"));
                         }
-                        println!("❌ checksums do not match for eras:{f_str}");
+                        log::error!(
+                            "❌ checksums do not match for {} files:{f_str}",
+                            mismatches.len()
+                        );
                     } else {
-                        println!("✅ checksums match serialized checksum map. data is intact.");
+                        log::info!("✅ checksums match serialized checksum map. data is intact.");
+                    }
+
+                    let stale = stale_nba();
+
+                    for (season, inputs) in &stale {
+                        for input in inputs {
+                            log::warn!("⚠️ {season} volume is stale: {input}");
+                        }
+                    }
+
+                    if !stale.is_empty() {
+                        log::info!(
+                            "ℹ️  {} volumes are out of date with their inputs. run `warheads sync` to rebuild them.",
+                            stale.len()
+                        );
+                    }
+
+                    if expected.version < CHECKSUM_MAP_VERSION {
+                        log::info!(
+                            "ℹ️  checksum file uses version {} (adler-32, source files only). run `warheads checksums fingerprint` to upgrade.",
+                            expected.version
+                        );
                     }
 
                     Ok(())
                 }
 
                 ChecksumCommand::Fingerprint => {
-                    let checksums = generate_checksums();
+                    let checksums = generate_checksums_parallel(&ChecksumMap::new()).await;
 
                     checksums
                         .save()
@@ -117,50 +536,486 @@ impl Dispatch {
                     Ok(())
                 }
             },
-            // model prodecures
-            Commands::Train { model_name, args } => {
-                let mut model = get_model_from_inventory(model_name, args)?;
+            Commands::Edits { action } => match action {
+                EditsCommand::Blame { game_id } => {
+                    let edits = load_edit_list().map_err(DispatchError::EditLoadError)?;
 
-                let start = Instant::now();
+                    let game_id = GameId(*game_id);
+                    let game_edits = edits.game(game_id);
 
-                model
-                    .train(Chronology::new())
-                    .map_err(|e| DispatchError::ModelTrainingError(e))?;
+                    if game_edits.is_empty() {
+                        log::info!("ℹ️  no edits recorded for game {game_id}");
+                    }
 
-                println!(
-                    "✅ successfully trained {} in {}ms",
-                    model.model_name(),
-                    start.elapsed().as_millis()
+                    for edit in game_edits {
+                        println!("{}", format::bar(80));
+                        println!("{}", edit.blame());
+                    }
+
+                    Ok(())
+                }
+            },
+            Commands::Config => {
+                println!("{}", crate::constants::config::config());
+
+                Ok(())
+            }
+            Commands::SchemaReport { require } => {
+                let surveys = survey_nba();
+
+                for survey in &surveys {
+                    println!("{survey}");
+                }
+
+                if !require.is_empty() {
+                    let columns = require
+                        .iter()
+                        .map(|col| col.column_name())
+                        .collect::<Vec<_>>()
+                        .join(", ");
+
+                    for kind in [NBAStatKind::Team, NBAStatKind::Player] {
+                        let seasons = compatible_seasons(&surveys, kind, require);
+
+                        println!(
+                            "{}\nℹ️  {} of {} seasons have {kind} box scores with {columns}",
+                            format::bar(80),
+                            seasons.len(),
+                            surveys.len()
+                        );
+
+                        if let (Some(first), Some(last)) = (seasons.first(), seasons.last()) {
+                            println!("📅 {first} through {last}");
+                        }
+                    }
+                }
+
+                Ok(())
+            }
+
+            Commands::Lint { fix } => {
+                let mut violations = lint_nba();
+
+                for violation in &violations {
+                    println!("{violation}");
+                }
+
+                if violations.is_empty() {
+                    log::info!("✅ no rule violations found in stored volumes.");
+                } else {
+                    log::info!("ℹ️  {} rule violations found.", violations.len());
+                }
+
+                if *fix && !violations.is_empty() {
+                    let mut edits = load_edit_list().map_err(DispatchError::EditLoadError)?;
+                    let mut seasons = Vec::new();
+
+                    for violation in violations.iter_mut() {
+                        violation.candidate.prompt();
+
+                        if let Some(edit) = violation.candidate.build() {
+                            if !seasons.contains(&edit.season) {
+                                seasons.push(edit.season);
+                            }
+
+                            edits.insert(edit);
+                        }
+                    }
+
+                    save_edit_list(&edits).map_err(|_| DispatchError::EditSaveError)?;
+
+                    for season in seasons {
+                        match inscribe(season) {
+                            Ok(_) => log::info!("✅ successfully re-chronicled {season}"),
+                            Err(e) => log::error!("{e}\n❌ failed to re-chronicle {season}"),
+                        }
+                    }
+
+                    sign_volumes();
+                }
+
+                Ok(())
+            }
+            Commands::Query {
+                team,
+                opponent,
+                player,
+                seasons,
+                period,
+                venue,
+                from,
+                to,
+                result,
+                stats,
+                format,
+            } => {
+                let mut query = GameQuery::new();
+
+                if let Some(team) = team {
+                    query = query.team(team);
+                }
+                if let Some(opponent) = opponent {
+                    query = query.opponent(opponent);
+                }
+                if let Some(player) = player {
+                    let (players, _) = load_directories();
+
+                    query = query.player(
+                        resolve_player(&players, player).map_err(DispatchError::DirectoryError)?,
+                    );
+                }
+                if let Some(seasons) = seasons {
+                    query = query.seasons(seasons.clone());
+                }
+                if let Some(period) = period {
+                    query = query.period(*period);
+                }
+                if let Some(venue) = venue {
+                    query = query.visiting(*venue);
+                }
+                if let Some(from) = from {
+                    query = query.from(*from);
+                }
+                if let Some(to) = to {
+                    query = query.to(*to);
+                }
+                if let Some(result) = result {
+                    query = query.result(*result);
+                }
+                for predicate in stats {
+                    query = query.stat(*predicate);
+                }
+
+                let rows = Chronology::new()
+                    .query(&query)
+                    .map_err(DispatchError::QueryError)?;
+
+                match format {
+                    OutputFormat::Table => {
+                        println!("{}", query_table(&rows, query.predicates()));
+                        log::info!("ℹ️  {} games matched.", rows.len());
+                    }
+                    OutputFormat::Csv => {
+                        print!("{}", query_csv(&rows).map_err(DispatchError::CsvError)?)
+                    }
+                    OutputFormat::Json => {
+                        println!("{}", query_json(&rows).map_err(DispatchError::JsonError)?)
+                    }
+                }
+
+                Ok(())
+            }
+            Commands::Export {
+                format,
+                kind,
+                seasons,
+                out,
+            } => {
+                let years = seasons.clone().unwrap_or_else(|| {
+                    let lifespan = nba_lifespan();
+                    lifespan.start..=lifespan.end - 1
+                });
+
+                let path = out.clone().unwrap_or_else(|| {
+                    nba_export_path(*kind, *years.start(), *years.end(), format.ext())
+                });
+
+                let count = export_nba(&path, *format, *kind, &years)
+                    .map_err(DispatchError::ExportError)?;
+
+                log::info!(
+                    "✅ exported {count} {kind} box scores to {}",
+                    path.display()
                 );
+
                 Ok(())
             }
 
+            Commands::Models { action } => {
+                match action {
+                    ModelsCommand::List { model } => {
+                        let models = match model {
+                            Some(model) => vec![model.clone()],
+                            None => registry::registered_models()
+                                .map_err(DispatchError::RegistryError)?,
+                        };
+
+                        if models.is_empty() {
+                            log::info!("ℹ️  no training runs yet. try `warheads train <model>`");
+                        }
+
+                        for model in models {
+                            let runs = model_runs(&model).map_err(DispatchError::RegistryError)?;
+
+                            let promoted = promoted_run(&model);
+
+                            println!("{}\n", runs_table(&model, &runs, promoted.as_deref()));
+                        }
+                    }
+                    ModelsCommand::Show { model, run } => {
+                        let promoted = promoted_run(model);
+
+                        let run_id = run
+                            .clone()
+                            .or(promoted.clone())
+                            .ok_or_else(|| DispatchError::ModelNotTrained(model.to_owned()))?;
+
+                        let run = RunMetadata::load(model, &run_id)
+                            .map_err(DispatchError::RegistryError)?;
+
+                        println!(
+                            "{}",
+                            run_summary(&run, promoted.as_deref() == Some(run_id.as_str()))
+                        );
+                    }
+                    ModelsCommand::Promote { model, run } => {
+                        registry::promote(model, run).map_err(DispatchError::RegistryError)?;
+
+                        log::info!("✅ promoted run {run} of {model}");
+                    }
+                    ModelsCommand::Rm { model, run } => {
+                        let count = remove_run(model, run).map_err(DispatchError::RegistryError)?;
+
+                        log::info!("✅ removed run {run} of {model} ({count} files)");
+                    }
+                }
+
+                Ok(())
+            }
+
+            Commands::Remote { action } => {
+                let (direction, prefix) = match action {
+                    RemoteCommand::Push { prefix } => (Direction::Push, prefix),
+                    RemoteCommand::Pull { prefix } => (Direction::Pull, prefix),
+                };
+
+                let count = mirror(direction, prefix).map_err(DispatchError::RemoteError)?;
+
+                log::info!("✅ copied {count} files");
+
+                Ok(())
+            }
+            Commands::Grade { model, since } => {
+                let grades =
+                    grade_nba(model.as_deref(), *since).map_err(DispatchError::GradeError)?;
+
+                if grades.is_empty() {
+                    log::info!(
+                        "ℹ️  no saved forecasts to grade. try `warheads forecast <model> --save`"
+                    );
+                }
+
+                for grade in &grades {
+                    println!("{}", grade_table(grade));
+
+                    if grade.pending > 0 {
+                        log::info!(
+                            "ℹ️  {} forecast games have not been played or synced yet.",
+                            grade.pending
+                        );
+                    }
+                }
+
+                Ok(())
+            }
+            Commands::Directory { action } => {
+                match action {
+                    DirectoryCommand::Player { name, limit } => {
+                        let (players, teams) = load_directories();
+
+                        let matches = players.search(name);
+
+                        if matches.is_empty() {
+                            return Err(DispatchError::DirectoryError(
+                                DirectoryError::UnknownPlayer(name.to_owned()),
+                            ));
+                        }
+
+                        let shown = &matches[..matches.len().min(*limit)];
+
+                        println!("{}", player_matches(name, shown, &players, &teams));
+
+                        if matches.len() > shown.len() {
+                            log::info!("ℹ️  {} more players match", matches.len() - shown.len());
+                        }
+                    }
+                    DirectoryCommand::Team { name } => {
+                        let (_, teams) = load_directories();
+
+                        let matches = teams.find(name);
+
+                        if matches.is_empty() {
+                            return Err(DispatchError::DirectoryError(
+                                DirectoryError::UnknownTeam(name.to_owned()),
+                            ));
+                        }
+
+                        for franchise in matches.iter().filter_map(|id| teams.franchise(*id)) {
+                            println!("{}\n", franchise_history(franchise));
+                        }
+                    }
+                    DirectoryCommand::Build => catalog_nba(),
+                }
+
+                Ok(())
+            }
+            Commands::Ratings {
+                run,
+                format,
+                action,
+            } => {
+                let mut tracker = EloTracker::new();
+
+                let elo = tracker.model_name();
+
+                let run_id = use_run(&mut tracker, run.as_deref())?;
+
+                let tracker = EloTracker::from_run(run_id.as_deref())
+                    .map_err(|_| DispatchError::ModelNotTrained(elo))?;
+                let ratings = tracker.historical_ratings();
+
+                let mut chronology = Chronology::new();
+
+                chronology.load_all().map_err(DispatchError::HistoryError)?;
+
+                let directory = chronology.player_directory();
+
+                let (title, history) = match action {
+                    RatingsCommand::Player { player } => {
+                        let (players, _) = load_directories();
+
+                        let player_id = resolve_player(&players, player)
+                            .map_err(DispatchError::DirectoryError)?;
+
+                        let name = players
+                            .get(player_id)
+                            .map(|name| name.to_string())
+                            .unwrap_or_else(|| player_id.to_string());
+
+                        let history =
+                            player_history(ratings, &game_dates(chronology.games()), player_id);
+
+                        if history.is_empty() {
+                            return Err(DispatchError::RatingsError(RatingsError::NoRatings(name)));
+                        }
+
+                        (name, history)
+                    }
+                    RatingsCommand::Team { team, season } => {
+                        let (_, teams) = load_directories();
+
+                        let team_id =
+                            resolve_team(&teams, team).map_err(DispatchError::DirectoryError)?;
+
+                        let history = team_history(ratings, chronology.games(), team_id, *season);
+
+                        // the name the franchise played the season under
+                        let name = history
+                            .first()
+                            .and_then(|point| teams.franchise(team_id)?.identity_on(point.date))
+                            .map(|identity| identity.name.0.clone())
+                            .unwrap_or_else(|| team.to_owned());
+
+                        if history.is_empty() {
+                            return Err(DispatchError::RatingsError(RatingsError::NoGames(
+                                name,
+                                season_fmt(*season),
+                            )));
+                        }
+
+                        (format!("{name} {}", season_fmt(*season)), history)
+                    }
+                    RatingsCommand::Top { date, limit } => {
+                        let date = date.unwrap_or_else(GameDate::today);
+
+                        let top = top_rated(ratings, &game_dates(chronology.games()), date, *limit);
+
+                        match format {
+                            OutputFormat::Table => println!("{}", top_table(date, &top, directory)),
+                            OutputFormat::Csv => print!(
+                                "{}",
+                                top_csv(&top, directory).map_err(DispatchError::CsvError)?
+                            ),
+                            OutputFormat::Json => println!(
+                                "{}",
+                                top_json(&top, directory).map_err(DispatchError::JsonError)?
+                            ),
+                        }
+
+                        return Ok(());
+                    }
+                };
+
+                match format {
+                    OutputFormat::Table => println!("{}", rating_chart(&title, &history)),
+                    OutputFormat::Csv => print!(
+                        "{}",
+                        rating_history_csv(&history).map_err(DispatchError::CsvError)?
+                    ),
+                    OutputFormat::Json => println!(
+                        "{}",
+                        serde_json::to_string_pretty(&history).map_err(DispatchError::JsonError)?
+                    ),
+                }
+
+                Ok(())
+            }
+            // model prodecures
+            Commands::Train {
+                promote,
+                model_name,
+                args,
+            } => train_model(model_name, args, *promote),
+
+            Commands::Update {
+                run,
+                model_name,
+                args,
+            } => update_model(model_name, args, run.as_deref()),
+
             Commands::Forecast {
+                run,
+                format,
+                save,
                 model_name,
                 days,
                 args,
             } => {
-                let mut model = get_model_from_inventory(model_name, &args)?;
+                let (name, run_id, predictions) =
+                    forecast_model(model_name, args, run.as_deref(), *days).await?;
 
-                model
-                    .initialize()
-                    .map_err(|_| DispatchError::ModelNotTrained(model_name.to_owned()))?;
+                let output = match format {
+                    ForecastFormat::Table => predictions_table(&predictions),
+                    ForecastFormat::Json => {
+                        predictions_json(&predictions).map_err(DispatchError::JsonError)?
+                    }
+                    ForecastFormat::Csv => {
+                        predictions_csv(&predictions).map_err(DispatchError::CsvError)?
+                    }
+                    ForecastFormat::Markdown => predictions_markdown(&predictions),
+                };
 
-                let predictions = forecast_nba(model, *days)
-                    .await
-                    .map_err(|e| DispatchError::ForecastError(e))?;
+                println!("{output}");
 
-                println!("{}", format::bar(80));
-                for prediction in predictions {
-                    println!("{prediction}");
+                if *save {
+                    let files = write_predictions(&name, run_id.as_deref(), &predictions)
+                        .map_err(DispatchError::PredictionSaveError)?;
+
+                    log::info!("📄 saved predictions for {} dates", files.len());
                 }
 
                 Ok(())
             }
 
-            Commands::Evaluate { model_name, args } => {
+            Commands::Evaluate {
+                run,
+                model_name,
+                args,
+            } => {
                 let mut model = get_model_from_inventory(model_name, &args)?;
 
+                use_run(&mut model, run.as_deref())?;
+
                 model
                     .initialize()
                     .map_err(|_| DispatchError::ModelNotTrained(model_name.to_owned()))?;
@@ -174,14 +1029,397 @@ impl Dispatch {
 
                 Ok(())
             }
+            Commands::Daemon { jobs } => {
+                let path = jobs.clone().unwrap_or_else(daemon_jobs_file);
+
+                let jobs = match jobs.is_some() || storage::exists(&path) {
+                    true => load_jobs(&path).map_err(DispatchError::JobConfigError)?,
+                    false => {
+                        log::info!(
+                            "ℹ️  no jobs in {}. running the default jobs",
+                            path.display()
+                        );
+
+                        default_jobs()
+                    }
+                };
+
+                let clock = SystemClock;
+                let mut runner = CommandRunner;
+
+                let status = daemon_status_file();
+
+                let mut daemon = Daemon::new(jobs, clock.now()).with_status_file(&status);
+
+                log::info!("📡 daemon started. status is kept in {}", status.display());
+
+                tokio::select! {
+                    result = daemon.run(&clock, &mut runner, None) => {
+                        result.map_err(DispatchError::DaemonError)
+                    }
+                    _ = tokio::signal::ctrl_c() => {
+                        log::info!("👋 stopping the daemon");
+
+                        Ok(())
+                    }
+                }
+            }
+            Commands::Serve { port, models } => {
+                let names = match models.is_empty() {
+                    true => registry::registered_models()
+                        .map_err(DispatchError::RegistryError)?
+                        .into_iter()
+                        .filter(|model| promoted_run(model).is_some())
+                        .collect(),
+                    false => models.clone(),
+                };
+
+                let mut resident = Vec::new();
+
+                for name in &names {
+                    let mut model = match promoted_model(name) {
+                        Ok(model) => model,
+                        Err(e) => {
+                            log::warn!("{e}\n⚠️ not serving {name}");
+                            continue;
+                        }
+                    };
+
+                    match model.initialize() {
+                        Ok(_) => {
+                            log::info!("✅ loaded {name}");
+
+                            resident.push(model);
+                        }
+                        Err(_) => log::warn!(
+                            "{}\n⚠️ not serving {name}",
+                            DispatchError::ModelNotTrained(name.to_owned())
+                        ),
+                    }
+                }
+
+                let elo = EloTracker::new().model_name();
+
+                let ratings = EloTracker::from_run(promoted_run(&elo).as_deref()).ok();
+
+                log::info!("📜 loading the chronology...");
+
+                let mut chronology = Chronology::new();
+
+                chronology.load_all().map_err(DispatchError::HistoryError)?;
+
+                serve(*port, ApiState::new(resident, chronology, ratings))
+                    .await
+                    .map_err(DispatchError::ServeError)
+            }
+            Commands::Tui {
+                run,
+                model_name,
+                days,
+                args,
+            } => {
+                let mut model = get_model_from_inventory(model_name, args)?;
+
+                let run_id = use_run(&mut model, run.as_deref())?;
+
+                // the elo ratings follow the run being looked at when it's an elo run
+                let elo = EloTracker::new().model_name();
+
+                let ratings_run = match model.model_name() == elo {
+                    true => run_id,
+                    false => promoted_run(&elo),
+                };
+
+                // the dashboard still opens without a forecast, e.g. when offline
+                let predictions = match model.initialize() {
+                    Ok(_) => forecast_nba(model, *days).await.map_err(|e| e.to_string()),
+                    Err(_) => {
+                        Err(DispatchError::ModelNotTrained(model_name.to_owned()).to_string())
+                    }
+                };
+
+                Dashboard::new(DashboardData::load(predictions, ratings_run.as_deref()))
+                    .run()
+                    .map_err(DispatchError::DashboardError)
+            }
+        }
+    }
+}
+
+fn parse_schema_column(s: &str) -> Result<StatColumn, String> {
+    schema_column(s).ok_or_else(|| format!("{s} is not a box score column"))
+}
+
+fn parse_season_period(s: &str) -> Result<SeasonPeriod, String> {
+    s.parse::<SeasonPeriod>()
+        .map_err(|_| format!("{s} is not a season period"))
+}
+
+fn parse_stat_kind(s: &str) -> Result<NBAStatKind, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "team" => Ok(NBAStatKind::Team),
+        "player" => Ok(NBAStatKind::Player),
+        _ => Err(format!("{s} is neither team nor player")),
+    }
+}
+
+fn parse_visiting(s: &str) -> Result<Visiting, String> {
+    match s.to_ascii_lowercase().as_str() {
+        "home" => Ok(Visiting::Home),
+        "away" | "road" => Ok(Visiting::Away),
+        _ => Err(format!("{s} is neither home nor away")),
+    }
+}
+
+/// read a model's artifacts from `run`, or from its promoted run, and return the run used. models
+/// trained before runs were recorded have neither and are read from the model's directory.
+fn use_run<M: Model>(model: &mut M, run: Option<&str>) -> Result<Option<String>, DispatchError> {
+    let model_name = model.model_name();
+
+    let run_id = match run {
+        Some(run_id) => {
+            RunMetadata::load(&model_name, run_id).map_err(DispatchError::RegistryError)?;
+
+            run_id.to_owned()
         }
+        None => match promoted_run(&model_name) {
+            Some(run_id) => run_id,
+            None => return Ok(None),
+        },
+    };
+
+    log::info!("ℹ️  using run {run_id} of {model_name}");
+
+    model.set_run(&run_id);
+
+    Ok(Some(run_id))
+}
+
+/// the model whose artifacts are stored under `name`, set to its promoted run and rebuilt with the
+/// params the run was trained with. `name` is a model's name including its params, e.g.
+/// `last-n-games(days=10)`. models without a promoted run are built with default params.
+fn promoted_model(name: &str) -> Result<Box<dyn Model>, DispatchError> {
+    let Some(run_id) = promoted_run(name) else {
+        return get_model_from_inventory(name, &[]);
+    };
+
+    let run = RunMetadata::load(name, &run_id).map_err(DispatchError::RegistryError)?;
+
+    let args = model_args(&run.params);
+
+    let mut model = inventory::iter::<Registration>()
+        .filter_map(|registration| get_model_from_inventory(registration.model_name, &args).ok())
+        .find(|model| model.model_name() == name)
+        .ok_or_else(|| DispatchError::UnknownModel(name.to_owned()))?;
+
+    log::info!("ℹ️  using run {run_id} of {name}");
+
+    model.set_run(&run_id);
+
+    Ok(model)
+}
+
+/// train a model on all of history and record the run. the run is promoted if `promote` is set
+/// or the model has no promoted run yet.
+fn train_model(model_name: &str, args: &[String], promote: bool) -> Result<(), DispatchError> {
+    let (registration, matches) = model_matches(model_name, args)?;
+
+    let mut model = (registration.factory)(&matches);
+
+    let name = model.model_name();
+
+    let run = Run::start(&name, model_params(&matches));
+
+    model.set_run(run.run_id());
+
+    let last_game = last_game_date();
+
+    model
+        .train(Chronology::new())
+        .map_err(|e| DispatchError::ModelTrainingError(e))?;
+
+    let run = run
+        .finish(model.evaluate(), last_game)
+        .map_err(DispatchError::RegistryError)?;
+
+    log::info!(
+        "✅ successfully trained {name} in {}ms (run {})",
+        run.duration_ms,
+        run.run_id
+    );
+
+    // the first run of a model is promoted so `eval` and `forecast` can find it
+    if promote || promoted_run(&name).is_none() {
+        registry::promote(&name, &run.run_id).map_err(DispatchError::RegistryError)?;
+
+        log::info!("✅ promoted run {} of {name}", run.run_id);
     }
+
+    sign(&[DataClass::Models]).map_err(|_| DispatchError::ChecksumSerializationError)?;
+
+    Ok(())
+}
+
+/// learn the games played since `run` of a model, or its promoted run, last learned on top of the
+/// run. the result is recorded and promoted as a new run, leaving the run it started from as it was.
+fn update_model(model_name: &str, args: &[String], run: Option<&str>) -> Result<(), DispatchError> {
+    let mut model = get_model_from_inventory(model_name, args)?;
+
+    let name = model.model_name();
+
+    let run_id = use_run(&mut model, run)?
+        .ok_or_else(|| DispatchError::ModelNotTrained(model_name.to_owned()))?;
+
+    let parent = RunMetadata::load(&name, &run_id).map_err(DispatchError::RegistryError)?;
+
+    model
+        .initialize()
+        .map_err(|_| DispatchError::ModelNotTrained(model_name.to_owned()))?;
+
+    let since = parent.learned_through();
+
+    let games = Chronology::new()
+        .training_data_since(since)
+        .map_err(DispatchError::HistoryError)?;
+
+    let Some(last_game) = games.last().map(|(card, _)| card.date()) else {
+        log::info!(
+            "ℹ️  {name} run {run_id} is up to date. it has learned every game through {since}"
+        );
+
+        return Ok(());
+    };
+
+    let run = Run::update(&parent);
+
+    model.set_run(run.run_id());
+
+    model
+        .update(&games)
+        .map_err(DispatchError::ModelTrainingError)?;
+
+    let run = run
+        .finish(model.evaluate(), Some(last_game))
+        .map_err(DispatchError::RegistryError)?;
+
+    log::info!(
+        "✅ {name} run {} learned {} games played from {} through {last_game} on top of run {run_id}",
+        run.run_id,
+        games.len(),
+        since.next()
+    );
+
+    registry::promote(&name, &run.run_id).map_err(DispatchError::RegistryError)?;
+
+    log::info!("✅ promoted run {} of {name}", run.run_id);
+
+    sign(&[DataClass::Models]).map_err(|_| DispatchError::ChecksumSerializationError)?;
+
+    Ok(())
+}
+
+/// predictions for the next `days` of games from `run` of a model, or its promoted run, with the
+/// model's name and the run that made them.
+async fn forecast_model(
+    model_name: &str,
+    args: &[String],
+    run: Option<&str>,
+    days: usize,
+) -> Result<(String, Option<String>, Vec<Prediction>), DispatchError> {
+    let mut model = get_model_from_inventory(model_name, args)?;
+
+    let name = model.model_name();
+
+    let run_id = use_run(&mut model, run)?;
+
+    model
+        .initialize()
+        .map_err(|_| DispatchError::ModelNotTrained(model_name.to_owned()))?;
+
+    let predictions = forecast_nba(model, days)
+        .await
+        .map_err(|e| DispatchError::ForecastError(e))?;
+
+    Ok((name, run_id, predictions))
+}
+
+/// runs daemon jobs the way their commands would.
+struct CommandRunner;
+
+impl JobRunner for CommandRunner {
+    async fn run(&mut self, kind: &JobKind) -> Result<(), String> {
+        let result = match kind {
+            JobKind::Sync => update_local_files().await,
+            JobKind::Train {
+                model,
+                args,
+                promote,
+            } => {
+                let (model, args, promote) = (model.clone(), args.clone(), *promote);
+
+                blocking(move || train_model(&model, &args, promote)).await
+            }
+            // a model without a run has nothing to update, so it's trained on all of history
+            JobKind::Update { model, args } => {
+                let (model, args) = (model.clone(), args.clone());
+
+                blocking(move || match update_model(&model, &args, None) {
+                    Err(DispatchError::ModelNotTrained(_)) => train_model(&model, &args, true),
+                    result => result,
+                })
+                .await
+            }
+            JobKind::Forecast {
+                model,
+                args,
+                days,
+                save,
+            } => match forecast_model(model, args, None, *days).await {
+                Ok((name, run_id, predictions)) => {
+                    log::info!("🔮 forecast {} games", predictions.len());
+
+                    match save {
+                        true => write_predictions(&name, run_id.as_deref(), &predictions)
+                            .map(|files| {
+                                log::info!("📄 saved predictions for {} dates", files.len())
+                            })
+                            .map_err(DispatchError::PredictionSaveError),
+                        false => Ok(()),
+                    }
+                }
+                Err(e) => Err(e),
+            },
+        };
+
+        result.map_err(|e| e.to_string())
+    }
+}
+
+/// run a training job on the blocking pool, so the daemon can still stop on ctrl-c while it runs.
+async fn blocking(
+    job: impl FnOnce() -> Result<(), DispatchError> + Send + 'static,
+) -> Result<(), DispatchError> {
+    tokio::task::spawn_blocking(job)
+        .await
+        .unwrap_or_else(|e| Err(DispatchError::JobPanicked(e)))
 }
 
 fn get_model_from_inventory(
     model_name: &str,
     args: &[String],
 ) -> Result<Box<dyn Model>, DispatchError> {
+    let (registration, matches) = model_matches(model_name, args)?;
+
+    let model = (registration.factory)(&matches);
+
+    Ok(model)
+}
+
+/// the registration of a model and its arguments parsed by the model's schema.
+fn model_matches(
+    model_name: &str,
+    args: &[String],
+) -> Result<(&'static Registration, clap::ArgMatches), DispatchError> {
     let registration = inventory::iter::<Registration>()
         .find(|r| r.model_name == model_name)
         .ok_or_else(|| DispatchError::UnknownModel(model_name.to_owned()))?;
@@ -193,27 +1431,65 @@ fn get_model_from_inventory(
         .try_get_matches_from(full_args.iter())
         .map_err(|e| DispatchError::ArgumentParseError(e))?;
 
-    let model = (registration.factory)(&matches);
-
-    Ok(model)
+    Ok((registration, matches))
 }
 
 #[derive(Debug, Error)]
 pub enum DispatchError {
+    #[error("{0}\n❌ failed to load configuration.")]
+    ConfigError(ConfigError),
+    #[error("{0}\n❌ failed to configure s3 storage.")]
+    StorageError(S3Error),
+    #[error("{0}\n❌ failed to load http headers for nba requests.")]
+    HeaderError(HeaderError),
+    #[error("{0}\n❌ failed to run the dashboard.")]
+    DashboardError(DashboardError),
     #[error("❌ source data was not correctly serialized. ")]
     SourceDataError,
     #[error("{0}\n❌ nba files in storage are malformed: training data could not be interpreted")]
     HistoryError(ChronologyError),
+    #[error("{0}\n❌ failed to query games from storage")]
+    QueryError(ChronologyError),
+    #[error("{0}\n❌ failed to write results as csv.")]
+    CsvError(csv::Error),
+    #[error("{0}\n❌ failed to write results as json.")]
+    JsonError(serde_json::Error),
+    #[error("{0}\n❌ failed to export box scores.")]
+    ExportError(ExportError),
+    #[error("{0}\n❌ failed to sync with remote storage.")]
+    RemoteError(RemoteError),
     #[error("❌ failed to initialize NBA data. ")]
     InitializationError,
     #[error("{0}\n❌ failed to load checksums from file.")]
     ChecksumLoadError(ChecksumMapError),
+    #[error("{0}\n❌ failed to load edits from file.")]
+    EditLoadError(EditLoadingError),
+    #[error("❌ failed to save edits to file: {}", nba_edit_file().display())]
+    EditSaveError,
     #[error("❌ failed to serialize checksums to file: {}", nba_checksum_file().display())]
     ChecksumSerializationError,
     #[error("{0}\n❌ failed to create predictions for upcoming NBA games. ")]
     ForecastError(ForecastError),
+    #[error("❌ {0}\n❌ failed to save predictions.")]
+    PredictionSaveError(std::io::Error),
+    #[error("{0}\n❌ failed to grade saved forecasts.")]
+    GradeError(GradeError),
     #[error("{0}\n❌ failed to train model ")]
     ModelTrainingError(TrainingError),
+    #[error("{0}\n❌ failed to look up a player or team.")]
+    DirectoryError(DirectoryError),
+    #[error("{0}\n❌ failed to look up ratings.")]
+    RatingsError(RatingsError),
+    #[error("{0}\n❌ failed to load daemon jobs.")]
+    JobConfigError(JobConfigError),
+    #[error("{0}\n❌ the daemon stopped.")]
+    DaemonError(DaemonError),
+    #[error("❌ {0}\n❌ the job stopped before it finished.")]
+    JobPanicked(tokio::task::JoinError),
+    #[error("{0}\n❌ failed to serve the api.")]
+    ServeError(ServeError),
+    #[error("{0}\n❌ failed to manage model runs.")]
+    RegistryError(RegistryError),
     #[error("❌ model {0} is not trained. try running `warheads train {0}` ")]
     ModelNotTrained(String),
     #[error("❌ unknown model '{0}'. ")]
@@ -226,7 +1502,7 @@ async fn initialize() -> Result<(), DispatchError> {
     observe_nba().await;
     annotate_nba().await;
     chronicle_nba();
-    println!("✅ successfully initialized NBA data in warheads directory.");
+    log::info!("✅ successfully initialized NBA data in warheads directory.");
     Ok(())
 }
 
@@ -234,10 +1510,10 @@ async fn update_local_files() -> Result<(), DispatchError> {
     let _ = update_source_data()
         .await
         .map_err(|_| DispatchError::SourceDataError)?;
-    println!("✅ successfully updated source data.");
+    log::info!("✅ successfully updated source data.");
 
     let _ = chronicle_nba();
-    println!("✅ NBA volumes created successfully.");
+    log::info!("✅ NBA volumes created successfully.");
 
     Ok(())
 }
@@ -0,0 +1,531 @@
+use crate::dapi::read_disk::{read_nba_season_shared, NBAReadError};
+use crate::dapi::season_manager::nba_lifespan_period;
+use crate::dapi::team_box_score::TeamBoxScore;
+
+use crate::stats::box_score::BoxScore;
+use crate::stats::game_obj::GameObject;
+use crate::stats::identity::Identity;
+use crate::stats::nba_kind::NBAStatKind;
+use crate::stats::stat_column::StatColumn::{self, *};
+use crate::stats::visiting::Visiting;
+
+use crate::types::{PlayerName, SeasonId, TeamName};
+
+use arrow_array::{ArrayRef, Date32Array, Int32Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{ArrowError, DataType, Field, Schema};
+
+use clap::ValueEnum;
+
+use serde::ser::SerializeMap;
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use thiserror::Error;
+
+/// the stats in every exported row, after the identity columns.
+const STAT_COLUMNS: [StatColumn; 18] = [
+    WL, MIN, FGM, FGA, FG3M, FG3A, FTM, FTA, OREB, DREB, REB, AST, STL, BLK, TOV, PF, PTS,
+    PLUS_MINUS,
+];
+
+const TEAM_IDENTITY_COLUMNS: [StatColumn; 7] = [
+    SEASON_ID,
+    TEAM_ID,
+    TEAM_ABBREVIATION,
+    TEAM_NAME,
+    GAME_ID,
+    GAME_DATE,
+    MATCHUP,
+];
+
+const PLAYER_IDENTITY_COLUMNS: [StatColumn; 9] = [
+    SEASON_ID,
+    PLAYER_ID,
+    PLAYER_NAME,
+    TEAM_ID,
+    TEAM_ABBREVIATION,
+    TEAM_NAME,
+    GAME_ID,
+    GAME_DATE,
+    MATCHUP,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExportFormat {
+    Csv,
+    Jsonl,
+    /// an arrow ipc file, readable by pandas (`pyarrow.ipc`), polars and duckdb.
+    Arrow,
+}
+
+impl ExportFormat {
+    pub fn ext(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Jsonl => "jsonl",
+            ExportFormat::Arrow => "arrow",
+        }
+    }
+}
+
+/// the columns of an export of `kind`, in order, named by `StatColumn::column_name`.
+pub fn export_columns(kind: NBAStatKind) -> Vec<StatColumn> {
+    let identity: &[StatColumn] = match kind {
+        NBAStatKind::Player => &PLAYER_IDENTITY_COLUMNS,
+        _ => &TEAM_IDENTITY_COLUMNS,
+    };
+
+    identity
+        .iter()
+        .chain(STAT_COLUMNS.iter())
+        .copied()
+        .collect()
+}
+
+/// ## ExportRow
+///
+/// one team's or one player's box score in a game, flattened with the identity of the game.
+#[derive(Debug, Clone)]
+pub struct ExportRow {
+    pub identity: Identity,
+    pub team_name: TeamName,
+    pub player_name: Option<PlayerName>,
+    pub matchup: String,
+    pub box_score: BoxScore,
+}
+
+impl ExportRow {
+    /// the rows of `kind` in `game`: one per team, or one per player on either roster.
+    pub fn from_game(game: &GameObject, kind: NBAStatKind) -> Vec<ExportRow> {
+        let mut rows = Vec::new();
+
+        for (team, opponent) in [(game.away(), game.home()), (game.home(), game.away())] {
+            let identity = Identity {
+                season_id: game.season(),
+                player_id: None,
+                team_id: team.team_id(),
+                team_abbr: team.team_abbr(),
+                game_id: game.game_id(),
+                game_date: game.game_date(),
+            };
+
+            let row = |identity: Identity, player_name, box_score: &BoxScore| ExportRow {
+                identity,
+                team_name: team.team_name(),
+                player_name,
+                matchup: matchup(team, opponent),
+                box_score: box_score.clone(),
+            };
+
+            match kind {
+                NBAStatKind::Player => {
+                    for player in team.roster_box_scores() {
+                        let identity = Identity {
+                            player_id: Some(player.player_id()),
+                            ..identity.clone()
+                        };
+
+                        rows.push(row(
+                            identity,
+                            Some(player.player_name().clone()),
+                            player.box_score(),
+                        ));
+                    }
+                }
+                _ => rows.push(row(identity, None, team.box_score())),
+            }
+        }
+
+        rows
+    }
+
+    /// the value of `col` in this row. unrecorded stats are null.
+    pub fn value(&self, col: StatColumn) -> Value {
+        let id = &self.identity;
+
+        match col {
+            SEASON_ID => json!(id.season_id.period().get_offset() + id.season_id.year()),
+            PLAYER_ID => json!(id.player_id.map(|p| p.0)),
+            PLAYER_NAME => json!(self.player_name.as_ref().map(|n| n.0.clone())),
+            TEAM_ID => json!(id.team_id.0),
+            TEAM_ABBREVIATION => json!(id.team_abbr.emphasize()),
+            TEAM_NAME => json!(self.team_name.0),
+            GAME_ID => json!(id.game_id.to_string()),
+            GAME_DATE => json!(format!("{:?}", id.game_date)),
+            MATCHUP => json!(self.matchup),
+            WL => json!(self.box_score.wl().to_string()),
+            PLUS_MINUS => json!(self.box_score.plus_minus().0),
+            col => match self.box_score.stat(col) {
+                Some(stat) => json!(stat as i64),
+                None => Value::Null,
+            },
+        }
+    }
+}
+
+/// the matchup as the nba writes it from `team`'s side, e.g. `LAL @ BOS` or `BOS vs. LAL`.
+fn matchup(team: &TeamBoxScore, opponent: &TeamBoxScore) -> String {
+    let (team_abbr, opponent_abbr) = (
+        team.team_abbr().emphasize(),
+        opponent.team_abbr().emphasize(),
+    );
+
+    match team.visiting() {
+        Visiting::Home => format!("{team_abbr} vs. {opponent_abbr}"),
+        Visiting::Away => format!("{team_abbr} @ {opponent_abbr}"),
+    }
+}
+
+/// a row as a json object with its columns in export order.
+struct OrderedRow<'a> {
+    row: &'a ExportRow,
+    columns: &'a [StatColumn],
+}
+
+impl Serialize for OrderedRow<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut map = serializer.serialize_map(Some(self.columns.len()))?;
+
+        for col in self.columns {
+            map.serialize_entry(col.column_name(), &self.row.value(*col))?;
+        }
+
+        map.end()
+    }
+}
+
+/// the arrow type of each exported column.
+fn data_type(col: StatColumn) -> DataType {
+    match col {
+        SEASON_ID => DataType::Int32,
+        PLAYER_ID | TEAM_ID => DataType::UInt64,
+        GAME_DATE => DataType::Date32,
+        PLAYER_NAME | TEAM_ABBREVIATION | TEAM_NAME | GAME_ID | MATCHUP | WL => DataType::Utf8,
+        _ => DataType::Int32,
+    }
+}
+
+fn arrow_schema(columns: &[StatColumn]) -> Schema {
+    Schema::new(
+        columns
+            .iter()
+            .map(|col| Field::new(col.column_name(), data_type(*col), true))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn record_batch(
+    schema: &Arc<Schema>,
+    columns: &[StatColumn],
+    rows: &[ExportRow],
+) -> Result<RecordBatch, ArrowError> {
+    let arrays = columns
+        .iter()
+        .map(|col| -> ArrayRef {
+            match (col, data_type(*col)) {
+                (GAME_DATE, _) => Arc::new(Date32Array::from(
+                    rows.iter()
+                        .map(|r| Some(r.identity.game_date.timestamp()))
+                        .collect::<Vec<_>>(),
+                )),
+                (col, DataType::UInt64) => Arc::new(UInt64Array::from(
+                    rows.iter()
+                        .map(|r| r.value(*col).as_u64())
+                        .collect::<Vec<_>>(),
+                )),
+                (col, DataType::Utf8) => Arc::new(StringArray::from(
+                    rows.iter()
+                        .map(|r| r.value(*col).as_str().map(str::to_string))
+                        .collect::<Vec<_>>(),
+                )),
+                (col, _) => Arc::new(Int32Array::from(
+                    rows.iter()
+                        .map(|r| r.value(*col).as_i64().map(|v| v as i32))
+                        .collect::<Vec<_>>(),
+                )),
+            }
+        })
+        .collect::<Vec<_>>();
+
+    RecordBatch::try_new(schema.clone(), arrays)
+}
+
+/// a writer for one of the export formats. rows are written a season at a time.
+enum ExportWriter {
+    Csv(csv::Writer<File>),
+    Jsonl(BufWriter<File>),
+    Arrow(arrow_ipc::writer::FileWriter<File>, Arc<Schema>),
+}
+
+impl ExportWriter {
+    fn create(
+        path: &Path,
+        format: ExportFormat,
+        columns: &[StatColumn],
+    ) -> Result<Self, ExportError> {
+        let file =
+            File::create(path).map_err(|e| ExportError::CreateError(path.to_path_buf(), e))?;
+
+        Ok(match format {
+            ExportFormat::Csv => {
+                let mut wtr = csv::Writer::from_writer(file);
+
+                wtr.write_record(columns.iter().map(|col| col.column_name()))
+                    .map_err(ExportError::CsvError)?;
+
+                ExportWriter::Csv(wtr)
+            }
+            ExportFormat::Jsonl => ExportWriter::Jsonl(BufWriter::new(file)),
+            ExportFormat::Arrow => {
+                let schema = Arc::new(arrow_schema(columns));
+
+                let wtr = arrow_ipc::writer::FileWriter::try_new(file, &schema)
+                    .map_err(ExportError::ArrowError)?;
+
+                ExportWriter::Arrow(wtr, schema)
+            }
+        })
+    }
+
+    fn write(&mut self, columns: &[StatColumn], rows: &[ExportRow]) -> Result<(), ExportError> {
+        match self {
+            ExportWriter::Csv(wtr) => {
+                for row in rows {
+                    let record = columns.iter().map(|col| match row.value(*col) {
+                        Value::Null => String::new(),
+                        Value::String(s) => s,
+                        v => v.to_string(),
+                    });
+
+                    wtr.write_record(record).map_err(ExportError::CsvError)?;
+                }
+            }
+            ExportWriter::Jsonl(wtr) => {
+                for row in rows {
+                    serde_json::to_writer(&mut *wtr, &OrderedRow { row, columns })
+                        .map_err(ExportError::JsonError)?;
+
+                    writeln!(wtr).map_err(ExportError::WriteError)?;
+                }
+            }
+            ExportWriter::Arrow(wtr, schema) => {
+                let batch = record_batch(schema, columns, rows).map_err(ExportError::ArrowError)?;
+
+                wtr.write(&batch).map_err(ExportError::ArrowError)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn finish(self) -> Result<(), ExportError> {
+        match self {
+            ExportWriter::Csv(mut wtr) => wtr.flush().map_err(ExportError::WriteError),
+            ExportWriter::Jsonl(mut wtr) => wtr.flush().map_err(ExportError::WriteError),
+            ExportWriter::Arrow(mut wtr, _) => wtr.finish().map_err(ExportError::ArrowError),
+        }
+    }
+}
+
+/// the stored eras of every season starting in `years`.
+pub fn export_eras(years: &RangeInclusive<i32>) -> Vec<SeasonId> {
+    nba_lifespan_period()
+        .into_iter()
+        .filter(|era| years.contains(&era.year()))
+        .collect()
+}
+
+/// write every `kind` box score of the seasons starting in `years` to `path`, returning the number
+/// of rows written.
+pub fn export_nba(
+    path: &Path,
+    format: ExportFormat,
+    kind: NBAStatKind,
+    years: &RangeInclusive<i32>,
+) -> Result<usize, ExportError> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).map_err(|e| ExportError::CreateError(dir.to_path_buf(), e))?;
+    }
+
+    let columns = export_columns(kind);
+
+    let mut writer = ExportWriter::create(path, format, &columns)?;
+    let mut count = 0;
+
+    for era in export_eras(years) {
+        let games = read_nba_season_shared(era).map_err(|e| ExportError::ReadError(era, e))?;
+
+        let rows = games
+            .iter()
+            .flat_map(|game| ExportRow::from_game(game, kind))
+            .collect::<Vec<_>>();
+
+        writer.write(&columns, &rows)?;
+        count += rows.len();
+
+        log::info!("✅ exported {} {kind} rows from {era}", rows.len());
+    }
+
+    writer.finish()?;
+
+    Ok(count)
+}
+
+#[derive(Debug, Error)]
+pub enum ExportError {
+    #[error("{1}\n❌ failed to read {0} for export")]
+    ReadError(SeasonId, NBAReadError),
+    #[error("{1}\n❌ failed to create export file: {path}", path = .0.display())]
+    CreateError(PathBuf, std::io::Error),
+    #[error("{0}\n❌ failed to write export file")]
+    WriteError(std::io::Error),
+    #[error("{0}\n❌ failed to write export as csv")]
+    CsvError(csv::Error),
+    #[error("{0}\n❌ failed to write export as json lines")]
+    JsonError(serde_json::Error),
+    #[error("{0}\n❌ failed to write export as arrow")]
+    ArrowError(ArrowError),
+}
+
+#[cfg(test)]
+mod test_export {
+    use super::*;
+
+    use arrow_array::Array;
+
+    fn team(id: u64, abbr: &str, visiting: &str, wl: &str, pts: u64) -> Value {
+        let box_score = |pts: u64| {
+            json!({
+                "wl": wl, "min": 240, "fgm": 40, "fga": 80, "fg3m": null, "fg3a": null,
+                "ftm": 20, "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8,
+                "blk": 5, "tov": 12, "pf": 20, "pts": pts, "plus_minus": -4
+            })
+        };
+
+        json!({
+            "team_id": id,
+            "team_abbreviation": abbr,
+            "team_name": format!("{abbr} Team"),
+            "visiting": visiting,
+            "roster": [
+                { "player_id": id * 10, "player_name": "One", "box_score": box_score(pts / 2) },
+                { "player_id": id * 10 + 1, "player_name": "Two", "box_score": box_score(pts / 2) },
+            ],
+            "box_score": box_score(pts),
+        })
+    }
+
+    fn game() -> GameObject {
+        serde_json::from_value(json!({
+            "season_id": "22015",
+            "game_date": "2016-01-10",
+            "game_id": "0021500001",
+            "home": team(1, "BOS", "Home", "L", 118),
+            "away": team(2, "LAL", "Away", "W", 124),
+        }))
+        .unwrap()
+    }
+
+    fn export(format: ExportFormat, kind: NBAStatKind) -> (tempfile::TempDir, PathBuf) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(format!("export.{}", format.ext()));
+
+        let columns = export_columns(kind);
+        let rows = ExportRow::from_game(&game(), kind);
+
+        let mut writer = ExportWriter::create(&path, format, &columns).unwrap();
+        writer.write(&columns, &rows).unwrap();
+        writer.finish().unwrap();
+
+        (dir, path)
+    }
+
+    #[test]
+    fn test_flatten_game() {
+        assert_eq!(ExportRow::from_game(&game(), NBAStatKind::Team).len(), 2);
+
+        let players = ExportRow::from_game(&game(), NBAStatKind::Player);
+
+        assert_eq!(players.len(), 4);
+        assert_eq!(players[0].value(PLAYER_ID), json!(20));
+        assert_eq!(players[0].value(MATCHUP), json!("LAL @ BOS"));
+        assert_eq!(players[2].value(MATCHUP), json!("BOS vs. LAL"));
+        assert_eq!(players[0].value(FG3M), Value::Null);
+        assert_eq!(players[0].value(PLUS_MINUS), json!(-4));
+    }
+
+    #[test]
+    fn test_csv_export() {
+        let (_dir, path) = export(ExportFormat::Csv, NBAStatKind::Team);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+        let mut lines = contents.lines();
+
+        assert_eq!(
+            lines.next().unwrap(),
+            "season_id,team_id,team_abbreviation,team_name,game_id,game_date,matchup,wl,min,fgm,fga,fg3m,fg3a,ftm,fta,oreb,dreb,reb,ast,stl,blk,tov,pf,pts,plus_minus"
+        );
+        assert_eq!(
+            lines.next().unwrap(),
+            "22015,2,LAL,LAL Team,0021500001,2016-01-10,LAL @ BOS,W,240,40,80,,,20,25,10,30,40,25,8,5,12,20,124,-4"
+        );
+        assert_eq!(lines.count(), 1);
+    }
+
+    #[test]
+    fn test_jsonl_export_keeps_column_order() {
+        let (_dir, path) = export(ExportFormat::Jsonl, NBAStatKind::Player);
+
+        let contents = std::fs::read_to_string(path).unwrap();
+
+        assert_eq!(contents.lines().count(), 4);
+
+        let first = contents.lines().next().unwrap();
+
+        assert!(first.starts_with(
+            "{\"season_id\":22015,\"player_id\":20,\"player_name\":\"One\",\"team_id\":2"
+        ));
+    }
+
+    #[test]
+    fn test_arrow_export() {
+        let (_dir, path) = export(ExportFormat::Arrow, NBAStatKind::Player);
+
+        let reader =
+            arrow_ipc::reader::FileReader::try_new(File::open(path).unwrap(), None).unwrap();
+
+        let batches = reader.collect::<Result<Vec<_>, _>>().unwrap();
+
+        assert_eq!(batches.len(), 1);
+
+        let batch = &batches[0];
+
+        assert_eq!(batch.num_rows(), 4);
+        assert_eq!(
+            batch.num_columns(),
+            export_columns(NBAStatKind::Player).len()
+        );
+
+        let fg3m = batch.column_by_name("fg3m").unwrap();
+
+        assert_eq!(fg3m.null_count(), 4);
+
+        let pts = batch
+            .column_by_name("pts")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<Int32Array>()
+            .unwrap();
+
+        assert_eq!(pts.value(0), 62);
+    }
+}
@@ -18,32 +18,41 @@ pub(crate) async fn forecast_nba(
     mut model: impl Model,
     days: usize,
 ) -> Result<Vec<Prediction>, ForecastError> {
-    println!("📥 fetching upcoming games from nba.com...");
+    log::info!("📥 fetching upcoming games from nba.com...");
 
     let mut cards = get_upcoming_games(days).await?;
 
-    println!("🗓️  successfully got upcoming nba schedule");
+    log::info!("🗓️  successfully got upcoming nba schedule");
 
-    println!("📜 loading current era chronology...");
+    log::info!("📜 loading current and previous season chronology...");
 
-    let chronology = Chronology::from_era(get_current_era());
+    let chronology = Chronology::with_lookback(get_current_era());
 
-    println!("📖 loaded current era chronology.");
+    log::info!("📖 loaded current and previous season chronology.");
 
+    Ok(predict_cards(&mut model, &mut cards, &chronology))
+}
+
+/// predict `cards` with the rosters each team is expected to play according to `chronology`.
+pub(crate) fn predict_cards(
+    model: &mut impl Model,
+    cards: &mut [GameCard],
+    chronology: &Chronology,
+) -> Vec<Prediction> {
     for card in cards.iter_mut() {
         card.add_away_roster(chronology.get_expected_roster(card.away().team_id(), card.game_id()));
         card.add_home_roster(chronology.get_expected_roster(card.home().team_id(), card.game_id()));
     }
-    println!("📖 assigned expected rosters to all cards");
+    log::info!("📖 assigned expected rosters to all cards");
 
-    println!("🔮 generating predictions...");
-    Ok(cards
+    log::info!("🔮 generating predictions...");
+    cards
         .iter()
-        .map(|card| Prediction::new(card, model.predict(&card)))
-        .collect())
+        .map(|card| Prediction::new(card, model.predict(card)))
+        .collect()
 }
 
-async fn get_upcoming_games(n: usize) -> Result<Vec<GameCard>, ForecastError> {
+pub(crate) async fn get_upcoming_games(n: usize) -> Result<Vec<GameCard>, ForecastError> {
     // Forecast NBA games
     let today = GameDate::today();
 
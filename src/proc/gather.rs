@@ -125,7 +125,7 @@ fn generate_nba_games_from_source(
             },
             ProcessingResult::Edit(edit_builder) => {
                 if edit_builder.date().is_today() {
-                    println!("⏳ game is live. omitting stats.")
+                    log::info!("⏳ game is live. omitting stats.")
                 } else {
                     edit_builder.prompt(); //starts the tui prompter
 
@@ -137,12 +137,12 @@ fn generate_nba_games_from_source(
                 }
             },
             ProcessingResult::Delete(ident) => match ident.team_or_player() {
-                NBAStatKind::Team => println!(
+                NBAStatKind::Team => log::info!(
                     "🗑️ deleting team record for {} game: {}. all associated player records will be ignored",
                     ident.team_abbr(),
                     ident.game_id
                 ),
-                NBAStatKind::Player => println!(
+                NBAStatKind::Player => log::info!(
                     "🗑️ deleting player record for id: {} game: {}. the respective game object will not be affected though stat totals may not be consistent.",
                     ident.player_id.unwrap(),
                     ident.game_id
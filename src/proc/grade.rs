@@ -0,0 +1,442 @@
+use crate::dapi::season_manager::get_current_era;
+
+use crate::format::path_manager::models_root;
+
+use crate::proc::prophet::{read_predictions, PredictionReadError, SavedForecast};
+
+use crate::stats::chronology::Chronology;
+use crate::stats::visiting::Visiting;
+
+use crate::storage;
+
+use crate::types::{GameDate, GameId};
+
+use chrono::{DateTime, NaiveTime, Utc};
+
+use std::collections::{BTreeMap, HashMap};
+use std::io;
+use std::path::{Path, PathBuf};
+
+use thiserror::Error;
+
+/// probabilities are clamped this far from 0 and 1 so a certain miss doesn't make log loss infinite.
+const EPSILON: f64 = 1e-15;
+
+/// ## Scorecard
+///
+/// how well a set of predictions called the games they were made for.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Scorecard {
+    games: u64,
+    correct: u64,
+    log_loss: f64,
+    brier: f64,
+}
+
+impl Scorecard {
+    /// grade a prediction that the home team wins with `probability`.
+    pub fn add(&mut self, probability: f64, home_won: bool) {
+        let actual = home_won as u8 as f64;
+        let p = probability.clamp(EPSILON, 1.0 - EPSILON);
+
+        self.games += 1;
+        self.correct += ((probability >= 0.5) == home_won) as u64;
+        self.log_loss -= actual * p.ln() + (1.0 - actual) * (1.0 - p).ln();
+        self.brier += (probability - actual).powi(2);
+    }
+
+    pub fn games(&self) -> u64 {
+        self.games
+    }
+
+    /// the share of games whose favorite won.
+    pub fn accuracy(&self) -> f64 {
+        self.mean(self.correct as f64)
+    }
+
+    pub fn log_loss(&self) -> f64 {
+        self.mean(self.log_loss)
+    }
+
+    pub fn brier(&self) -> f64 {
+        self.mean(self.brier)
+    }
+
+    fn mean(&self, total: f64) -> f64 {
+        match self.games {
+            0 => f64::NAN,
+            n => total / n as f64,
+        }
+    }
+}
+
+/// ## ModelGrade
+///
+/// a model's live record: every game it forecast that has since been played.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ModelGrade {
+    pub model_name: String,
+    pub overall: Scorecard,
+    /// keyed by run id. forecasts made before runs are under `unversioned`.
+    pub runs: BTreeMap<String, Scorecard>,
+    /// keyed by the (year, month) the games were played in.
+    pub months: BTreeMap<(i32, u32), Scorecard>,
+    /// forecast games that haven't been played (or synced) yet.
+    pub pending: usize,
+}
+
+/// the forecast of a game that counts towards a grade.
+#[derive(Debug, Clone, Copy)]
+struct Graded<'a> {
+    generated_at: DateTime<Utc>,
+    date: GameDate,
+    probability: f64,
+    run_id: Option<&'a str>,
+}
+
+/// grade a model's saved forecasts against `results`, whether the home team won each completed
+/// game.
+///
+/// only forecasts saved before the day of the game began count, so a forecast saved on game day,
+/// possibly after the final, is never graded. when a game was forecast more than once the latest of those
+/// is graded: once in the model's overall and monthly record, and once for each run that
+/// forecast it.
+pub fn grade_forecasts(
+    model_name: &str,
+    forecasts: &[SavedForecast],
+    results: &HashMap<GameId, bool>,
+    since: Option<GameDate>,
+) -> ModelGrade {
+    let mut latest = HashMap::<GameId, Graded>::new();
+    let mut latest_by_run = HashMap::<(Option<&str>, GameId), Graded>::new();
+
+    for forecast in forecasts {
+        if since.is_some_and(|since| forecast.date < since) {
+            continue;
+        }
+
+        if !saved_before_game_day(forecast) {
+            continue;
+        }
+
+        for prediction in &forecast.predictions {
+            let game_id = prediction.card().game_id();
+
+            let graded = Graded {
+                generated_at: forecast.generated_at,
+                date: prediction.date(),
+                probability: prediction.probability(),
+                run_id: forecast.run_id.as_deref(),
+            };
+
+            keep_latest(latest.entry(game_id).or_insert(graded), graded);
+            keep_latest(
+                latest_by_run
+                    .entry((graded.run_id, game_id))
+                    .or_insert(graded),
+                graded,
+            );
+        }
+    }
+
+    let mut grade = ModelGrade {
+        model_name: model_name.to_owned(),
+        ..Default::default()
+    };
+
+    for (game_id, graded) in &latest {
+        let Some(home_won) = results.get(game_id) else {
+            grade.pending += 1;
+            continue;
+        };
+
+        let (year, month, _) = graded.date.destructure();
+
+        grade.overall.add(graded.probability, *home_won);
+        grade
+            .months
+            .entry((year, month))
+            .or_default()
+            .add(graded.probability, *home_won);
+    }
+
+    for ((run_id, game_id), graded) in &latest_by_run {
+        if let Some(home_won) = results.get(game_id) {
+            grade
+                .runs
+                .entry(run_id.unwrap_or("unversioned").to_owned())
+                .or_default()
+                .add(graded.probability, *home_won);
+        }
+    }
+
+    grade
+}
+
+/// whether `forecast` was saved before midnight utc on the day of its games, which is the evening
+/// before in the us, so before any of them could have tipped off.
+fn saved_before_game_day(forecast: &SavedForecast) -> bool {
+    forecast.generated_at < forecast.date.0.and_time(NaiveTime::MIN).and_utc()
+}
+
+fn keep_latest<'a>(kept: &mut Graded<'a>, graded: Graded<'a>) {
+    if graded.generated_at > kept.generated_at {
+        *kept = graded;
+    }
+}
+
+/// grade every model with saved forecasts (or just `model_name`) against the games played this
+/// season and last.
+pub fn grade_nba(
+    model_name: Option<&str>,
+    since: Option<GameDate>,
+) -> Result<Vec<ModelGrade>, GradeError> {
+    let models = match model_name {
+        Some(model_name) => vec![model_name.to_owned()],
+        None => forecasting_models()?,
+    };
+
+    if models.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let results = Chronology::with_lookback(get_current_era())
+        .games()
+        .map(|game| (game.game_id(), game.winning_side() == Visiting::Home))
+        .collect::<HashMap<_, _>>();
+
+    let mut grades = Vec::with_capacity(models.len());
+
+    for model in models {
+        let forecasts = read_predictions(&model).map_err(GradeError::PredictionReadError)?;
+
+        grades.push(grade_forecasts(&model, &forecasts, &results, since));
+    }
+
+    Ok(grades)
+}
+
+/// every model with forecasts saved under its `predictions` directory.
+pub fn forecasting_models() -> Result<Vec<String>, GradeError> {
+    let root = models_root();
+
+    let files = storage::list(&root).map_err(|e| GradeError::ListError(root.clone(), e))?;
+
+    let mut models = files
+        .iter()
+        .filter_map(|path| forecasting_model(&root, path))
+        .collect::<Vec<_>>();
+
+    models.sort();
+    models.dedup();
+
+    Ok(models)
+}
+
+/// the model a `{model}/predictions/{date}/{forecast}.json` file belongs to.
+fn forecasting_model(root: &Path, path: &Path) -> Option<String> {
+    let parts = path
+        .strip_prefix(root)
+        .ok()?
+        .iter()
+        .map(|part| part.to_str())
+        .collect::<Option<Vec<_>>>()?;
+
+    match parts.as_slice() {
+        [model, "predictions", _, _] => Some(model.to_string()),
+        _ => None,
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum GradeError {
+    #[error("{1}\n❌ failed to list models in {path}", path = .0.display())]
+    ListError(PathBuf, io::Error),
+    #[error("{0}\n❌ failed to read saved forecasts.")]
+    PredictionReadError(PredictionReadError),
+}
+
+#[cfg(test)]
+mod test_grade {
+    use super::{forecasting_model, grade_forecasts, Scorecard};
+
+    use crate::proc::prophet::SavedForecast;
+
+    use crate::stats::gamecard::GameCard;
+    use crate::stats::prediction::Prediction;
+    use crate::stats::record::Record;
+    use crate::stats::season_period::SeasonPeriod;
+    use crate::stats::teamcard::TeamCard;
+
+    use crate::types::{GameDate, GameId, SeasonId, TeamAbbreviation, TeamId, TeamName};
+
+    use chrono::{DateTime, TimeZone, Utc};
+
+    use std::collections::HashMap;
+    use std::path::Path;
+    use std::str::FromStr;
+
+    fn prediction(game_id: &str, date: &str, probability: f64) -> Prediction {
+        let team = |id: u64, name: &str, abbr: &str| {
+            TeamCard::new(
+                TeamId(id),
+                TeamName(name.to_owned()),
+                TeamAbbreviation::from_str(abbr).unwrap(),
+                Record::new(),
+            )
+        };
+
+        let card = GameCard::new(
+            GameId::from(game_id),
+            SeasonId::from((2024, SeasonPeriod::RegularSeason)),
+            GameDate::from_str(date).unwrap(),
+            team(1610612738, "Boston Celtics", "BOS"),
+            team(1610612752, "New York Knicks", "NYK"),
+        );
+
+        Prediction::new(&card, probability)
+    }
+
+    fn forecast(
+        run_id: Option<&str>,
+        generated_at: DateTime<Utc>,
+        predictions: Vec<Prediction>,
+    ) -> SavedForecast {
+        SavedForecast {
+            model_name: "elo".to_owned(),
+            run_id: run_id.map(str::to_owned),
+            generated_at,
+            date: predictions[0].date(),
+            predictions,
+        }
+    }
+
+    fn at(day: u32, hour: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 10, day, hour, 0, 0).unwrap()
+    }
+
+    #[test]
+    fn test_scorecard() {
+        let mut scorecard = Scorecard::default();
+
+        scorecard.add(0.8, true);
+        scorecard.add(0.6, false);
+
+        assert_eq!(scorecard.games(), 2);
+        assert_eq!(scorecard.accuracy(), 0.5);
+        assert!((scorecard.brier() - (0.04 + 0.36) / 2.0).abs() < 1e-12);
+        assert!((scorecard.log_loss() - -(0.8f64.ln() + 0.4f64.ln()) / 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_scorecard_certain_miss_is_finite() {
+        let mut scorecard = Scorecard::default();
+
+        scorecard.add(1.0, false);
+
+        assert!(scorecard.log_loss().is_finite());
+        assert_eq!(scorecard.brier(), 1.0);
+    }
+
+    #[test]
+    fn test_grade_latest_forecast_before_tipoff() {
+        let forecasts = vec![
+            forecast(
+                Some("a"),
+                at(20, 12),
+                vec![prediction("0022400001", "2024-10-22", 0.3)],
+            ),
+            forecast(
+                Some("b"),
+                at(21, 12),
+                vec![prediction("0022400001", "2024-10-22", 0.9)],
+            ),
+            // saved the day after the game, once the result was known
+            forecast(
+                Some("b"),
+                at(23, 12),
+                vec![prediction("0022400001", "2024-10-22", 0.1)],
+            ),
+            forecast(
+                Some("b"),
+                at(22, 12),
+                vec![prediction("0022400002", "2024-10-24", 0.7)],
+            ),
+        ];
+
+        let results = HashMap::from([(GameId::from("0022400001"), true)]);
+
+        let grade = grade_forecasts("elo", &forecasts, &results, None);
+
+        assert_eq!(grade.overall.games(), 1);
+        assert_eq!(grade.overall.accuracy(), 1.0);
+        assert!((grade.overall.brier() - 0.01).abs() < 1e-12);
+        assert_eq!(grade.pending, 1);
+
+        assert_eq!(grade.runs["a"].accuracy(), 0.0);
+        assert_eq!(grade.runs["b"].accuracy(), 1.0);
+        assert_eq!(grade.months[&(2024, 10)].games(), 1);
+    }
+
+    #[test]
+    fn test_grade_skips_game_day_forecasts() {
+        let forecasts = vec![
+            forecast(
+                Some("a"),
+                at(21, 12),
+                vec![prediction("0022400001", "2024-10-22", 0.9)],
+            ),
+            // saved the same day, after an afternoon game had finished
+            forecast(
+                Some("a"),
+                at(22, 19),
+                vec![prediction("0022400001", "2024-10-22", 0.1)],
+            ),
+        ];
+
+        let results = HashMap::from([(GameId::from("0022400001"), true)]);
+
+        let grade = grade_forecasts("elo", &forecasts, &results, None);
+
+        assert_eq!(grade.overall.games(), 1);
+        assert!((grade.overall.brier() - 0.01).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_grade_since() {
+        let forecasts = vec![forecast(
+            None,
+            DateTime::<Utc>::MIN_UTC,
+            vec![prediction("0022400001", "2024-10-22", 0.6)],
+        )];
+
+        let results = HashMap::from([(GameId::from("0022400001"), false)]);
+
+        let since = GameDate::from_str("2024-10-23").ok();
+
+        assert_eq!(
+            grade_forecasts("elo", &forecasts, &results, since)
+                .overall
+                .games(),
+            0
+        );
+
+        let grade = grade_forecasts("elo", &forecasts, &results, None);
+
+        assert_eq!(grade.runs["unversioned"].games(), 1);
+    }
+
+    #[test]
+    fn test_forecasting_model() {
+        let root = Path::new("/models");
+
+        assert_eq!(
+            forecasting_model(root, Path::new("/models/elo/predictions/2024_10_22/f.json")),
+            Some("elo".to_owned())
+        );
+        assert_eq!(
+            forecasting_model(root, Path::new("/models/elo/runs/a/run.json")),
+            None
+        );
+    }
+}
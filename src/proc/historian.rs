@@ -1,78 +1,118 @@
-use crate::checksum::checksum_map::ChecksumMap;
-use crate::checksum::sign::sign_nba;
+use crate::checksum::checksum_map::{ChecksumMap, DataClass};
+use crate::checksum::lineage::{Lineage, StaleInput};
+use crate::checksum::sign::{sign, sign_nba};
 
-use crate::dapi::read_disk::read_nba_season;
+use crate::dapi::directory::build_directories;
+use crate::dapi::read_disk::{read_nba_season, read_nba_volume_header};
 use crate::dapi::season_manager::{get_current_era, nba_lifespan_period};
+use crate::dapi::volume::{upgrade_legacy_volume, VOLUME_FORMAT_VERSION};
 
 use crate::edit::edit_list::EditList;
 use crate::edit::edit_loader::load_edit_list;
+use crate::edit::provenance::Provenance;
 
-use crate::format::path_manager::nba_checksum_file;
+use crate::format::path_manager::{
+    nba_checksum_file, nba_player_directory_file, nba_storage_path, nba_team_directory_file,
+};
 
 use crate::proc::hunting::compare_and_fetch;
 use crate::proc::hunting::fetch_and_save_nba_stats;
-use crate::proc::query::nba_annotation_file;
+use crate::proc::query::{build_nba_annotations_url, nba_annotation_file};
 use crate::proc::store::inscribe;
 
+use crate::stats::lint::{lint_game, Violation};
 use crate::stats::nba_kind::NBAStatKind;
+use crate::stats::schema_report::SeasonSchema;
+
+use crate::storage;
+
+use crate::tui::progress::Progress;
+
+use crate::types::SeasonId;
 
 pub async fn observe_nba() {
     match ChecksumMap::load() {
         Ok(checksums) => {
             let eras = nba_lifespan_period();
+
+            let progress = Progress::new(eras.len() as u64, "📡 fetching eras");
+
             for era in &eras[0..eras.len() - 1] {
                 compare_and_fetch(*era, NBAStatKind::Player, &checksums).await;
                 compare_and_fetch(*era, NBAStatKind::Team, &checksums).await;
+
+                progress.inc(1);
             }
             let current_era = get_current_era();
             match fetch_and_save_nba_stats(current_era, NBAStatKind::Player).await {
                 Ok(_) => {
-                    println!("✅ successfully fetched and saved nba player stats for {current_era}")
+                    log::info!(
+                        "✅ successfully fetched and saved nba player stats for {current_era}"
+                    )
                 }
                 Err(e) => {
-                    println!("❌ failed to fetch and save nba player stats for {current_era}\n{e}")
+                    log::error!(
+                        "❌ failed to fetch and save nba player stats for {current_era}\n{e}"
+                    )
                 }
             }
             match fetch_and_save_nba_stats(current_era, NBAStatKind::Team).await {
                 Ok(_) => {
-                    println!("✅ successfully fetched and saved nba team stats for {current_era}")
+                    log::info!("✅ successfully fetched and saved nba team stats for {current_era}")
                 }
                 Err(e) => {
-                    println!("❌ failed to fetch and save nba team stats for {current_era}\n{e}")
+                    log::error!("❌ failed to fetch and save nba team stats for {current_era}\n{e}")
                 }
             }
+
+            progress.inc(1);
+            progress.finish();
+
             match sign_nba() {
-                Ok(_) => println!(
+                Ok(_) => log::info!(
                     "✅ successfully signed nba data with checksums in {}",
                     nba_checksum_file().display()
                 ),
-                Err(_) => println!(
+                Err(_) => log::error!(
                     "❌ failed to sign nba data with checksums in {}",
                     nba_checksum_file().display()
                 ),
             }
         }
         Err(_) => {
-            for era in nba_lifespan_period() {
+            let eras = nba_lifespan_period();
+
+            let progress = Progress::new(eras.len() as u64, "📡 fetching eras");
+
+            for era in eras {
                 match fetch_and_save_nba_stats(era, NBAStatKind::Player).await {
                     Ok(_) => {
-                        println!("✅ successfully fetched and saved nba player stats for {era}")
+                        log::info!("✅ successfully fetched and saved nba player stats for {era}")
                     }
                     Err(e) => {
-                        println!("❌ failed to fetch and save nba player stats for {era}\n{e}")
+                        log::error!("❌ failed to fetch and save nba player stats for {era}\n{e}")
                     }
                 }
                 match fetch_and_save_nba_stats(era, NBAStatKind::Team).await {
-                    Ok(_) => println!("✅ successfully fetched and saved nba team stats for {era}"),
-                    Err(e) => println!("❌ failed to fetch and save nba team stats for {era}\n{e}"),
+                    Ok(_) => {
+                        log::info!("✅ successfully fetched and saved nba team stats for {era}")
+                    }
+                    Err(e) => {
+                        log::error!("❌ failed to fetch and save nba team stats for {era}\n{e}")
+                    }
                 }
+
+                progress.inc(1);
             }
+
+            progress.finish();
+
             match sign_nba() {
-                Ok(_) => println!(
+                Ok(_) => log::info!(
                     "✅ successfully signed nba data with checksums in {}",
                     nba_checksum_file().display()
                 ),
-                Err(_) => println!(
+                Err(_) => log::error!(
                     "❌ failed to sign nba data with checksums in {}",
                     nba_checksum_file().display()
                 ),
@@ -81,32 +121,188 @@ pub async fn observe_nba() {
     }
 }
 
+/// rebuild every volume whose source files or edits changed since it was inscribed.
 pub fn chronicle_nba() {
+    migrate_nba();
+
+    let stale_seasons = stale_nba();
+
+    let progress = Progress::new(stale_seasons.len() as u64, "📜 inscribing volumes");
+
+    for (season, stale) in stale_seasons {
+        let reasons = stale
+            .iter()
+            .map(|input| input.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        log::info!("ℹ️  rebuilding {season}: {reasons}");
+
+        match inscribe(season) {
+            Ok(_) => log::info!("✅ successfully chronicled {}", season),
+            Err(e) => log::error!("{e}\n❌ failed to chronicle {}", season),
+        }
+
+        progress.inc(1);
+    }
+
+    progress.finish();
+
+    sign_volumes();
+    catalog_nba();
+}
+
+/// every season whose volume is missing or out of date with its inputs, and why.
+pub fn stale_nba() -> Vec<(SeasonId, Vec<StaleInput>)> {
+    let edits = load_edit_list().unwrap_or_default();
+
+    nba_lifespan_period()
+        .into_iter()
+        .map(|season| (season, stale_inputs(season, &edits)))
+        .filter(|(_, stale)| !stale.is_empty())
+        .collect()
+}
+
+fn stale_inputs(season: SeasonId, edits: &EditList) -> Vec<StaleInput> {
+    if !storage::exists(&nba_storage_path(season)) {
+        return vec![StaleInput::MissingVolume];
+    }
+
+    if !matches!(read_nba_volume_header(season), Ok(Some(header)) if header.is_current()) {
+        return vec![StaleInput::OutdatedFormat];
+    }
+
+    let Ok(current) = Lineage::current(season, edits) else {
+        // without source files the volume can't be rebuilt, so it is as fresh as it can be
+        return Vec::new();
+    };
+
+    match Lineage::load(season) {
+        Ok(recorded) => recorded.stale_inputs(&current),
+        Err(_) => vec![StaleInput::MissingLineage],
+    }
+}
+
+/// add a header to every volume written before volumes had one, as long as it still reads as the
+/// current schema. volumes that can't be upgraded in place are left to be regenerated from source.
+pub fn migrate_nba() {
     for season in nba_lifespan_period() {
-        if read_nba_season(season).is_err() || season.is_current_era() {
-            match inscribe(season) {
-                Ok(_) => println!("✅ successfully chronicled {}", season),
-                Err(e) => println!("{e}\n❌ failed to chronicle {}", season),
+        let path = nba_storage_path(season);
+
+        if !matches!(read_nba_volume_header(season), Ok(None)) {
+            continue;
+        }
+
+        let Ok(bytes) = storage::read(&path) else {
+            continue;
+        };
+
+        match upgrade_legacy_volume(&bytes) {
+            Some(upgraded) => match storage::write(&path, upgraded) {
+                Ok(_) => {
+                    log::info!("✅ upgraded {season} volume to format v{VOLUME_FORMAT_VERSION}")
+                }
+                Err(e) => log::error!("❌ {e}\n❌ failed to upgrade {season} volume"),
+            },
+            None => {
+                log::info!("ℹ️  {season} volume can't be upgraded in place, it will be regenerated")
             }
         }
     }
 }
 
+/// re-sign the edit list and volumes after they have been rewritten.
+pub fn sign_volumes() {
+    match sign(&[DataClass::Edits, DataClass::Volumes]) {
+        Ok(_) => log::info!(
+            "✅ successfully signed edits and volumes with checksums in {}",
+            nba_checksum_file().display()
+        ),
+        Err(e) => log::error!(
+            "{e}\n❌ failed to sign edits and volumes with checksums in {}",
+            nba_checksum_file().display()
+        ),
+    }
+}
+
+/// rebuild the player and team directories from the stored volumes and save them.
+pub fn catalog_nba() {
+    let (players, teams) = build_directories();
+
+    match players.save() {
+        Ok(_) => log::info!(
+            "✅ saved {} players to {}",
+            players.len(),
+            nba_player_directory_file().display()
+        ),
+        Err(e) => log::error!("{e}\n❌ failed to save the player directory"),
+    }
+
+    match teams.save() {
+        Ok(_) => log::info!(
+            "✅ saved {} franchises to {}",
+            teams.franchises().count(),
+            nba_team_directory_file().display()
+        ),
+        Err(e) => log::error!("{e}\n❌ failed to save the team directory"),
+    }
+    match sign(&[DataClass::Directory]) {
+        Ok(_) => log::info!(
+            "✅ successfully signed the directories with checksums in {}",
+            nba_checksum_file().display()
+        ),
+        Err(e) => log::error!(
+            "{e}\n❌ failed to sign the directories with checksums in {}",
+            nba_checksum_file().display()
+        ),
+    }
+}
+
+/// check every stored volume against the box score consistency rules in `stats::lint`.
+pub fn lint_nba() -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    for season in nba_lifespan_period() {
+        match read_nba_season(season) {
+            Ok(games) => violations.extend(games.iter().flat_map(lint_game)),
+            Err(e) => log::error!("{e}\n❌ failed to lint {season}"),
+        }
+    }
+
+    violations
+}
+
+/// tally which schema every stored team and player box score matches, season by season.
+pub fn survey_nba() -> Vec<SeasonSchema> {
+    let mut surveys = Vec::new();
+
+    for season in nba_lifespan_period() {
+        match read_nba_season(season) {
+            Ok(games) => surveys.push(SeasonSchema::survey(season, &games)),
+            Err(e) => log::error!("{e}\n❌ failed to survey {season}"),
+        }
+    }
+
+    surveys
+}
+
 pub(crate) async fn annotate_nba() {
     match nba_annotation_file().await {
         Ok(json) => {
             let mut previous = load_edit_list().unwrap_or_default();
 
-            let new = serde_json::from_str::<EditList>(&json.to_string()).unwrap_or_default();
+            let mut new = serde_json::from_str::<EditList>(&json.to_string()).unwrap_or_default();
+
+            new.attribute(&Provenance::remote(&build_nba_annotations_url()));
 
             previous.merge(new);
 
             if let Err(_) = previous.write_to_file() {
-                println!("❌ failed to write edit list to file.");
+                log::error!("❌ failed to write edit list to file.");
             }
         }
         Err(e) => {
-            println!("{e}\n❌ failed to fetch nba annotation file.");
+            log::error!("{e}\n❌ failed to fetch nba annotation file.");
         }
     }
 }
@@ -1,5 +1,5 @@
-use crate::checksum::checksum_map::ChecksumMap;
-use crate::checksum::read_checksum::read_checksum;
+use crate::checksum::checksum_map::{ChecksumMap, DataClass};
+use crate::checksum::read_checksum::{read_checksum, read_checksum_as};
 
 use crate::dapi::write::write_serializable_with_directory;
 
@@ -24,9 +24,10 @@ pub async fn fetch_and_save_nba_stats(season: SeasonId, stat: NBAStatKind) -> Re
     match query::nba_history_json(season, stat).await {
         Ok(response_data) => match write_serializable_with_directory(&file_path, &response_data) {
             Ok(_) => {
-                println!(
+                log::info!(
                     "✅ successfully saved nba stats for {} season at file: {:?}",
-                    season, &file_path
+                    season,
+                    &file_path
                 );
                 Ok(())
             }
@@ -56,23 +57,31 @@ pub(crate) async fn compare_and_fetch(
     //
     if let Err(_) = read_checksum(&source_path) {
         if let Err(msg) = fetch_and_save_nba_stats(season_id, kind).await {
-            println!("{}", msg);
+            log::error!("{}", msg);
         } else {
-            println!("✅ successfully wrote {kind} data to file for the {season_id}");
+            log::info!("✅ successfully wrote {kind} data to file for the {season_id}");
         }
     } else if let Ok(checksum) = read_checksum(&source_path) {
-        let expected_checksum = checksums.get(&checksum_path);
+        let expected_checksum = checksums.get(DataClass::Source, &checksum_path);
+
+        // older maps recorded adler-32 checksums, so compare with the same algorithm
+        let checksum = match expected_checksum {
+            Some(expected) => {
+                read_checksum_as(&source_path, expected.algorithm()).unwrap_or(checksum)
+            }
+            None => checksum,
+        };
 
         if expected_checksum.is_none() || checksum != *expected_checksum.unwrap()
         //this might fail on new records
         {
             if let Err(msg) = fetch_and_save_nba_stats(season_id, kind).await {
-                println!("{}", msg);
+                log::error!("{}", msg);
             } else {
-                println!("✅ successfully wrote {kind} data to file for the {season_id}");
+                log::info!("✅ successfully wrote {kind} data to file for the {season_id}");
             }
         } else {
-            println!("✅ bypassing fetching {kind} data for the {season_id}, checksums match. ");
+            log::info!("✅ bypassing fetching {kind} data for the {season_id}, checksums match. ");
         }
     }
 }
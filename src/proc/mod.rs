@@ -1,12 +1,15 @@
 pub mod dispatch;
 pub mod error;
+pub mod export;
 pub mod forecast;
 pub mod gather;
+pub mod grade;
 pub mod historian;
 pub mod hunting;
 pub mod prophet;
 pub mod query;
 pub mod refresher;
+pub mod remote;
 mod revise;
 pub mod rip;
 mod store;
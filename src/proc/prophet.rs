@@ -1,33 +1,119 @@
-use crate::format::path_manager::nba_prediction_file;
-
-use crate::ml::model::Model;
+use crate::format::path_manager::{nba_prediction_file, nba_predictions_root};
 
 use crate::stats::prediction::Prediction;
 
 use crate::dapi::write::write_serializable_with_directory;
 
+use crate::storage;
+
 use crate::types::GameDate;
 
-use std::collections::HashMap;
+use chrono::{DateTime, Utc};
+
+use serde::{Deserialize, Serialize};
+
+use std::collections::BTreeMap;
 use std::io;
+use std::path::PathBuf;
+
+use thiserror::Error;
+
+/// ## SavedForecast
+///
+/// the predictions one run of a model made for the games on one date. every forecast is kept, so
+/// a date forecast several times has one file per forecast.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SavedForecast {
+    pub model_name: String,
+    /// the training run that made the predictions. `None` for models trained before runs.
+    pub run_id: Option<String>,
+    pub generated_at: DateTime<Utc>,
+    pub date: GameDate,
+    pub predictions: Vec<Prediction>,
+}
+
+// write the predictions generated by the prediction engine to date based files. saved forecasts
+// live outside the model's runs, so they aren't checksummed as model artifacts and don't need
+// re-signing.
+pub fn write_predictions(
+    model_name: &str,
+    run_id: Option<&str>,
+    predictions: &[Prediction],
+) -> Result<Vec<PathBuf>, io::Error> {
+    let generated_at = Utc::now();
 
-// write the predictions generated by the prediction engine to date based files
-pub fn write_predictions<M: Model>(
-    model: &M,
-    predictions: &Vec<Prediction>,
-) -> Result<(), io::Error> {
-    let mut date_map = HashMap::<GameDate, Vec<Prediction>>::new();
+    let mut date_map = BTreeMap::<GameDate, Vec<Prediction>>::new();
 
     for prediction in predictions {
         date_map
             .entry(prediction.date())
-            .or_insert_with(Vec::new)
+            .or_default()
             .push(prediction.clone());
     }
 
+    let mut files = Vec::new();
+
     for (date, predictions) in date_map {
-        write_serializable_with_directory(&nba_prediction_file(model, date), &predictions)?;
+        let path = nba_prediction_file(model_name, date, run_id, generated_at);
+
+        let forecast = SavedForecast {
+            model_name: model_name.to_owned(),
+            run_id: run_id.map(str::to_owned),
+            generated_at,
+            date,
+            predictions,
+        };
+
+        write_serializable_with_directory(&path, &forecast)?;
+
+        files.push(path);
     }
 
-    Ok(())
+    Ok(files)
+}
+
+/// every forecast saved for a model, oldest first.
+pub fn read_predictions(model_name: &str) -> Result<Vec<SavedForecast>, PredictionReadError> {
+    let root = nba_predictions_root(model_name);
+
+    let files =
+        storage::list(&root).map_err(|e| PredictionReadError::ListError(root.clone(), e))?;
+
+    let mut forecasts = Vec::new();
+
+    for path in files {
+        let contents = storage::read_to_string(&path)
+            .map_err(|e| PredictionReadError::ReadError(path.clone(), e))?;
+
+        // forecasts saved before they were versioned are a bare list of predictions
+        let forecast = match serde_json::from_str::<SavedForecast>(&contents) {
+            Ok(forecast) => forecast,
+            Err(e) => match serde_json::from_str::<Vec<Prediction>>(&contents) {
+                Ok(predictions) if !predictions.is_empty() => SavedForecast {
+                    model_name: model_name.to_owned(),
+                    run_id: None,
+                    generated_at: DateTime::<Utc>::MIN_UTC,
+                    date: predictions[0].date(),
+                    predictions,
+                },
+                _ => return Err(PredictionReadError::ParseError(path, e)),
+            },
+        };
+
+        forecasts.push(forecast);
+    }
+
+    forecasts.sort_by_key(|forecast| (forecast.generated_at, forecast.date));
+
+    Ok(forecasts)
+}
+
+#[derive(Debug, Error)]
+pub enum PredictionReadError {
+    #[error("{1}\n❌ failed to list saved predictions in {path}", path = .0.display())]
+    ListError(PathBuf, io::Error),
+    #[error("{1}\n❌ failed to read saved predictions from {path}", path = .0.display())]
+    ReadError(PathBuf, io::Error),
+    #[error("{1}\n❌ failed to parse saved predictions in {path}", path = .0.display())]
+    ParseError(PathBuf, serde_json::Error),
 }
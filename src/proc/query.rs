@@ -1,4 +1,4 @@
-use crate::constants::header_manager::HEADER_MANAGER;
+use crate::constants::header_manager::{header_manager, HeaderError};
 
 use crate::format::url_format::UrlFormatter;
 
@@ -55,7 +55,11 @@ pub(crate) async fn make_nba_history_request(
 
     client
         .get(&url)
-        .headers(HEADER_MANAGER.history_request_headers())
+        .headers(
+            header_manager()
+                .map_err(NBAQueryError::HeaderError)?
+                .history_request_headers(),
+        )
         .send()
         .await
         .map_err(|e| NBAQueryError::RequestError(e))
@@ -108,7 +112,11 @@ async fn make_nba_gamecard_request(date: GameDate) -> Result<Response, NBAQueryE
 
     client
         .get(&url)
-        .headers(HEADER_MANAGER.gamecard_request_headers())
+        .headers(
+            header_manager()
+                .map_err(NBAQueryError::HeaderError)?
+                .gamecard_request_headers(),
+        )
         .send()
         .await
         .map_err(|e| NBAQueryError::RequestError(e))
@@ -142,7 +150,7 @@ pub(crate) async fn nba_annotation_file() -> Result<String, NBAQueryError> {
     Ok(body)
 }
 
-fn build_nba_annotations_url() -> String {
+pub(crate) fn build_nba_annotations_url() -> String {
     //publicly shared, no worries about leaking secrets
     "https://drive.google.com/uc?export=download&id=1r8XyRZN14Z1Q9_7F6KyHOJapd4PUSzaC".to_owned()
 }
@@ -168,6 +176,9 @@ pub enum NBAQueryError {
 
     #[error("❌ Drive Resource Error")]
     DriveResourceError,
+
+    #[error("{0}\n❌ failed to load request headers")]
+    HeaderError(HeaderError),
 }
 
 #[cfg(test)]
@@ -20,24 +20,24 @@ pub(crate) async fn update_source_data() -> Result<(), ()> {
     let current_era = get_current_era();
 
     match fetch_and_save_nba_stats(current_era, NBAStatKind::Player).await {
-        Ok(_) => println!("✅ updated player source data for the {}", current_era),
-        Err(e) => println!(
+        Ok(_) => log::info!("✅ updated player source data for the {}", current_era),
+        Err(e) => log::error!(
             "{e}\n❌ failed to fetch and update NBA player source data for the {}",
             current_era
         ),
     };
 
     match fetch_and_save_nba_stats(current_era, NBAStatKind::Team).await {
-        Ok(_) => println!("✅ updated team source data for the {}", current_era),
-        Err(e) => println!(
+        Ok(_) => log::info!("✅ updated team source data for the {}", current_era),
+        Err(e) => log::error!(
             "{e}\n❌ failed to fetch and update NBA team source data for the {}",
             current_era
         ),
     };
 
     match sign_nba() {
-        Ok(_) => println!("✅ updated NBA source data checksums. "),
-        Err(_) => println!("❌ failed to update NBA source data checksum"),
+        Ok(_) => log::info!("✅ updated NBA source data checksums. "),
+        Err(_) => log::error!("❌ failed to update NBA source data checksum"),
     };
 
     Ok(())
@@ -0,0 +1,79 @@
+use crate::constants::config::config;
+
+use crate::storage::local::LocalStorage;
+use crate::storage::s3::{S3Error, S3Storage};
+use crate::storage::Storage;
+
+use std::io;
+
+use thiserror::Error;
+
+/// which way `mirror` copies files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    /// from the local data root to the bucket.
+    Push,
+    /// from the bucket to the local data root.
+    Pull,
+}
+
+/// copy every file whose key starts with `prefix` between the local data root and the s3 bucket
+/// in the config, whatever the configured storage is. files in the cache are never copied.
+/// returns the number of files copied.
+pub fn mirror(direction: Direction, prefix: &str) -> Result<usize, RemoteError> {
+    let local = LocalStorage::from_config();
+    let remote = S3Storage::from_config().map_err(RemoteError::ConnectionError)?;
+
+    let cache = local.key(&config().cache);
+
+    let (from, to): (&dyn Storage, &dyn Storage) = match direction {
+        Direction::Push => (&local, &remote),
+        Direction::Pull => (&remote, &local),
+    };
+
+    log::info!("ℹ️  copying {} → {}", from.describe(), to.describe());
+
+    let keys = from
+        .list(prefix)
+        .map_err(|e| RemoteError::ListError(from.describe(), e))?;
+
+    let mut count = 0;
+
+    for key in keys {
+        if cache
+            .as_ref()
+            .is_some_and(|cache| !cache.is_empty() && key.starts_with(&format!("{cache}/")))
+        {
+            continue;
+        }
+
+        copy(from, to, &key)?;
+
+        count += 1;
+    }
+
+    Ok(count)
+}
+
+fn copy(from: &dyn Storage, to: &dyn Storage, key: &str) -> Result<(), RemoteError> {
+    let contents = from
+        .read(key)
+        .map_err(|e| RemoteError::TransferError(key.to_string(), e))?;
+
+    to.write(key, &contents)
+        .map_err(|e| RemoteError::TransferError(key.to_string(), e))?;
+
+    log::info!("📄 {key}");
+
+    Ok(())
+}
+
+#[derive(Debug, Error)]
+pub enum RemoteError {
+    #[error("{0}\n❌ failed to connect to the s3 bucket")]
+    ConnectionError(S3Error),
+    #[error("❌ {1}\n❌ failed to list files in {0}")]
+    ListError(String, io::Error),
+    #[error("❌ {1}\n❌ failed to copy {0}")]
+    TransferError(String, io::Error),
+}
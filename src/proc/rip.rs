@@ -172,7 +172,7 @@ fn fields_to_team_box_score(
     if delete {
         Ok(ProcessingResult::Delete(identity))
     } else if edit_builder.has_corrections() {
-        println!(
+        log::error!(
             "❌ failed to create a TeamBoxScore for {team_name}. id: {team_id} game id: {game_id}"
         );
 
@@ -290,7 +290,7 @@ fn fields_to_player_box_score(
     if delete {
         Ok(ProcessingResult::Delete(identity))
     } else if edit_builder.has_corrections() {
-        println!("❌ failed to create a PlayerBoxScore for {player_name}. id: {player_id} game id: {game_id}.");
+        log::error!("❌ failed to create a PlayerBoxScore for {player_name}. id: {player_id} game id: {game_id}.");
 
         Ok(ProcessingResult::Edit(edit_builder))
     } else {
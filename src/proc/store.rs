@@ -1,7 +1,10 @@
+use crate::checksum::lineage::{Lineage, LineageError};
+
 use crate::dapi::team_box_score::TeamBoxScore;
 
 use crate::edit::edit_builder::EditBuilder;
 use crate::edit::edit_loader::{load_edit_list, save_edit_list, EditLoadingError};
+use crate::edit::provenance::Provenance;
 
 use crate::proc::error::ReadProcessError;
 use crate::proc::gather::load_season_from_source;
@@ -33,6 +36,8 @@ pub enum InscriptionError {
     SaveEditListError,
     #[error("❌ failed to construct an edit, some fields were missing.")]
     BuildEditError,
+    #[error("{0}\n❌ failed to record the lineage of the volume.")]
+    LineageError(LineageError),
 }
 
 pub fn inscribe(era: SeasonId) -> Result<(), InscriptionError> {
@@ -47,7 +52,7 @@ pub fn inscribe(era: SeasonId) -> Result<(), InscriptionError> {
 
     match pairs {
         Err(mut edit_builders) => {
-            println!(
+            log::info!(
                 "ℹ️  there are {} corrections to make to Team box scores for the {} season.",
                 edit_builders.len(),
                 era
@@ -62,6 +67,9 @@ pub fn inscribe(era: SeasonId) -> Result<(), InscriptionError> {
                         let matchup = sibling.inverse_matchup_as_value().unwrap();
 
                         edit_builder.add_missing_field(StatColumn::MATCHUP, matchup);
+                        edit_builder.attribute(
+                            Provenance::local().with_reason("matchup inferred from sibling edit"),
+                        );
 
                         if let Some(edit) = edit_builder.build() {
                             edits.insert(edit);
@@ -84,7 +92,11 @@ pub fn inscribe(era: SeasonId) -> Result<(), InscriptionError> {
             inscribe(era)
         }
         Ok(games) => {
-            save_nba_games(era, &games).map_err(|e| InscriptionError::SerializeGameError(e))
+            save_nba_games(era, &games).map_err(|e| InscriptionError::SerializeGameError(e))?;
+
+            Lineage::current(era, &edits)
+                .and_then(|lineage| lineage.save())
+                .map_err(InscriptionError::LineageError)
         }
     }
 }
@@ -117,7 +129,7 @@ pub(crate) fn pair_off(games: Vec<TeamGame>) -> Result<Vec<GameObject>, Vec<Edit
     for (id, pair) in pairs.iter() {
         match pair {
             (Some(game), None) | (None, Some(game)) => {
-                println!(
+                log::warn!(
                     "⚠️ unpaired game: {} season: {}",
                     id,
                     pair.0.as_ref().unwrap().0.season_id
@@ -0,0 +1,247 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::types::GameDate;
+
+/// how many days `/forecast` looks ahead when it isn't told.
+pub const DEFAULT_DAYS: usize = 7;
+
+/// the furthest `/forecast` looks ahead, so one request can't query the nba for months of games.
+pub const MAX_DAYS: usize = 30;
+
+/// ## Route
+///
+/// an endpoint of the api and its parameters.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    /// `GET /models`
+    Models,
+    /// `GET /forecast?model=&days=`
+    Forecast { model: String, days: usize },
+    /// `GET /games?date=`, defaulting to today
+    Games { date: GameDate },
+    /// `GET /teams/{abbr}/record?season=`, defaulting to the current season
+    TeamRecord { team: String, season: Option<i32> },
+    /// `GET /players/{id}/ratings`
+    PlayerRatings { player: String },
+    /// `GET /evaluate/{model}`
+    Evaluate { model: String },
+}
+
+impl Route {
+    /// the route a `GET` of `path` (with its query string, if any) asks for.
+    pub fn parse(path: &str, query: Option<&str>) -> Result<Route, ApiError> {
+        let params = parse_query(query.unwrap_or_default());
+
+        let segments = path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect::<Vec<_>>();
+
+        let segments = segments.iter().map(String::as_str).collect::<Vec<_>>();
+
+        match segments.as_slice() {
+            ["models"] => Ok(Route::Models),
+            ["forecast"] => Ok(Route::Forecast {
+                model: required(&params, "model")?,
+                days: match params.get("days") {
+                    Some(days) => parse_param::<usize>("days", days)?.clamp(1, MAX_DAYS),
+                    None => DEFAULT_DAYS,
+                },
+            }),
+            ["games"] => Ok(Route::Games {
+                date: match params.get("date") {
+                    Some(date) => parse_param("date", date)?,
+                    None => GameDate::today(),
+                },
+            }),
+            ["teams", team, "record"] => Ok(Route::TeamRecord {
+                team: team.to_string(),
+                season: params
+                    .get("season")
+                    .map(|season| parse_param("season", season))
+                    .transpose()?,
+            }),
+            ["players", player, "ratings"] => Ok(Route::PlayerRatings {
+                player: player.to_string(),
+            }),
+            ["evaluate", model] => Ok(Route::Evaluate {
+                model: model.to_string(),
+            }),
+            _ => Err(ApiError::NotFound(format!("no endpoint at {path}"))),
+        }
+    }
+}
+
+fn required(params: &HashMap<String, String>, name: &str) -> Result<String, ApiError> {
+    params
+        .get(name)
+        .filter(|value| !value.is_empty())
+        .cloned()
+        .ok_or_else(|| ApiError::BadRequest(format!("{name} is required")))
+}
+
+fn parse_param<T: FromStr>(name: &str, value: &str) -> Result<T, ApiError> {
+    value
+        .parse()
+        .map_err(|_| ApiError::BadRequest(format!("{value} is not a valid {name}")))
+}
+
+/// the decoded `key=value` pairs of a query string. later pairs replace earlier ones.
+pub fn parse_query(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| match pair.split_once('=') {
+            Some((key, value)) => (percent_decode(key), percent_decode(value)),
+            None => (percent_decode(pair), String::new()),
+        })
+        .collect()
+}
+
+/// `s` with `%XX` escapes and `+` decoded. malformed escapes are kept as they are.
+pub fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => match s
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                None => decoded.push(b'%'),
+            },
+            b'+' => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+
+        i += 1;
+    }
+
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+/// ## ApiError
+///
+/// a request the api can't answer, sent back as `{"error": ...}` with its status code.
+#[derive(Debug, Error, PartialEq)]
+pub enum ApiError {
+    #[error("{0}")]
+    BadRequest(String),
+    #[error("{0}")]
+    NotFound(String),
+    #[error("only GET requests are supported")]
+    MethodNotAllowed,
+    #[error("{0}")]
+    Unavailable(String),
+    #[error("{0}")]
+    Internal(String),
+}
+
+impl ApiError {
+    pub fn status(&self) -> u16 {
+        match self {
+            ApiError::BadRequest(_) => 400,
+            ApiError::NotFound(_) => 404,
+            ApiError::MethodNotAllowed => 405,
+            ApiError::Unavailable(_) => 503,
+            ApiError::Internal(_) => 500,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_routes {
+    use super::{parse_query, percent_decode, ApiError, Route, DEFAULT_DAYS, MAX_DAYS};
+
+    use crate::types::GameDate;
+
+    use std::str::FromStr;
+
+    #[test]
+    fn test_percent_decode() {
+        assert_eq!(percent_decode("Nikola%20Joki%C4%87"), "Nikola Jokić");
+        assert_eq!(percent_decode("oklahoma+city"), "oklahoma city");
+        assert_eq!(percent_decode("100%"), "100%");
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let params = parse_query("model=elo-v1&days=3&flag");
+
+        assert_eq!(params["model"], "elo-v1");
+        assert_eq!(params["days"], "3");
+        assert_eq!(params["flag"], "");
+    }
+
+    #[test]
+    fn test_parse_routes() {
+        assert_eq!(Route::parse("/models", None), Ok(Route::Models));
+        assert_eq!(
+            Route::parse("/forecast", Some("model=elo-v1")),
+            Ok(Route::Forecast {
+                model: "elo-v1".to_owned(),
+                days: DEFAULT_DAYS
+            })
+        );
+        assert_eq!(
+            Route::parse("/forecast", Some("model=elo-v1&days=365")),
+            Ok(Route::Forecast {
+                model: "elo-v1".to_owned(),
+                days: MAX_DAYS
+            })
+        );
+        assert_eq!(
+            Route::parse("/games", Some("date=2024-01-15")),
+            Ok(Route::Games {
+                date: GameDate::from_str("2024-01-15").unwrap()
+            })
+        );
+        assert_eq!(
+            Route::parse("/teams/OKC/record", Some("season=2015")),
+            Ok(Route::TeamRecord {
+                team: "OKC".to_owned(),
+                season: Some(2015)
+            })
+        );
+        assert_eq!(
+            Route::parse("/players/LeBron%20James/ratings/", None),
+            Ok(Route::PlayerRatings {
+                player: "LeBron James".to_owned()
+            })
+        );
+        assert_eq!(
+            Route::parse("/evaluate/elo-v1", None),
+            Ok(Route::Evaluate {
+                model: "elo-v1".to_owned()
+            })
+        );
+    }
+
+    #[test]
+    fn test_bad_routes() {
+        assert!(matches!(
+            Route::parse("/forecast", None),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            Route::parse("/games", Some("date=yesterday")),
+            Err(ApiError::BadRequest(_))
+        ));
+        assert!(matches!(
+            Route::parse("/teams/OKC", None),
+            Err(ApiError::NotFound(_))
+        ));
+    }
+}
@@ -0,0 +1,473 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::rc::Rc;
+
+use http_body_util::Full;
+use hyper::body::{Bytes, Incoming};
+use hyper::header::{ACCESS_CONTROL_ALLOW_ORIGIN, CONTENT_TYPE};
+use hyper::server::conn::http1;
+use hyper::service::service_fn;
+use hyper::{Method, Request, Response, StatusCode};
+use hyper_util::rt::TokioIo;
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use thiserror::Error;
+
+use tokio::net::TcpListener;
+use tokio::task::LocalSet;
+
+use crate::dapi::directory::{resolve_player, resolve_team, DirectoryError};
+use crate::dapi::season_manager::get_current_era;
+
+use crate::format::season::season_fmt;
+
+use crate::ml::elo::rating_history::{game_dates, player_history, GameDates};
+use crate::ml::model::Model;
+use crate::ml::models::elo_models::elo_tracker::EloTracker;
+use crate::ml::models::registration::Registration;
+use crate::ml::registry::{model_runs, promoted_run, registered_models};
+
+use crate::proc::forecast::{get_upcoming_games, predict_cards};
+
+use crate::serve::routes::{ApiError, Route};
+
+use crate::stats::chronology::Chronology;
+use crate::stats::game_obj::GameObject;
+use crate::stats::season_period::SeasonPeriod;
+
+use crate::types::{GameDate, GameId, SeasonId, TeamAbbreviation, TeamId, TeamName};
+
+/// ## ApiState
+///
+/// everything the api answers from, loaded once when the server starts and kept for every
+/// request.
+pub struct ApiState {
+    models: BTreeMap<String, RefCell<Box<dyn Model>>>,
+    chronology: Chronology,
+    dates: GameDates,
+    ratings: Option<EloTracker>,
+}
+
+impl ApiState {
+    /// `models` must already be initialized. `ratings` backs `/players/{id}/ratings`.
+    pub fn new(
+        models: Vec<Box<dyn Model>>,
+        chronology: Chronology,
+        ratings: Option<EloTracker>,
+    ) -> Self {
+        let dates = game_dates(chronology.games());
+
+        ApiState {
+            models: models
+                .into_iter()
+                .map(|model| (model.model_name(), RefCell::new(model)))
+                .collect(),
+            chronology,
+            dates,
+            ratings,
+        }
+    }
+
+    pub async fn handle(&self, route: Route) -> Result<Value, ApiError> {
+        match route {
+            Route::Models => Ok(self.models()),
+            Route::Forecast { model, days } => self.forecast(&model, days).await,
+            Route::Games { date } => self.games(date),
+            Route::TeamRecord { team, season } => self.team_record(&team, season),
+            Route::PlayerRatings { player } => self.player_ratings(&player),
+            Route::Evaluate { model } => self.evaluate(&model),
+        }
+    }
+
+    /// every resident or trained model, by the name (with its params) it's stored and served under.
+    fn models(&self) -> Value {
+        let names = self
+            .models
+            .keys()
+            .cloned()
+            .chain(registered_models().unwrap_or_default())
+            .collect::<BTreeSet<_>>();
+
+        let models = names
+            .iter()
+            .map(|name| {
+                json!({
+                    "model_name": name,
+                    "resident": self.models.contains_key(name),
+                    "promoted_run": promoted_run(name),
+                    "runs": model_runs(name).map(|runs| runs.len()).unwrap_or(0),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        Value::Array(models)
+    }
+
+    async fn forecast(&self, model_name: &str, days: usize) -> Result<Value, ApiError> {
+        let model = self.model(model_name)?;
+
+        if self.chronology.eras().next().is_none() {
+            return Err(ApiError::Unavailable(
+                "no stored games to draw expected rosters from".to_owned(),
+            ));
+        }
+
+        let mut cards = get_upcoming_games(days).await.map_err(|e| {
+            log::error!("{e}");
+            ApiError::Unavailable("failed to get upcoming games from nba.com".to_owned())
+        })?;
+
+        // the cards are fetched before the model is borrowed, so no borrow is held across an await
+        let predictions = predict_cards(&mut *model.borrow_mut(), &mut cards, &self.chronology);
+
+        to_value(&predictions)
+    }
+
+    fn games(&self, date: GameDate) -> Result<Value, ApiError> {
+        let games = self
+            .chronology
+            .games()
+            .filter(|game| game.game_date() == date)
+            .map(GameSummary::from)
+            .collect::<Vec<_>>();
+
+        to_value(&games)
+    }
+
+    fn team_record(&self, team: &str, season: Option<i32>) -> Result<Value, ApiError> {
+        let teams = self.chronology.team_directory();
+
+        let team_id = resolve_team(teams, team).map_err(directory_error)?;
+
+        let year = season.unwrap_or_else(|| get_current_era().year());
+        let season = SeasonId::from((year, SeasonPeriod::RegularSeason));
+
+        let games = self
+            .chronology
+            .games()
+            .filter(|game| game.season() == season && game.participant(team_id))
+            .collect::<Vec<_>>();
+
+        let wins = games.iter().filter(|game| game.winner() == team_id).count();
+
+        let identity = teams
+            .franchise(team_id)
+            .and_then(|franchise| match games.first() {
+                Some(game) => franchise.identity_on(game.game_date()),
+                None => franchise.current(),
+            });
+
+        Ok(json!({
+            "team_id": team_id,
+            "team_abbr": identity.map(|identity| identity.abbr.clone()),
+            "team_name": identity.map(|identity| identity.name.clone()),
+            "season": season_fmt(year),
+            "wins": wins,
+            "losses": games.len() - wins,
+        }))
+    }
+
+    fn player_ratings(&self, player: &str) -> Result<Value, ApiError> {
+        let ratings = self
+            .ratings
+            .as_ref()
+            .ok_or_else(|| ApiError::Unavailable("elo-v1 is not trained".to_owned()))?;
+
+        let players = self.chronology.player_directory();
+
+        let player_id = resolve_player(players, player).map_err(directory_error)?;
+
+        let history = player_history(ratings.historical_ratings(), &self.dates, player_id);
+
+        Ok(json!({
+            "player_id": player_id,
+            "player_name": players.get(player_id),
+            "ratings": history,
+        }))
+    }
+
+    fn evaluate(&self, model_name: &str) -> Result<Value, ApiError> {
+        let metrics = self
+            .model(model_name)?
+            .borrow()
+            .evaluate()
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        to_value(&metrics)
+    }
+
+    fn model(&self, model_name: &str) -> Result<&RefCell<Box<dyn Model>>, ApiError> {
+        if let Some(model) = self.models.get(model_name) {
+            return Ok(model);
+        }
+
+        let known = inventory::iter::<Registration>().any(|r| r.model_name == model_name)
+            || registered_models()
+                .unwrap_or_default()
+                .iter()
+                .any(|name| name == model_name);
+
+        match known {
+            true => Err(ApiError::Unavailable(format!(
+                "{model_name} isn't loaded. train it or start the server with `--model {model_name}`"
+            ))),
+            false => Err(ApiError::NotFound(format!("unknown model {model_name}"))),
+        }
+    }
+}
+
+/// a played game and how it ended.
+#[derive(Debug, Serialize)]
+struct GameSummary {
+    game_id: GameId,
+    season: SeasonId,
+    game_date: GameDate,
+    home: TeamSummary,
+    away: TeamSummary,
+    winner: TeamId,
+}
+
+#[derive(Debug, Serialize)]
+struct TeamSummary {
+    team_id: TeamId,
+    team_abbr: TeamAbbreviation,
+    team_name: TeamName,
+    pts: u8,
+}
+
+impl From<&GameObject> for GameSummary {
+    fn from(game: &GameObject) -> Self {
+        let team = |team_id| {
+            let side = if game.home_team_id() == team_id {
+                game.home()
+            } else {
+                game.away()
+            };
+
+            TeamSummary {
+                team_id,
+                team_abbr: side.team_abbr(),
+                team_name: side.team_name(),
+                pts: side.box_score().pts().0,
+            }
+        };
+
+        GameSummary {
+            game_id: game.game_id(),
+            season: game.season(),
+            game_date: game.game_date(),
+            home: team(game.home_team_id()),
+            away: team(game.away_team_id()),
+            winner: game.winner(),
+        }
+    }
+}
+
+fn to_value<T: Serialize>(value: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::Internal(e.to_string()))
+}
+
+fn directory_error(e: DirectoryError) -> ApiError {
+    match e {
+        DirectoryError::UnknownPlayer(name) => {
+            ApiError::NotFound(format!("no player is named {name}"))
+        }
+        DirectoryError::UnknownTeam(name) => {
+            ApiError::NotFound(format!("no team has been called {name}"))
+        }
+        DirectoryError::AmbiguousPlayer(name, matches)
+        | DirectoryError::AmbiguousTeam(name, matches) => {
+            ApiError::BadRequest(format!("more than one match for {name}: {matches}"))
+        }
+        e => ApiError::Internal(e.to_string()),
+    }
+}
+
+/// answer requests on `port` until ctrl-c. requests are answered one at a time on this thread, so
+/// models don't have to be shared across threads.
+pub async fn serve(port: u16, state: ApiState) -> Result<(), ServeError> {
+    let addr = SocketAddr::from(([127, 0, 0, 1], port));
+
+    let listener = TcpListener::bind(addr)
+        .await
+        .map_err(|e| ServeError::BindError(addr, e))?;
+
+    log::info!("📡 serving the warheads api on http://{addr}");
+
+    let state = Rc::new(state);
+
+    LocalSet::new()
+        .run_until(async move {
+            let shutdown = tokio::signal::ctrl_c();
+            tokio::pin!(shutdown);
+
+            loop {
+                tokio::select! {
+                    accepted = listener.accept() => match accepted {
+                        Ok((stream, _)) => {
+                            let state = Rc::clone(&state);
+
+                            tokio::task::spawn_local(async move {
+                                let service =
+                                    service_fn(move |request| respond(Rc::clone(&state), request));
+
+                                if let Err(e) = http1::Builder::new()
+                                    .serve_connection(TokioIo::new(stream), service)
+                                    .await
+                                {
+                                    log::warn!("⚠️ {e}\n⚠️ connection closed with an error");
+                                }
+                            });
+                        }
+                        Err(e) => log::warn!("⚠️ {e}\n⚠️ failed to accept a connection"),
+                    },
+                    _ = &mut shutdown => {
+                        log::info!("👋 shutting down the api");
+
+                        return Ok(());
+                    }
+                }
+            }
+        })
+        .await
+}
+
+async fn respond(
+    state: Rc<ApiState>,
+    request: Request<Incoming>,
+) -> Result<Response<Full<Bytes>>, Infallible> {
+    let result = match *request.method() == Method::GET {
+        true => match Route::parse(request.uri().path(), request.uri().query()) {
+            Ok(route) => state.handle(route).await,
+            Err(e) => Err(e),
+        },
+        false => Err(ApiError::MethodNotAllowed),
+    };
+
+    let (status, body) = match result {
+        Ok(body) => (200, body),
+        Err(e) => (e.status(), json!({ "error": e.to_string() })),
+    };
+
+    log::info!("📡 {} {} {status}", request.method(), request.uri());
+
+    let response = Response::builder()
+        .status(StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+        .header(CONTENT_TYPE, "application/json")
+        .header(ACCESS_CONTROL_ALLOW_ORIGIN, "*")
+        .body(Full::new(Bytes::from(body.to_string())))
+        .unwrap_or_else(|_| Response::new(Full::new(Bytes::new())));
+
+    Ok(response)
+}
+
+#[derive(Debug, Error)]
+pub enum ServeError {
+    #[error("❌ {1}\n❌ failed to listen on {0}")]
+    BindError(SocketAddr, io::Error),
+}
+
+#[cfg(test)]
+mod test_server {
+    use super::ApiState;
+
+    use crate::ml::model::{Model, TrainingError};
+
+    use crate::serve::routes::{ApiError, Route};
+
+    use crate::stats::chronology::Chronology;
+    use crate::stats::gamecard::GameCard;
+
+    use serde_json::json;
+
+    use std::collections::HashMap;
+
+    struct CoinFlip;
+
+    impl Model for CoinFlip {
+        fn model_name(&self) -> String {
+            "coin-flip".to_owned()
+        }
+
+        fn initialize(&mut self) -> Result<(), ()> {
+            Ok(())
+        }
+
+        fn train(&mut self, _data: Chronology) -> Result<(), TrainingError> {
+            Ok(())
+        }
+
+        fn evaluate(&self) -> HashMap<String, f64> {
+            HashMap::from([("accuracy".to_owned(), 0.5)])
+        }
+
+        fn predict(&mut self, _obj: &GameCard) -> f64 {
+            0.5
+        }
+    }
+
+    fn state() -> ApiState {
+        ApiState::new(vec![Box::new(CoinFlip)], Chronology::new(), None)
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_resident_model() {
+        let state = state();
+
+        let metrics = state
+            .handle(Route::Evaluate {
+                model: "coin-flip".to_owned(),
+            })
+            .await;
+
+        assert_eq!(metrics, Ok(json!({ "accuracy": 0.5 })));
+    }
+
+    #[tokio::test]
+    async fn test_models_lists_residents_by_name() {
+        let models = state().handle(Route::Models).await.unwrap();
+
+        assert!(models
+            .as_array()
+            .unwrap()
+            .iter()
+            .any(|model| model["model_name"] == "coin-flip" && model["resident"] == true));
+    }
+
+    #[tokio::test]
+    async fn test_unavailable_resources() {
+        let state = state();
+
+        assert!(matches!(
+            state
+                .handle(Route::Evaluate {
+                    model: "crystal-ball".to_owned()
+                })
+                .await,
+            Err(ApiError::NotFound(_))
+        ));
+        assert!(matches!(
+            state
+                .handle(Route::Forecast {
+                    model: "coin-flip".to_owned(),
+                    days: 1
+                })
+                .await,
+            Err(ApiError::Unavailable(_))
+        ));
+        assert!(matches!(
+            state
+                .handle(Route::PlayerRatings {
+                    player: "2544".to_owned()
+                })
+                .await,
+            Err(ApiError::Unavailable(_))
+        ));
+    }
+}
@@ -48,7 +48,9 @@ pub struct BoxScore {
 }
 
 impl BoxScore {
-    fn fingerprint(&self) -> u32 {
+    /// a bit set of the columns recorded in this box score. see `nba_schema::SCHEMA_COLUMNS` for
+    /// the bit each column occupies.
+    pub fn fingerprint(&self) -> u32 {
         let bit = |present: bool, pos: u32| if present { 1 << pos } else { 0 };
 
         bit(true, MIN_BIT)
@@ -71,13 +73,56 @@ impl BoxScore {
             | bit(true, WL_BIT)
     }
 
-    pub(super) fn schema(&self) -> Option<NBASchema> {
+    /// the known schema this box score matches, or `None` if its columns don't match any era.
+    pub fn schema(&self) -> Option<NBASchema> {
         match self.fingerprint().try_into() {
             Ok(x) => Some(x),
             Err(_) => None,
         }
     }
 
+    /// whether every column in `required` is recorded in this box score.
+    pub fn has_fields(&self, required: &[StatColumn]) -> bool {
+        let required = fingerprint(required);
+
+        self.fingerprint() & required == required
+    }
+
+    /// the box score columns that are null in this box score.
+    pub fn missing_fields(&self) -> Vec<StatColumn> {
+        schema_columns(MODERN_NBA_SCHEMA & !self.fingerprint())
+    }
+
+    /// the value of a box score column as a number, or `None` if it wasn't recorded or `col` isn't
+    /// a numeric box score column. wins count as 1 and losses as 0.
+    pub fn stat(&self, col: StatColumn) -> Option<f64> {
+        match col {
+            StatColumn::MIN => Some(self.min.0 as f64),
+            StatColumn::FGM => Some(self.fgm.0 as f64),
+            StatColumn::FGA => self.fga.0.map(|v| v as f64),
+            StatColumn::FG3M => self.fg3m.0.map(|v| v as f64),
+            StatColumn::FG3A => self.fg3a.0.map(|v| v as f64),
+            StatColumn::FTM => Some(self.ftm.0 as f64),
+            StatColumn::FTA => self.fta.0.map(|v| v as f64),
+            StatColumn::OREB => self.oreb.0.map(|v| v as f64),
+            StatColumn::DREB => self.dreb.0.map(|v| v as f64),
+            StatColumn::REB => self.reb.0.map(|v| v as f64),
+            StatColumn::AST => self.ast.0.map(|v| v as f64),
+            StatColumn::STL => self.stl.0.map(|v| v as f64),
+            StatColumn::BLK => self.blk.0.map(|v| v as f64),
+            StatColumn::TOV => self.tov.0.map(|v| v as f64),
+            StatColumn::PF => Some(self.pf.0 as f64),
+            StatColumn::PTS => Some(self.pts.0 as f64),
+            StatColumn::PLUS_MINUS => self.plus_minus.0.map(|v| v as f64),
+            StatColumn::WL => match self.wl {
+                GameResult::Win => Some(1.0),
+                GameResult::Loss => Some(0.0),
+                GameResult::Draw => Some(0.5),
+            },
+            _ => None,
+        }
+    }
+
     /// document this function and test please
     pub fn calculate_fantasy(&self) -> FantasyPoints {
         // three pointers
@@ -381,7 +426,7 @@ impl BoxScore {
                 Ok(())
             }
             Err(e) => {
-                eprintln!("❌ WL as JSON must be type String and in [W, L]: {e}");
+                log::error!("❌ WL as JSON must be type String and in [W, L]: {e}");
 
                 Err(())
             }
@@ -495,10 +540,7 @@ mod tests {
 
             chrono.load_era(era).expect("failed to load games in test");
 
-            let games = chrono
-                .games()
-                .as_ref()
-                .expect("failed to load games in test");
+            let games = chrono.games();
 
             for game in games {
                 println!("{game}");
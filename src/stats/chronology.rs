@@ -1,29 +1,46 @@
 use crate::dapi::player_directory::PlayerDirectory;
-use crate::dapi::read_disk::{read_nba_season, NBAReadError};
-use crate::dapi::season_manager::nba_lifespan_period;
+use crate::dapi::read_disk::{read_nba_season_shared, NBAReadError};
+use crate::dapi::season_manager::{get_era_by_date, nba_lifespan_period};
 use crate::dapi::team_directory::TeamDirectory;
 
 use crate::ml::vector::Vector;
 
+use crate::stats::game_index::GameIndex;
 use crate::stats::game_obj::GameObject;
+use crate::stats::game_query::{GameQuery, QueryRow};
 use crate::stats::gamecard::GameCard;
 use crate::stats::nba_kind::NBAStatKind;
-use crate::stats::nba_schema::NBASchema;
+use crate::stats::nba_schema::{schema_columns, MODERN_NBA_SCHEMA};
 use crate::stats::record::Record;
+use crate::stats::season_period::SeasonPeriod;
+use crate::stats::stat_column::StatColumn;
+use crate::stats::visiting::Visiting;
 
-use crate::types::{GameId, PlayerId, SeasonId, TeamId};
+use crate::tui::progress::Progress;
+
+use crate::types::{GameDate, GameId, PlayerId, SeasonId, TeamId};
 
 use std::cmp::max;
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use rand::RngExt;
 use thiserror::Error;
 
 /// an efficient way to query through historical games
+///
+/// a chronology holds one era or a contiguous run of eras (up to the whole of
+/// `nba_lifespan_period()`), indexed together so that recent games and expected rosters look back
+/// across the boundaries between eras.
+///
+/// the games of each loaded era are shared with every other chronology that loaded the same era,
+/// so cloning a chronology or reloading an era doesn't copy them.
 #[derive(Debug, Clone)]
 pub struct Chronology {
-    games: Option<Vec<GameObject>>,
-    era: Option<SeasonId>,
+    eras: Vec<(SeasonId, Arc<Vec<GameObject>>)>,
+    /// the position of the first game of each era in the index.
+    offsets: Vec<usize>,
+    index: GameIndex,
     player_directory: PlayerDirectory,
     team_directory: TeamDirectory,
 }
@@ -31,8 +48,9 @@ pub struct Chronology {
 impl Chronology {
     pub fn new() -> Self {
         Self {
-            games: None,
-            era: None,
+            eras: Vec::new(),
+            offsets: Vec::new(),
+            index: GameIndex::default(),
             player_directory: Default::default(),
             team_directory: Default::default(),
         }
@@ -42,7 +60,7 @@ impl Chronology {
         let mut timeline = Chronology::new();
 
         if let Err(_) = timeline.load_era(era) {
-            println!(
+            log::warn!(
                 "⚠️ failed to load {} in a chronology. returning empty chronology",
                 era
             );
@@ -52,88 +70,193 @@ impl Chronology {
         }
     }
 
+    /// a chronology of `era` and every era since the start of the season before it, so that
+    /// expected rosters early in `era` can be drawn from last season. eras that fail to load (e.g.
+    /// a season that hasn't started) are left out.
+    pub fn with_lookback(era: SeasonId) -> Self {
+        let first = lookback_start(era);
+
+        let eras = nba_lifespan_period()
+            .into_iter()
+            .filter(|e| first <= *e && *e <= era)
+            .filter(|e| match read_nba_season_shared(*e) {
+                Ok(_) => true,
+                Err(_) => {
+                    log::warn!("⚠️ failed to load {e} in a chronology. leaving it out");
+                    false
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let mut timeline = Chronology::new();
+
+        match timeline.load_eras(&eras) {
+            Ok(_) => timeline,
+            Err(_) => Chronology::new(),
+        }
+    }
+
     pub fn load_era(&mut self, era: SeasonId) -> Result<(), ChronologyError> {
-        if self.era.is_some() && era == self.era.unwrap() {
+        self.load_eras(&[era])
+    }
+
+    /// load every era from `first` through `last` in `nba_lifespan_period()`.
+    pub fn load_range(&mut self, first: SeasonId, last: SeasonId) -> Result<(), ChronologyError> {
+        let eras = nba_lifespan_period()
+            .into_iter()
+            .filter(|era| first <= *era && *era <= last)
+            .collect::<Vec<_>>();
+
+        self.load_eras(&eras)
+    }
+
+    /// load `era` and every era since the start of the season before it.
+    pub fn load_with_lookback(&mut self, era: SeasonId) -> Result<(), ChronologyError> {
+        self.load_range(lookback_start(era), era)
+    }
+
+    /// load the whole history of the nba.
+    pub fn load_all(&mut self) -> Result<(), ChronologyError> {
+        self.load_eras(&nba_lifespan_period())
+    }
+
+    /// replace the loaded games with those of `eras`, which must be in chronological order.
+    pub fn load_eras(&mut self, eras: &[SeasonId]) -> Result<(), ChronologyError> {
+        if self.eras().eq(eras.iter().copied()) {
             return Ok(());
         }
 
-        let games = read_nba_season(era).map_err(|e| ChronologyError::ReadSeasonError(e))?;
+        let mut loaded = Vec::with_capacity(eras.len());
 
-        games.iter().for_each(|game| {
-            self.team_directory.insert((
-                game.away().team_id(),
-                game.away().team_abbr().clone(),
-                game.away().team_name().clone(),
-            ));
-            self.team_directory.insert((
-                game.home().team_id(),
-                game.home().team_abbr().clone(),
-                game.home().team_name().clone(),
-            ));
-
-            for p in game.away_roster().iter().chain(game.home_roster().iter()) {
-                self.player_directory
-                    .insert(p.player_id(), p.player_name().clone())
-            }
-        });
+        let progress = Progress::new(eras.len() as u64, "📖 reading volumes");
+
+        for era in eras {
+            let games =
+                read_nba_season_shared(*era).map_err(|e| ChronologyError::ReadSeasonError(e))?;
 
-        self.era = Some(era);
-        self.games = Some(games);
+            loaded.push((*era, games));
+
+            progress.inc(1);
+        }
+
+        progress.finish();
+
+        self.index_eras(loaded);
 
         Ok(())
     }
 
+    /// replace the loaded games with `loaded`, indexing them together.
+    fn index_eras(&mut self, loaded: Vec<(SeasonId, Arc<Vec<GameObject>>)>) {
+        self.player_directory = PlayerDirectory::default();
+        self.team_directory = TeamDirectory::new();
+
+        for (_, games) in &loaded {
+            self.register(games);
+        }
+
+        self.offsets = loaded
+            .iter()
+            .scan(0, |start, (_, games)| {
+                let offset = *start;
+                *start += games.len();
+                Some(offset)
+            })
+            .collect();
+        self.index = GameIndex::build(loaded.iter().flat_map(|(_, games)| games.iter()));
+        self.eras = loaded;
+    }
+
+    /// add the teams and players of `games` to the directories of the loaded games.
+    fn register(&mut self, games: &[GameObject]) {
+        games.iter().for_each(|game| {
+            self.team_directory.record_game(game);
+            self.player_directory.record_game(game);
+        });
+    }
+
+    /// load the era after the last loaded era on its own.
     pub fn next(&mut self) -> Result<(), ChronologyError> {
-        let current_era = self.era.unwrap();
+        let current_era = self.eras().last().unwrap();
         let next_era = current_era.next();
 
         self.load_era(next_era)
     }
 
+    /// load the era before the first loaded era on its own.
     pub fn prev(&mut self) -> Result<(), ChronologyError> {
-        let current_era = self.era.unwrap();
+        let current_era = self.eras().next().unwrap();
         let previous_era = current_era.prev();
 
         self.load_era(previous_era)
     }
 
+    /// every game in history paired with its card. the whole history is loaded at once so the
+    /// expected rosters of each season's first games can look back at the season before.
     pub fn as_training_data(mut self) -> Result<Vec<(GameCard, GameObject)>, ChronologyError> {
-        let mut games = Vec::new();
+        self.load_all()?;
 
-        for era in nba_lifespan_period() {
-            if let Err(e) = self.load_era(era) {
-                return Err(e);
-            }
+        Ok(self.training_pairs(|_| true))
+    }
 
-            games.extend(
-                self.games
-                    .clone()
-                    .ok_or(ChronologyError::ChronologyMemoryError)?
-                    .iter_mut()
-                    .map(|game| {
-                        let mut card = game.card();
-
-                        card.add_away_roster(
-                            self.get_expected_roster(game.away_team_id(), game.game_id()),
-                        );
-                        card.add_home_roster(
-                            self.get_expected_roster(game.home_team_id(), game.game_id()),
-                        );
-
-                        (card, game.clone())
-                    }),
-            )
-        }
+    /// the games played after `date` paired with their cards, oldest first, for models that learn
+    /// new games on top of what they already know. eras from the season before `date` on are
+    /// loaded so the first new games still have rosters to look back at.
+    pub fn training_data_since(
+        mut self,
+        date: GameDate,
+    ) -> Result<Vec<(GameCard, GameObject)>, ChronologyError> {
+        let first = lookback_start(get_era_by_date(date));
+
+        let last = nba_lifespan_period()
+            .last()
+            .copied()
+            .unwrap_or_else(|| get_era_by_date(date));
+
+        self.load_range(first, last)?;
+
+        Ok(self.training_pairs(|game| game.game_date() > date))
+    }
+
+    /// the loaded games that are `kept`, paired with their cards and sorted by date.
+    fn training_pairs(&self, kept: impl Fn(&GameObject) -> bool) -> Vec<(GameCard, GameObject)> {
+        let progress = Progress::new(self.games().count() as u64, "🃏 dealing game cards");
+
+        let mut games = self
+            .games()
+            .inspect(|_| progress.inc(1))
+            .filter(|game| kept(game))
+            .map(|game| {
+                let mut card = game.card();
+
+                card.add_away_roster(self.get_expected_roster(game.away_team_id(), game.game_id()));
+                card.add_home_roster(self.get_expected_roster(game.home_team_id(), game.game_id()));
+
+                (card, game.clone())
+            })
+            .collect::<Vec<_>>();
 
         games.sort_by_key(|(c, _g)| c.date());
 
-        Ok(games)
+        games
     }
 
+    /// regression data made only of box scores that match the modern schema exactly.
     pub fn as_pure_regression_data(
+        self,
+        kind: NBAStatKind,
+        split: f64,
+    ) -> Result<(Vec<Vector>, Vec<Vector>), ChronologyError> {
+        self.as_regression_data(kind, split, &schema_columns(MODERN_NBA_SCHEMA))
+    }
+
+    /// regression data made of every box score in history that records all of the `required`
+    /// columns. models that need fewer columns get to train on a wider span of history.
+    pub fn as_regression_data(
         mut self,
         kind: NBAStatKind,
         split: f64,
+        required: &[StatColumn],
     ) -> Result<(Vec<Vector>, Vec<Vector>), ChronologyError> {
         assert!(0f64 < split && split < 1f64, "💀 as_regression_data requires a fractional split size greater than 0 and less than 1 (0.7 is recommended). expected: split ∈(0,1)\treceived: {}", split);
 
@@ -147,35 +270,30 @@ impl Chronology {
                 return Err(e);
             }
 
-            let era_games = self
-                .games()
-                .as_ref()
-                .ok_or(ChronologyError::ChronologyMemoryError)?;
-
-            for game in era_games {
+            for game in self.games() {
                 match kind {
                     NBAStatKind::Team => {
                         let away_box = game.away().box_score();
-                        if away_box.schema() == Some(NBASchema::ModernNBASchema) {
+                        if away_box.has_fields(required) {
                             box_scores.push(away_box.clone());
                         }
 
                         let home_box = game.home().box_score();
-                        if home_box.schema() == Some(NBASchema::ModernNBASchema) {
+                        if home_box.has_fields(required) {
                             box_scores.push(home_box.clone());
                         }
                     }
                     NBAStatKind::Player => {
                         for player in game.away_roster() {
                             let away_player_box = player.box_score();
-                            if away_player_box.schema() == Some(NBASchema::ModernNBASchema) {
+                            if away_player_box.has_fields(required) {
                                 box_scores.push(away_player_box.clone());
                             }
                         }
 
                         for player in game.home_roster() {
                             let home_player_box = player.box_score();
-                            if home_player_box.schema() == Some(NBASchema::ModernNBASchema) {
+                            if home_player_box.has_fields(required) {
                                 box_scores.push(home_player_box.clone());
                             }
                         }
@@ -218,34 +336,36 @@ impl Chronology {
         Ok((training_data, test_data))
     }
 
-    fn n_most_recent_games(&self, n: usize, team_id: TeamId, game_id: GameId) -> Vec<GameObject> {
-        if !self.is_initialized() {
-            panic!("💀 tried to run most_recent_games on an uninitialized Chronology object.")
-        }
+    /// every team (or player) game matching `query`, in date order. loads each era the query can
+    /// match, leaving the last one loaded.
+    pub fn query(&mut self, query: &GameQuery) -> Result<Vec<QueryRow>, ChronologyError> {
+        let mut rows = Vec::new();
 
-        let mut games_for_team = self
-            .games
-            .as_ref()
-            .unwrap()
-            .iter()
-            .filter(|game| game.participant(team_id))
-            .map(|x| x.clone())
-            .collect::<Vec<GameObject>>();
+        for era in query.eras() {
+            self.load_era(era)?;
 
-        games_for_team.sort_by_key(|g| g.game_date());
+            for game in self.games() {
+                rows.extend(query.matches(game, Visiting::Away));
+                rows.extend(query.matches(game, Visiting::Home));
+            }
+        }
 
-        let index = games_for_team
-            .iter()
-            .position(|g| g.game_id() == game_id)
-            .unwrap_or(games_for_team.len());
+        rows.sort_by_key(|row| (row.game_date, row.game_id));
 
-        let mut last_n_games = Vec::new();
+        Ok(rows)
+    }
 
-        for i in index.saturating_sub(n)..index {
-            last_n_games.push(games_for_team[i].clone());
+    /// the last `n` games `team_id` played before `game_id`, oldest first.
+    fn n_most_recent_games(&self, n: usize, team_id: TeamId, game_id: GameId) -> Vec<&GameObject> {
+        if !self.is_initialized() {
+            panic!("💀 tried to run most_recent_games on an uninitialized Chronology object.")
         }
 
-        last_n_games
+        self.index
+            .before(n, team_id, game_id)
+            .iter()
+            .map(|&i| self.game_at(i))
+            .collect()
     }
 
     //todo: improve this with win sharing function as elo parameter
@@ -291,7 +411,14 @@ impl Chronology {
     }
 
     fn is_initialized(&self) -> bool {
-        self.games.is_some() && self.era.is_some()
+        !self.eras.is_empty()
+    }
+
+    /// the game at `position` in the index.
+    fn game_at(&self, position: usize) -> &GameObject {
+        let era = self.offsets.partition_point(|&start| start <= position) - 1;
+
+        &self.eras[era].1[position - self.offsets[era]]
     }
 
     pub fn calculate_record(&self, team_id: TeamId) -> Record {
@@ -302,11 +429,10 @@ impl Chronology {
         let mut wins = 0;
         let mut losses = 0;
 
-        self.games
-            .as_ref()
-            .unwrap()
+        self.index
+            .schedule(team_id)
             .iter()
-            .filter(|game| game.participant(team_id))
+            .map(|&i| self.game_at(i))
             .for_each(|game| {
                 if game.winner() == team_id {
                     wins += 1
@@ -318,8 +444,14 @@ impl Chronology {
         Record { wins, losses }
     }
 
-    pub fn games(&self) -> &Option<Vec<GameObject>> {
-        &self.games
+    /// every loaded game, era by era.
+    pub fn games(&self) -> impl Iterator<Item = &GameObject> {
+        self.eras.iter().flat_map(|(_, games)| games.iter())
+    }
+
+    /// the loaded eras, in chronological order.
+    pub fn eras(&self) -> impl Iterator<Item = SeasonId> + '_ {
+        self.eras.iter().map(|(era, _)| *era)
     }
 
     pub fn player_directory(&self) -> &PlayerDirectory {
@@ -331,6 +463,22 @@ impl Chronology {
     }
 }
 
+/// the date of the newest stored game, or `None` if no games are stored.
+pub fn last_game_date() -> Option<GameDate> {
+    nba_lifespan_period().into_iter().rev().find_map(|era| {
+        read_nba_season_shared(era)
+            .ok()?
+            .iter()
+            .map(GameObject::game_date)
+            .max()
+    })
+}
+
+/// the first era of the season before `era`.
+fn lookback_start(era: SeasonId) -> SeasonId {
+    SeasonId::from((era.year() - 1, SeasonPeriod::PreSeason))
+}
+
 #[derive(Debug, Error)]
 pub enum ChronologyError {
     #[error("{0}\n❌ failed to read season data from storage")]
@@ -477,6 +625,67 @@ mod test_chronology {
         assert_eq!(actual.len(), 81);
     }
 
+    fn lakers_game(season: &str, date: &str, game_id: &str) -> GameObject {
+        use serde_json::json;
+
+        let box_score = |wl: &str, pts: u64| {
+            json!({
+                "wl": wl, "min": 240, "fgm": 40, "fga": 80, "fg3m": 12, "fg3a": 30, "ftm": 20,
+                "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8, "blk": 5,
+                "tov": 12, "pf": 20, "pts": pts, "plus_minus": null
+            })
+        };
+
+        let team = |id: u64, abbr: &str, visiting: &str, wl: &str, pts: u64, player: u64| {
+            json!({
+                "team_id": id,
+                "team_abbreviation": abbr,
+                "team_name": abbr,
+                "visiting": visiting,
+                "roster": [{
+                    "player_id": player,
+                    "player_name": format!("{abbr} Star"),
+                    "box_score": box_score(wl, pts),
+                }],
+                "box_score": box_score(wl, pts),
+            })
+        };
+
+        serde_json::from_value(json!({
+            "season_id": season,
+            "game_date": date,
+            "game_id": game_id,
+            "home": team(1610612747, "LAL", "Home", "W", 110, 2544),
+            "away": team(1610612750, "MIN", "Away", "L", 103, 1630162),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_expected_roster_looks_back_across_eras() {
+        let last_season = SeasonId::from((2023, RegularSeason));
+        let season = SeasonId::from((2024, RegularSeason));
+        let opener = GameId(0022400062);
+        let lakers = TeamId(1610612747);
+
+        let finale = Arc::new(vec![lakers_game("22023", "2024-04-14", "0022301200")]);
+        let openers = Arc::new(vec![lakers_game("22024", "2024-10-22", "0022400062")]);
+
+        let mut single = Chronology::new();
+        single.index_eras(vec![(season, openers.clone())]);
+
+        assert!(single.get_expected_roster(lakers, opener).is_empty());
+
+        let mut chronology = Chronology::new();
+        chronology.index_eras(vec![(last_season, finale), (season, openers)]);
+
+        assert_eq!(chronology.eras().last(), Some(season));
+        assert_eq!(
+            chronology.get_expected_roster(lakers, opener),
+            vec![PlayerId(2544)]
+        );
+    }
+
     #[test]
     fn test_get_expected_roster() {
         let chronology = Chronology::from_era(SeasonId::from((2024, RegularSeason)));
@@ -0,0 +1,79 @@
+use crate::stats::game_obj::GameObject;
+
+use crate::types::{GameDate, GameId, TeamId};
+
+use std::collections::HashMap;
+
+/// ## GameIndex
+///
+/// positions of a chronology's games, grouped by team and sorted by date, so that a team's
+/// schedule or its games before a given game can be found without scanning every game.
+///
+/// games on the same date keep the order they were stored in.
+#[derive(Debug, Clone, Default)]
+pub struct GameIndex {
+    by_team: HashMap<TeamId, Vec<usize>>,
+    by_id: HashMap<GameId, usize>,
+    dates: Vec<GameDate>,
+}
+
+impl GameIndex {
+    /// index `games`, numbering them in the order they're given. games from several eras can be
+    /// indexed together by chaining them in chronological order.
+    pub fn build<'a>(games: impl IntoIterator<Item = &'a GameObject>) -> Self {
+        let mut by_team: HashMap<TeamId, Vec<usize>> = HashMap::new();
+        let mut by_id = HashMap::new();
+        let mut dates = Vec::new();
+
+        for (i, game) in games.into_iter().enumerate() {
+            by_team.entry(game.home_team_id()).or_default().push(i);
+            by_team.entry(game.away_team_id()).or_default().push(i);
+
+            by_id.insert(game.game_id(), i);
+            dates.push(game.game_date());
+        }
+
+        for schedule in by_team.values_mut() {
+            schedule.sort_by_key(|&i| dates[i]);
+        }
+
+        GameIndex {
+            by_team,
+            by_id,
+            dates,
+        }
+    }
+
+    /// the position of the game, if it was played.
+    pub fn game(&self, game_id: GameId) -> Option<usize> {
+        self.by_id.get(&game_id).copied()
+    }
+
+    /// the positions of every game `team_id` played, in date order.
+    pub fn schedule(&self, team_id: TeamId) -> &[usize] {
+        self.by_team.get(&team_id).map_or(&[], |s| s.as_slice())
+    }
+
+    /// the positions of (up to) the last `n` games `team_id` played before `game_id`. a game that
+    /// isn't indexed, such as an upcoming one, comes after every game played.
+    pub fn before(&self, n: usize, team_id: TeamId, game_id: GameId) -> &[usize] {
+        let schedule = self.schedule(team_id);
+
+        let end = match self.game(game_id) {
+            Some(game) => {
+                let date = self.dates[game];
+
+                let first = schedule.partition_point(|&i| self.dates[i] < date);
+
+                schedule[first..]
+                    .iter()
+                    .take_while(|&&i| self.dates[i] == date)
+                    .position(|&i| i == game)
+                    .map_or(schedule.len(), |offset| first + offset)
+            }
+            None => schedule.len(),
+        };
+
+        &schedule[end.saturating_sub(n)..end]
+    }
+}
@@ -75,7 +75,7 @@ impl GameObject {
 
         //draconian yes, but if there isn't a roster for the team we're not gonna study it.
         if game1.roster_box_scores().len() == 0 || game2.roster_box_scores().len() == 0 {
-            println!("No roster data available for one or both teams. {id1:?}, {id2:?}");
+            log::warn!("No roster data available for one or both teams. {id1:?}, {id2:?}");
             edit1.set_delete(true);
             edit2.set_delete(true);
         }
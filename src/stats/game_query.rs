@@ -0,0 +1,362 @@
+use crate::dapi::season_manager::nba_lifespan_period;
+use crate::dapi::team_box_score::TeamBoxScore;
+
+use crate::stats::box_score::BoxScore;
+use crate::stats::game_obj::GameObject;
+use crate::stats::nba_schema::schema_column;
+use crate::stats::season_period::SeasonPeriod;
+use crate::stats::stat_column::StatColumn;
+use crate::stats::visiting::Visiting;
+
+use crate::types::{
+    GameDate, GameId, GameResult, PlayerId, PlayerName, SeasonId, TeamAbbreviation,
+};
+
+use serde::Serialize;
+
+use std::fmt::{Display, Formatter};
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+/// how a box score column is compared in a `StatPredicate`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Eq,
+    Ne,
+    Ge,
+    Gt,
+}
+
+impl Comparison {
+    pub fn compare(&self, lhs: f64, rhs: f64) -> bool {
+        match self {
+            Comparison::Lt => lhs < rhs,
+            Comparison::Le => lhs <= rhs,
+            Comparison::Eq => lhs == rhs,
+            Comparison::Ne => lhs != rhs,
+            Comparison::Ge => lhs >= rhs,
+            Comparison::Gt => lhs > rhs,
+        }
+    }
+
+    fn symbol(&self) -> &'static str {
+        match self {
+            Comparison::Lt => "<",
+            Comparison::Le => "<=",
+            Comparison::Eq => "=",
+            Comparison::Ne => "!=",
+            Comparison::Ge => ">=",
+            Comparison::Gt => ">",
+        }
+    }
+}
+
+/// ## StatPredicate
+///
+/// a condition on one box score column, written as `<column><op><value>` (e.g. `pts>120`,
+/// `fg3m>=15`, `tov<10`). box scores that didn't record the column never match.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StatPredicate {
+    pub column: StatColumn,
+    pub comparison: Comparison,
+    pub value: f64,
+}
+
+impl StatPredicate {
+    pub fn new(column: StatColumn, comparison: Comparison, value: f64) -> Self {
+        StatPredicate {
+            column,
+            comparison,
+            value,
+        }
+    }
+
+    pub fn matches(&self, box_score: &BoxScore) -> bool {
+        box_score
+            .stat(self.column)
+            .is_some_and(|stat| self.comparison.compare(stat, self.value))
+    }
+}
+
+impl FromStr for StatPredicate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // two character operators first so `>=` isn't read as `>`
+        const OPERATORS: [(&str, Comparison); 7] = [
+            (">=", Comparison::Ge),
+            ("<=", Comparison::Le),
+            ("!=", Comparison::Ne),
+            ("==", Comparison::Eq),
+            (">", Comparison::Gt),
+            ("<", Comparison::Lt),
+            ("=", Comparison::Eq),
+        ];
+
+        let (column, comparison, value) = OPERATORS
+            .iter()
+            .find_map(|(op, comparison)| {
+                s.split_once(op)
+                    .map(|(column, value)| (column, *comparison, value))
+            })
+            .ok_or_else(|| format!("{s} is not a stat predicate (e.g. pts>120)"))?;
+
+        let column = schema_column(column.trim())
+            .ok_or_else(|| format!("{} is not a box score column", column.trim()))?;
+
+        let value = value
+            .trim()
+            .parse::<f64>()
+            .map_err(|e| format!("{e}: {} is not a number", value.trim()))?;
+
+        Ok(StatPredicate::new(column, comparison, value))
+    }
+}
+
+impl Display for StatPredicate {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.column,
+            self.comparison.symbol(),
+            self.value
+        )
+    }
+}
+
+/// parse a range of seasons, written as the year each season starts: `2015` for 2015-16 alone, or
+/// `2010..2024` for every season from 2010-11 through 2024-25 (inclusive of both).
+pub fn parse_season_range(s: &str) -> Result<RangeInclusive<i32>, String> {
+    let year = |y: &str| {
+        y.trim()
+            .parse::<i32>()
+            .map_err(|e| format!("{e}: {y} is not a season year"))
+    };
+
+    let (start, end) = match s.split_once("..") {
+        Some((start, end)) => (year(start)?, year(end.trim_start_matches('='))?),
+        None => (year(s)?, year(s)?),
+    };
+
+    if start > end {
+        return Err(format!("season range {s} ends before it starts"));
+    }
+
+    Ok(start..=end)
+}
+
+/// ## GameQuery
+///
+/// a composable filter over the games in a `Chronology`. each game is seen from both teams'
+/// side, so "Lakers road games in 2015-16 where they scored over 120" is
+///
+/// ```
+/// use warheads::stats::game_query::GameQuery;
+/// use warheads::stats::visiting::Visiting;
+///
+/// let query = GameQuery::new()
+///     .team("LAL")
+///     .seasons(2015..=2015)
+///     .visiting(Visiting::Away)
+///     .stat("pts>120".parse().unwrap());
+/// ```
+///
+/// filters that aren't set match everything. when a player is set, stat predicates are checked
+/// against that player's box score instead of the team's.
+#[derive(Debug, Clone, Default)]
+pub struct GameQuery {
+    team: Option<String>,
+    opponent: Option<String>,
+    player: Option<PlayerId>,
+    seasons: Option<RangeInclusive<i32>>,
+    period: Option<SeasonPeriod>,
+    visiting: Option<Visiting>,
+    from: Option<GameDate>,
+    to: Option<GameDate>,
+    result: Option<GameResult>,
+    stats: Vec<StatPredicate>,
+}
+
+impl GameQuery {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// games played by the team with this abbreviation at the time of the game.
+    pub fn team(mut self, abbr: &str) -> Self {
+        self.team = Some(abbr.to_ascii_uppercase());
+        self
+    }
+
+    /// games played against the team with this abbreviation at the time of the game.
+    pub fn opponent(mut self, abbr: &str) -> Self {
+        self.opponent = Some(abbr.to_ascii_uppercase());
+        self
+    }
+
+    /// games in which the player appeared in the box score.
+    pub fn player(mut self, player_id: PlayerId) -> Self {
+        self.player = Some(player_id);
+        self
+    }
+
+    /// games in seasons starting in these years.
+    pub fn seasons(mut self, years: RangeInclusive<i32>) -> Self {
+        self.seasons = Some(years);
+        self
+    }
+
+    pub fn period(mut self, period: SeasonPeriod) -> Self {
+        self.period = Some(period);
+        self
+    }
+
+    pub fn visiting(mut self, visiting: Visiting) -> Self {
+        self.visiting = Some(visiting);
+        self
+    }
+
+    /// games on or after `date`.
+    pub fn from(mut self, date: GameDate) -> Self {
+        self.from = Some(date);
+        self
+    }
+
+    /// games on or before `date`.
+    pub fn to(mut self, date: GameDate) -> Self {
+        self.to = Some(date);
+        self
+    }
+
+    pub fn result(mut self, result: GameResult) -> Self {
+        self.result = Some(result);
+        self
+    }
+
+    /// add a stat predicate. every predicate must hold for a game to match.
+    pub fn stat(mut self, predicate: StatPredicate) -> Self {
+        self.stats.push(predicate);
+        self
+    }
+
+    /// the stored eras that can contain a matching game.
+    pub fn eras(&self) -> Vec<SeasonId> {
+        nba_lifespan_period()
+            .into_iter()
+            .filter(|era| {
+                self.seasons
+                    .as_ref()
+                    .is_none_or(|s| s.contains(&era.year()))
+            })
+            .filter(|era| self.period.is_none_or(|p| era.period() == p))
+            .filter(|era| {
+                // an era runs from the fall of its year to the summer of the next
+                let after = self
+                    .from
+                    .is_none_or(|d| d.destructure().0 <= era.year() + 1);
+                let before = self.to.is_none_or(|d| d.destructure().0 >= era.year());
+
+                after && before
+            })
+            .collect()
+    }
+
+    /// the row for the `side` team of `game` if it matches the query.
+    pub fn matches(&self, game: &GameObject, side: Visiting) -> Option<QueryRow> {
+        let (team, opponent) = match side {
+            Visiting::Home => (game.home(), game.away()),
+            Visiting::Away => (game.away(), game.home()),
+        };
+
+        let season = game.season();
+        let date = game.game_date();
+
+        let abbr_is = |team: &TeamBoxScore, abbr: &String| team.team_abbr().emphasize() == *abbr;
+
+        let matches_game = self.team.as_ref().is_none_or(|abbr| abbr_is(team, abbr))
+            && self
+                .opponent
+                .as_ref()
+                .is_none_or(|abbr| abbr_is(opponent, abbr))
+            && self
+                .seasons
+                .as_ref()
+                .is_none_or(|s| s.contains(&season.year()))
+            && self.period.is_none_or(|p| season.period() == p)
+            && self.visiting.is_none_or(|v| v == side)
+            && self.from.is_none_or(|d| date >= d)
+            && self.to.is_none_or(|d| date <= d)
+            && self.result.is_none_or(|r| *team.box_score().wl() == r);
+
+        if !matches_game {
+            return None;
+        }
+
+        let (player, box_score) = match self.player {
+            Some(player_id) => {
+                let player = team
+                    .roster_box_scores()
+                    .iter()
+                    .find(|p| p.player_id() == player_id)?;
+
+                (Some(player.player_name().clone()), player.box_score())
+            }
+            None => (None, team.box_score()),
+        };
+
+        if !self
+            .stats
+            .iter()
+            .all(|predicate| predicate.matches(box_score))
+        {
+            return None;
+        }
+
+        Some(QueryRow {
+            season_id: season,
+            game_date: date,
+            game_id: game.game_id(),
+            team: team.team_abbr(),
+            opponent: opponent.team_abbr(),
+            visiting: side,
+            player_name: player,
+            box_score: box_score.clone(),
+        })
+    }
+
+    /// the predicates on box score columns, in the order they were added.
+    pub fn predicates(&self) -> &[StatPredicate] {
+        &self.stats
+    }
+}
+
+/// one team's (or player's) side of a game that matched a `GameQuery`.
+#[derive(Debug, Clone, Serialize)]
+pub struct QueryRow {
+    pub season_id: SeasonId,
+    pub game_date: GameDate,
+    pub game_id: GameId,
+    pub team: TeamAbbreviation,
+    pub opponent: TeamAbbreviation,
+    pub visiting: Visiting,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub player_name: Option<PlayerName>,
+    pub box_score: BoxScore,
+}
+
+impl QueryRow {
+    /// the matchup from this side, e.g. `LAL @ BOS` or `LAL vs. BOS`.
+    pub fn matchup(&self) -> String {
+        match self.visiting {
+            Visiting::Home => format!(
+                "{} vs. {}",
+                self.team.emphasize(),
+                self.opponent.emphasize()
+            ),
+            Visiting::Away => format!("{} @ {}", self.team.emphasize(), self.opponent.emphasize()),
+        }
+    }
+}
@@ -0,0 +1,305 @@
+use crate::dapi::player_box_score::PlayerBoxScore;
+use crate::dapi::team_box_score::TeamBoxScore;
+
+use crate::edit::edit_builder::EditBuilder;
+
+use crate::stats::box_score::BoxScore;
+use crate::stats::game_display::GameDisplay;
+use crate::stats::game_obj::GameObject;
+use crate::stats::stat_column::StatColumn::{self, *};
+
+use crate::types::{Matchup, PlayerId};
+
+use serde_json::{json, Value};
+
+use std::fmt::{Display, Formatter};
+
+/// player minutes are rounded to the nearest minute in the source data, so a full roster can sum
+/// to a few minutes over the true length of the game without any single line being wrong.
+const MINUTES_ROUNDING_TOLERANCE: u32 = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintRule {
+    /// field goal makes exceed field goal attempts
+    FieldGoals,
+    /// three point makes exceed field goal makes
+    ThreePointers,
+    /// total rebounds are not the sum of offensive and defensive rebounds
+    Rebounds,
+    /// team points are not the sum of the roster's points
+    TeamPoints,
+    /// roster minutes exceed the length of the game
+    Minutes,
+    /// points do not add up from field goals, threes and free throws
+    Scoring,
+    /// home and away plus minus are not opposites
+    PlusMinus,
+}
+
+impl LintRule {
+    /// the columns an edit has to touch to resolve a violation of the rule.
+    pub fn columns(&self) -> &'static [StatColumn] {
+        match self {
+            LintRule::FieldGoals => &[FGM, FGA],
+            LintRule::ThreePointers => &[FGM, FG3M],
+            LintRule::Rebounds => &[OREB, DREB, REB],
+            LintRule::TeamPoints => &[PTS],
+            LintRule::Minutes => &[MIN],
+            LintRule::Scoring => &[FGM, FG3M, FTM, PTS],
+            LintRule::PlusMinus => &[PLUS_MINUS],
+        }
+    }
+}
+
+impl Display for LintRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            LintRule::FieldGoals => "FGM > FGA",
+            LintRule::ThreePointers => "FG3M > FGM",
+            LintRule::Rebounds => "REB != OREB + DREB",
+            LintRule::TeamPoints => "team PTS != sum of player PTS",
+            LintRule::Minutes => "player MIN exceeds game length",
+            LintRule::Scoring => "PTS != 2·FGM + FG3M + FTM",
+            LintRule::PlusMinus => "home PLUS_MINUS != -away PLUS_MINUS",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
+/// a single broken rule, along with an `EditBuilder` pre-filled with the current values of the
+/// offending columns so it can be handed straight to the prompter.
+#[derive(Debug)]
+pub struct Violation {
+    pub rule: LintRule,
+    pub detail: String,
+    pub candidate: EditBuilder,
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let edit = self.candidate.correction();
+
+        write!(
+            f,
+            "⚠️ {} {} {}{}: {} ({})",
+            edit.season,
+            edit.game_id,
+            edit.team_abbr,
+            match edit.player_id {
+                Some(player_id) => format!(" player {player_id}"),
+                None => String::new(),
+            },
+            self.rule,
+            self.detail
+        )
+    }
+}
+
+/// check every consistency rule against the game and its rosters.
+pub fn lint_game(game: &GameObject) -> Vec<Violation> {
+    let mut violations = Vec::new();
+
+    let matchup = Matchup::from_matchup(game.home().team_abbr(), game.away().team_abbr());
+
+    for team in [game.home(), game.away()] {
+        let violation = |rule: LintRule, player: Option<&PlayerBoxScore>, detail: String| {
+            let box_score = player.map_or(team.box_score(), |p| p.box_score());
+
+            Violation {
+                rule,
+                detail,
+                candidate: candidate(
+                    game,
+                    team,
+                    player.map(|p| p.player_id()),
+                    &matchup,
+                    rule,
+                    box_score,
+                ),
+            }
+        };
+
+        for (rule, detail) in lint_box_score(team.box_score()) {
+            violations.push(violation(rule, None, detail));
+        }
+
+        for player in team.roster_box_scores() {
+            for (rule, detail) in lint_box_score(player.box_score()) {
+                violations.push(violation(rule, Some(player), detail));
+            }
+        }
+
+        let roster_points: u32 = team
+            .roster_box_scores()
+            .iter()
+            .map(|p| p.box_score().pts().0 as u32)
+            .sum();
+
+        let team_points = team.box_score().pts().0 as u32;
+
+        if !team.roster_box_scores().is_empty() && roster_points != team_points {
+            violations.push(violation(
+                LintRule::TeamPoints,
+                None,
+                format!("team: {team_points}, players: {roster_points}"),
+            ));
+        }
+
+        let roster_minutes: u32 = team
+            .roster_box_scores()
+            .iter()
+            .map(|p| p.box_score().min().0 as u32)
+            .sum();
+
+        let game_minutes = team_minutes(team.box_score());
+
+        if roster_minutes > game_minutes + MINUTES_ROUNDING_TOLERANCE {
+            violations.push(violation(
+                LintRule::Minutes,
+                None,
+                format!(
+                    "players: {roster_minutes}, allowed: {game_minutes} ({} OT)",
+                    (game_minutes - 240) / 25
+                ),
+            ));
+        }
+    }
+
+    if let (Some(home), Some(away)) = (
+        game.home().box_score().plus_minus().0,
+        game.away().box_score().plus_minus().0,
+    ) {
+        if home != -away {
+            for team in [game.home(), game.away()] {
+                violations.push(Violation {
+                    rule: LintRule::PlusMinus,
+                    detail: format!("home: {home}, away: {away}"),
+                    candidate: candidate(
+                        game,
+                        team,
+                        None,
+                        &matchup,
+                        LintRule::PlusMinus,
+                        team.box_score(),
+                    ),
+                });
+            }
+        }
+    }
+
+    violations
+}
+
+/// rules that only need a single box score, shared by teams and players.
+fn lint_box_score(box_score: &BoxScore) -> Vec<(LintRule, String)> {
+    let mut broken = Vec::new();
+
+    let fgm = box_score.fgm().0;
+
+    if let Some(fga) = box_score.fga().0 {
+        if fgm > fga {
+            broken.push((LintRule::FieldGoals, format!("FGM: {fgm}, FGA: {fga}")));
+        }
+    }
+
+    if let Some(fg3m) = box_score.fg3m().0 {
+        if fg3m > fgm {
+            broken.push((LintRule::ThreePointers, format!("FG3M: {fg3m}, FGM: {fgm}")));
+        }
+    }
+
+    if let (Some(oreb), Some(dreb), Some(reb)) =
+        (box_score.oreb().0, box_score.dreb().0, box_score.reb().0)
+    {
+        if oreb as u32 + dreb as u32 != reb as u32 {
+            broken.push((
+                LintRule::Rebounds,
+                format!("OREB: {oreb}, DREB: {dreb}, REB: {reb}"),
+            ));
+        }
+    }
+
+    // before the three point line fg3m is null and every field goal is worth two
+    let fg3m = box_score.fg3m().0.unwrap_or(0) as u32;
+    let ftm = box_score.ftm().0 as u32;
+    let pts = box_score.pts().0 as u32;
+
+    let expected = 2 * fgm as u32 + fg3m + ftm;
+
+    if pts != expected {
+        broken.push((
+            LintRule::Scoring,
+            format!("PTS: {pts}, 2·{fgm} + {fg3m} + {ftm} = {expected}"),
+        ));
+    }
+
+    broken
+}
+
+/// the number of player minutes available to a team: 240 in regulation plus 25 per overtime.
+///
+/// team minutes are stored as a `u8`, so overtime games (265, 290, ...) wrap around below 240.
+fn team_minutes(box_score: &BoxScore) -> u32 {
+    let min = box_score.min().0 as u32;
+
+    if min < 240 {
+        min + 256
+    } else {
+        min
+    }
+}
+
+fn candidate(
+    game: &GameObject,
+    team: &TeamBoxScore,
+    player_id: Option<PlayerId>,
+    matchup: &Matchup,
+    rule: LintRule,
+    box_score: &BoxScore,
+) -> EditBuilder {
+    let mut edit_builder = EditBuilder::new(
+        game.game_id(),
+        game.season(),
+        player_id,
+        team.team_id(),
+        team.team_abbr(),
+        game.game_date(),
+    );
+
+    let player_name = player_id.and_then(|id| {
+        team.roster_box_scores()
+            .iter()
+            .find(|p| p.player_id() == id)
+            .map(|p| p.player_name().clone())
+    });
+
+    edit_builder.update_display(GameDisplay::new(
+        matchup.clone(),
+        game.game_date(),
+        player_name,
+        team.team_name(),
+    ));
+
+    for &col in rule.columns() {
+        edit_builder.add_missing_field(col, column_value(box_score, col));
+    }
+
+    edit_builder
+}
+
+fn column_value(box_score: &BoxScore, col: StatColumn) -> Value {
+    match col {
+        MIN => json!(box_score.min().0),
+        FGM => json!(box_score.fgm().0),
+        FGA => json!(box_score.fga().0),
+        FG3M => json!(box_score.fg3m().0),
+        FTM => json!(box_score.ftm().0),
+        OREB => json!(box_score.oreb().0),
+        DREB => json!(box_score.dreb().0),
+        REB => json!(box_score.reb().0),
+        PTS => json!(box_score.pts().0),
+        PLUS_MINUS => json!(box_score.plus_minus().0),
+        _ => Value::Null,
+    }
+}
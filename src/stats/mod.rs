@@ -4,15 +4,19 @@ pub mod domain;
 pub mod field_status;
 mod game_data;
 pub mod game_display;
+pub mod game_index;
 pub mod game_obj;
+pub mod game_query;
 pub mod gamecard;
 pub mod identity;
 pub mod itemize;
+pub mod lint;
 pub mod nba_boxscore;
 pub mod nba_kind;
 pub mod nba_schema;
 pub mod prediction;
 pub mod record;
+pub mod schema_report;
 pub mod season_period;
 pub mod serde_enum;
 pub mod shooting;
@@ -1,3 +1,7 @@
+use crate::stats::stat_column::StatColumn::{self, *};
+
+use std::fmt::{Display, Formatter};
+
 pub(super) const MIN_BIT: u32 = 0;
 pub(super) const FGM_BIT: u32 = 1;
 pub(super) const FGA_BIT: u32 = 2;
@@ -59,12 +63,60 @@ pub const MODERN_NBA_SCHEMA: u32 = 0
     | (1 << PLUS_MINUS_BIT)
     | (1 << WL_BIT);
 
+/// the box score column recorded at each bit of a schema fingerprint.
+pub const SCHEMA_COLUMNS: [(u32, StatColumn); 18] = [
+    (MIN_BIT, MIN),
+    (FGM_BIT, FGM),
+    (FGA_BIT, FGA),
+    (FG3M_BIT, FG3M),
+    (FG3A_BIT, FG3A),
+    (FTM_BIT, FTM),
+    (FTA_BIT, FTA),
+    (OREB_BIT, OREB),
+    (DREB_BIT, DREB),
+    (REB_BIT, REB),
+    (AST_BIT, AST),
+    (STL_BIT, STL),
+    (BLK_BIT, BLK),
+    (TOV_BIT, TOV),
+    (PF_BIT, PF),
+    (PTS_BIT, PTS),
+    (PLUS_MINUS_BIT, PLUS_MINUS),
+    (WL_BIT, WL),
+];
+
+/// the fingerprint of a box score that records exactly `columns`. columns that are not part of a
+/// box score (identifiers, percentages) are ignored.
+pub fn fingerprint(columns: &[StatColumn]) -> u32 {
+    SCHEMA_COLUMNS
+        .iter()
+        .filter(|(_, col)| columns.contains(col))
+        .fold(0, |acc, (bit, _)| acc | (1 << bit))
+}
+
+/// the box score column named `name` (e.g. `fg3m`), as printed by `StatColumn::column_name`.
+pub fn schema_column(name: &str) -> Option<StatColumn> {
+    SCHEMA_COLUMNS
+        .iter()
+        .map(|(_, col)| *col)
+        .find(|col| col.column_name() == name.to_ascii_lowercase())
+}
+
+/// the box score columns recorded in `fingerprint`.
+pub fn schema_columns(fingerprint: u32) -> Vec<StatColumn> {
+    SCHEMA_COLUMNS
+        .iter()
+        .filter(|(bit, _)| (fingerprint >> bit) & 1 == 1)
+        .map(|(_, col)| *col)
+        .collect()
+}
+
 /// # Schema
 /// this enum defines the type of data provided as different eras of nba have yielded different recorded
 /// schemas. (e.g. early NBA stats only included points, pfs and makes but not attempts.)
 #[allow(dead_code)]
-#[derive(Debug, Clone, Eq, PartialEq)]
-pub(super) enum NBASchema {
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Hash)]
+pub enum NBASchema {
     //1946
     InitialNBASchema,
     InitialMinusFGA,
@@ -96,6 +148,24 @@ impl TryFrom<u32> for NBASchema {
     }
 }
 
+impl Display for NBASchema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            NBASchema::InitialNBASchema => "Initial",
+            NBASchema::InitialMinusFGA => "Initial -FGA",
+            NBASchema::InitialMinusFTA => "Initial -FTA",
+            NBASchema::InitialMinusATT => "Initial -FGA -FTA",
+            NBASchema::EarlyNBASchema => "Early",
+            NBASchema::EarlyNBAMinusFTA => "Early -FTA",
+            NBASchema::EarlyNBAMinusFGA => "Early -FGA",
+            NBASchema::Pre3PtEra => "Pre 3pt",
+            NBASchema::ModernNBASchema => "Modern",
+        };
+
+        write!(f, "{s}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -122,7 +192,7 @@ mod tests {
         );
         for era in nba_lifespan_period() {
             chrono.load_era(era).expect("failed to load chronology");
-            let games = chrono.games().as_ref().unwrap();
+            let games = chrono.games();
             let mut schema_map = HashMap::<u32, u32>::new();
             for game in games {
                 let boxscore = game.home().box_score();
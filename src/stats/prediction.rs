@@ -39,6 +39,15 @@ impl Prediction {
     pub fn date(&self) -> GameDate {
         self.card.date()
     }
+
+    pub fn card(&self) -> &GameCard {
+        &self.card
+    }
+
+    /// the probability the home team wins.
+    pub fn probability(&self) -> f64 {
+        self.probability
+    }
 }
 
 impl Display for Prediction {
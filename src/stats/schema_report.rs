@@ -0,0 +1,181 @@
+use crate::format::percent::percent_string;
+
+use crate::stats::box_score::BoxScore;
+use crate::stats::game_obj::GameObject;
+use crate::stats::nba_kind::NBAStatKind;
+use crate::stats::nba_schema::{fingerprint, schema_columns, NBASchema, MODERN_NBA_SCHEMA};
+use crate::stats::stat_column::StatColumn;
+
+use crate::types::SeasonId;
+
+use std::collections::HashMap;
+use std::fmt::{Display, Formatter};
+
+/// how many box scores of one kind (team or player) were recorded with each set of columns.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaTally {
+    pub total: usize,
+    pub fingerprints: HashMap<u32, usize>,
+}
+
+impl SchemaTally {
+    pub fn add(&mut self, box_score: &BoxScore) {
+        self.total += 1;
+
+        *self
+            .fingerprints
+            .entry(box_score.fingerprint())
+            .or_insert(0) += 1;
+    }
+
+    /// the number of box scores matching each known schema. `None` counts the box scores that
+    /// don't match any.
+    pub fn schemas(&self) -> HashMap<Option<NBASchema>, usize> {
+        let mut schemas = HashMap::new();
+
+        for (&fingerprint, &count) in &self.fingerprints {
+            *schemas.entry(fingerprint.try_into().ok()).or_insert(0) += count;
+        }
+
+        schemas
+    }
+
+    /// the number of box scores missing each column.
+    pub fn missing(&self) -> HashMap<StatColumn, usize> {
+        let mut missing = HashMap::new();
+
+        for (&fingerprint, &count) in &self.fingerprints {
+            for col in schema_columns(MODERN_NBA_SCHEMA & !fingerprint) {
+                *missing.entry(col).or_insert(0) += count;
+            }
+        }
+
+        missing
+    }
+
+    /// the number of box scores that record every column in `required`.
+    pub fn compatible(&self, required: &[StatColumn]) -> usize {
+        let required = fingerprint(required);
+
+        self.fingerprints
+            .iter()
+            .filter(|(&f, _)| f & required == required)
+            .map(|(_, count)| count)
+            .sum()
+    }
+}
+
+impl Display for SchemaTally {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let schemas = self.schemas();
+        let mut schemas = schemas.iter().collect::<Vec<_>>();
+        schemas.sort_by_key(|(_, count)| std::cmp::Reverse(**count));
+
+        let schemas = schemas
+            .iter()
+            .map(|(schema, count)| {
+                format!(
+                    "{} {}",
+                    match schema {
+                        Some(schema) => schema.to_string(),
+                        None => "Unknown".to_string(),
+                    },
+                    percent_string(**count as i32, self.total as i32)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        let missing = self.missing();
+        let mut missing = missing.iter().collect::<Vec<_>>();
+        missing.sort();
+
+        write!(f, "{:>6} | {}", self.total, schemas)?;
+
+        if !missing.is_empty() {
+            write!(
+                f,
+                "\n{:>6} | missing: {}",
+                "",
+                missing
+                    .iter()
+                    .map(|(col, count)| format!(
+                        "{col} {}",
+                        percent_string(**count as i32, self.total as i32)
+                    ))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// the schema makeup of the team and player box scores in a single season.
+#[derive(Debug, Clone)]
+pub struct SeasonSchema {
+    pub season: SeasonId,
+    pub team: SchemaTally,
+    pub player: SchemaTally,
+}
+
+impl SeasonSchema {
+    pub fn survey(season: SeasonId, games: &[GameObject]) -> Self {
+        let mut team = SchemaTally::default();
+        let mut player = SchemaTally::default();
+
+        for game in games {
+            for side in [game.home(), game.away()] {
+                team.add(side.box_score());
+
+                for p in side.roster_box_scores() {
+                    player.add(p.box_score());
+                }
+            }
+        }
+
+        SeasonSchema {
+            season,
+            team,
+            player,
+        }
+    }
+}
+
+/// the seasons in which every box score of `kind` records all of the `required` columns.
+pub fn compatible_seasons(
+    surveys: &[SeasonSchema],
+    kind: NBAStatKind,
+    required: &[StatColumn],
+) -> Vec<SeasonId> {
+    surveys
+        .iter()
+        .filter(|survey| {
+            let tally = match kind {
+                NBAStatKind::Team => &survey.team,
+                NBAStatKind::Player => &survey.player,
+                NBAStatKind::LineUp => unimplemented!("lineup stats not yet implemented"),
+            };
+
+            tally.total > 0 && tally.compatible(required) == tally.total
+        })
+        .map(|survey| survey.season)
+        .collect()
+}
+
+impl Display for SeasonSchema {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "📅 {}", self.season)?;
+        writeln!(
+            f,
+            "  team   {}",
+            self.team.to_string().replace('\n', "\n         ")
+        )?;
+        write!(
+            f,
+            "  player {}",
+            self.player.to_string().replace('\n', "\n         ")
+        )
+    }
+}
@@ -823,3 +823,505 @@ mod test_serialize_game_obj {
         );
     }
 }
+
+#[cfg(test)]
+mod test_lint {
+    use crate::stats::game_obj::GameObject;
+    use crate::stats::lint::{lint_game, LintRule};
+
+    use serde_json::{json, Value};
+
+    fn box_score(wl: &str, min: u32, fgm: u32, plus_minus: i32) -> Value {
+        json!({
+            "wl": wl,
+            "min": min,
+            "fgm": fgm,
+            "fga": 80,
+            "fg3m": 10,
+            "fg3a": 30,
+            "ftm": 20,
+            "fta": 25,
+            "oreb": 10,
+            "dreb": 30,
+            "reb": 40,
+            "ast": 25,
+            "stl": 8,
+            "blk": 5,
+            "tov": 12,
+            "pf": 20,
+            "pts": 2 * fgm + 10 + 20,
+            "plus_minus": plus_minus
+        })
+    }
+
+    fn team(id: u64, abbr: &str, visiting: &str, wl: &str, fgm: u32, plus_minus: i32) -> Value {
+        json!({
+            "team_id": id,
+            "team_abbreviation": abbr,
+            "team_name": abbr,
+            "visiting": visiting,
+            "roster": [
+                {
+                    "player_id": id + 1,
+                    "player_name": "Everybody",
+                    "box_score": box_score(wl, 48, fgm, plus_minus)
+                }
+            ],
+            "box_score": box_score(wl, 240, fgm, plus_minus)
+        })
+    }
+
+    fn clean_game() -> Value {
+        json!({
+            "season_id": "22005",
+            "game_date": "2006-02-01",
+            "game_id": "0020500673",
+            "home": team(1610612760, "SEA", "Home", "W", 40, 4),
+            "away": team(1610612747, "LAL", "Away", "L", 38, -4)
+        })
+    }
+
+    fn rules(game: Value) -> Vec<LintRule> {
+        let game: GameObject = serde_json::from_value(game).unwrap();
+
+        lint_game(&game).iter().map(|v| v.rule).collect()
+    }
+
+    #[test]
+    fn test_clean_game() {
+        assert!(rules(clean_game()).is_empty());
+    }
+
+    #[test]
+    fn test_field_goals() {
+        let mut game = clean_game();
+        game["home"]["roster"][0]["box_score"]["fga"] = json!(30);
+
+        assert_eq!(rules(game), vec![LintRule::FieldGoals]);
+    }
+
+    #[test]
+    fn test_rebounds() {
+        let mut game = clean_game();
+        game["away"]["box_score"]["reb"] = json!(41);
+
+        assert_eq!(rules(game), vec![LintRule::Rebounds]);
+    }
+
+    #[test]
+    fn test_team_points() {
+        let mut game = clean_game();
+        game["home"]["roster"][0]["box_score"]["pts"] = json!(109);
+        game["home"]["roster"][0]["box_score"]["ftm"] = json!(19);
+
+        assert_eq!(rules(game), vec![LintRule::TeamPoints]);
+    }
+
+    #[test]
+    fn test_scoring() {
+        let mut game = clean_game();
+        game["home"]["box_score"]["ftm"] = json!(21);
+
+        assert_eq!(rules(game), vec![LintRule::Scoring]);
+    }
+
+    #[test]
+    fn test_minutes() {
+        let mut game = clean_game();
+        game["away"]["roster"][0]["box_score"]["min"] = json!(250);
+
+        assert_eq!(rules(game), vec![LintRule::Minutes]);
+    }
+
+    #[test]
+    fn test_overtime_minutes() {
+        let mut game = clean_game();
+        // 265 team minutes wraps to 9 in a u8
+        game["away"]["box_score"]["min"] = json!(9);
+        game["away"]["roster"][0]["box_score"]["min"] = json!(250);
+
+        assert!(rules(game).is_empty());
+    }
+
+    #[test]
+    fn test_plus_minus() {
+        let mut game = clean_game();
+        game["away"]["box_score"]["plus_minus"] = json!(-3);
+
+        assert_eq!(rules(game), vec![LintRule::PlusMinus, LintRule::PlusMinus]);
+    }
+
+    #[test]
+    fn test_candidate_edit() {
+        let mut game = clean_game();
+        game["home"]["box_score"]["ftm"] = json!(21);
+
+        let game: GameObject = serde_json::from_value(game).unwrap();
+        let violations = lint_game(&game);
+        let edit = violations[0].candidate.correction();
+
+        assert_eq!(edit.player_id, None);
+        assert_eq!(edit.corrections.len(), 4);
+        assert_eq!(edit.corrections[&crate::stats::stat_column::StatColumn::FTM], json!(21));
+    }
+}
+
+#[cfg(test)]
+mod test_schema_report {
+    use crate::stats::box_score::BoxScore;
+    use crate::stats::nba_kind::NBAStatKind;
+    use crate::stats::nba_schema::{
+        fingerprint, schema_column, schema_columns, NBASchema, MODERN_NBA_SCHEMA,
+    };
+    use crate::stats::schema_report::{compatible_seasons, SchemaTally, SeasonSchema};
+    use crate::stats::stat_column::StatColumn::*;
+
+    use crate::types::SeasonId;
+
+    use serde_json::{json, Value};
+
+    fn modern() -> Value {
+        json!({
+            "wl": "W", "min": 240, "fgm": 40, "fga": 80, "fg3m": 10, "fg3a": 30, "ftm": 20,
+            "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8, "blk": 5,
+            "tov": 12, "pf": 20, "pts": 110, "plus_minus": 4
+        })
+    }
+
+    fn pre_three_point() -> Value {
+        let mut box_score = modern();
+        box_score["fg3m"] = Value::Null;
+        box_score["fg3a"] = Value::Null;
+        box_score
+    }
+
+    fn box_score(value: Value) -> BoxScore {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_fingerprint_round_trip() {
+        assert_eq!(fingerprint(&schema_columns(MODERN_NBA_SCHEMA)), MODERN_NBA_SCHEMA);
+    }
+
+    #[test]
+    fn test_schema_column() {
+        assert_eq!(schema_column("fg3m"), Some(FG3M));
+        assert_eq!(schema_column("PLUS_MINUS"), Some(PLUS_MINUS));
+        assert_eq!(schema_column("team_id"), None);
+    }
+
+    #[test]
+    fn test_has_fields() {
+        let modern = box_score(modern());
+        let old = box_score(pre_three_point());
+
+        assert_eq!(modern.schema(), Some(NBASchema::ModernNBASchema));
+        assert!(old.has_fields(&[FGA, REB, TOV]));
+        assert!(!old.has_fields(&[FGA, FG3M]));
+        assert_eq!(old.missing_fields(), vec![FG3M, FG3A]);
+    }
+
+    #[test]
+    fn test_tally() {
+        let mut tally = SchemaTally::default();
+
+        tally.add(&box_score(modern()));
+        tally.add(&box_score(modern()));
+        tally.add(&box_score(pre_three_point()));
+
+        assert_eq!(tally.total, 3);
+        assert_eq!(tally.schemas()[&Some(NBASchema::ModernNBASchema)], 2);
+        assert_eq!(tally.schemas()[&None], 1);
+        assert_eq!(tally.missing()[&FG3A], 1);
+        assert_eq!(tally.compatible(&[FGA, TOV]), 3);
+        assert_eq!(tally.compatible(&[FG3M]), 2);
+    }
+
+    #[test]
+    fn test_compatible_seasons() {
+        let mut old = SeasonSchema::survey(SeasonId::from(21978), &[]);
+        old.team.add(&box_score(pre_three_point()));
+
+        let mut new = SeasonSchema::survey(SeasonId::from(21979), &[]);
+        new.team.add(&box_score(modern()));
+
+        let surveys = vec![old, new];
+
+        assert_eq!(
+            compatible_seasons(&surveys, NBAStatKind::Team, &[FG3M]),
+            vec![SeasonId::from(21979)]
+        );
+        assert_eq!(
+            compatible_seasons(&surveys, NBAStatKind::Team, &[FGA]).len(),
+            2
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_game_index {
+    use crate::stats::game_index::GameIndex;
+    use crate::stats::game_obj::GameObject;
+
+    use crate::types::{GameId, TeamId};
+
+    use serde_json::{json, Value};
+
+    fn team(id: u64, visiting: &str, wl: &str) -> Value {
+        json!({
+            "team_id": id,
+            "team_abbreviation": "TST",
+            "team_name": "Test",
+            "visiting": visiting,
+            "roster": [],
+            "box_score": {
+                "wl": wl, "min": 240, "fgm": 40, "fga": 80, "fg3m": 10, "fg3a": 30, "ftm": 20,
+                "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8, "blk": 5,
+                "tov": 12, "pf": 20, "pts": 110, "plus_minus": null
+            }
+        })
+    }
+
+    fn game(id: &str, date: &str, home: u64, away: u64) -> GameObject {
+        serde_json::from_value(json!({
+            "season_id": "22005",
+            "game_date": date,
+            "game_id": id,
+            "home": team(home, "Home", "W"),
+            "away": team(away, "Away", "L")
+        }))
+        .unwrap()
+    }
+
+    fn season() -> Vec<GameObject> {
+        vec![
+            game("0020500003", "2006-02-03", 1, 2),
+            game("0020500001", "2006-02-01", 1, 3),
+            game("0020500004", "2006-02-04", 3, 1),
+            game("0020500002", "2006-02-02", 2, 3),
+            game("0020500005", "2006-02-04", 2, 4),
+        ]
+    }
+
+    fn ids(games: &[GameObject], positions: &[usize]) -> Vec<GameId> {
+        positions.iter().map(|&i| games[i].game_id()).collect()
+    }
+
+    #[test]
+    fn test_schedule_is_date_ordered() {
+        let games = season();
+        let index = GameIndex::build(&games);
+
+        assert_eq!(
+            ids(&games, index.schedule(TeamId(1))),
+            vec![GameId(20500001), GameId(20500003), GameId(20500004)]
+        );
+        assert!(index.schedule(TeamId(99)).is_empty());
+    }
+
+    #[test]
+    fn test_games_before() {
+        let games = season();
+        let index = GameIndex::build(&games);
+
+        assert_eq!(
+            ids(&games, index.before(5, TeamId(1), GameId(20500004))),
+            vec![GameId(20500001), GameId(20500003)]
+        );
+        assert_eq!(
+            ids(&games, index.before(1, TeamId(1), GameId(20500004))),
+            vec![GameId(20500003)]
+        );
+        assert!(index.before(5, TeamId(1), GameId(20500001)).is_empty());
+    }
+
+    #[test]
+    fn test_eras_chain_into_one_index() {
+        let previous = season();
+        let next = vec![
+            game("0020600001", "2006-11-01", 2, 1),
+            game("0020600002", "2006-11-02", 1, 3),
+        ];
+
+        let index = GameIndex::build(previous.iter().chain(next.iter()));
+        let games = previous.iter().chain(next.iter()).cloned().collect::<Vec<_>>();
+
+        assert_eq!(
+            ids(&games, index.before(3, TeamId(1), GameId(20600002))),
+            vec![GameId(20500003), GameId(20500004), GameId(20600001)]
+        );
+    }
+
+    #[test]
+    fn test_upcoming_game_is_after_every_game() {
+        let games = season();
+        let index = GameIndex::build(&games);
+
+        assert_eq!(
+            ids(&games, index.before(2, TeamId(2), GameId(20500099))),
+            vec![GameId(20500003), GameId(20500005)]
+        );
+    }
+}
+
+#[cfg(test)]
+mod test_game_query {
+    use crate::stats::game_obj::GameObject;
+    use crate::stats::game_query::{parse_season_range, GameQuery, StatPredicate};
+    use crate::stats::stat_column::StatColumn;
+    use crate::stats::visiting::Visiting;
+
+    use crate::types::{GameResult, PlayerId};
+
+    use serde_json::{json, Value};
+
+    fn box_score(wl: &str, pts: u64, fg3m: Option<u64>) -> Value {
+        json!({
+            "wl": wl, "min": 240, "fgm": 40, "fga": 80, "fg3m": fg3m, "fg3a": 30, "ftm": 20,
+            "fta": 25, "oreb": 10, "dreb": 30, "reb": 40, "ast": 25, "stl": 8, "blk": 5,
+            "tov": 12, "pf": 20, "pts": pts, "plus_minus": null
+        })
+    }
+
+    fn team(id: u64, abbr: &str, visiting: &str, wl: &str, pts: u64) -> Value {
+        json!({
+            "team_id": id,
+            "team_abbreviation": abbr,
+            "team_name": abbr,
+            "visiting": visiting,
+            "roster": [{
+                "player_id": id * 10,
+                "player_name": format!("{abbr} Star"),
+                "box_score": box_score(wl, pts / 3, Some(2)),
+            }],
+            "box_score": box_score(wl, pts, Some(10)),
+        })
+    }
+
+    fn game(season: &str, date: &str, home: (&str, u64), away: (&str, u64)) -> GameObject {
+        let (home_wl, away_wl) = if home.1 > away.1 {
+            ("W", "L")
+        } else {
+            ("L", "W")
+        };
+
+        serde_json::from_value(json!({
+            "season_id": season,
+            "game_date": date,
+            "game_id": "0021500001",
+            "home": team(1, home.0, "Home", home_wl, home.1),
+            "away": team(2, away.0, "Away", away_wl, away.1),
+        }))
+        .unwrap()
+    }
+
+    fn lakers_at_celtics() -> GameObject {
+        game("22015", "2016-01-10", ("BOS", 118), ("LAL", 124))
+    }
+
+    #[test]
+    fn test_each_side_is_matched_separately() {
+        let game = lakers_at_celtics();
+        let query = GameQuery::new().team("lal");
+
+        assert!(query.matches(&game, Visiting::Home).is_none());
+
+        let row = query.matches(&game, Visiting::Away).unwrap();
+
+        assert_eq!(row.matchup(), "LAL @ BOS");
+        assert_eq!(row.box_score.stat(StatColumn::PTS), Some(124.0));
+    }
+
+    #[test]
+    fn test_road_games_scoring_over_120() {
+        let game = lakers_at_celtics();
+
+        let query = GameQuery::new()
+            .team("LAL")
+            .opponent("BOS")
+            .seasons(2015..=2015)
+            .visiting(Visiting::Away)
+            .result(GameResult::Win)
+            .stat("pts>120".parse().unwrap());
+
+        assert!(query.matches(&game, Visiting::Away).is_some());
+
+        let strict = query.clone().stat("pts>=125".parse().unwrap());
+
+        assert!(strict.matches(&game, Visiting::Away).is_none());
+        assert!(query
+            .clone()
+            .seasons(2016..=2020)
+            .matches(&game, Visiting::Away)
+            .is_none());
+        assert!(query
+            .clone()
+            .to("2016-01-09".parse().unwrap())
+            .matches(&game, Visiting::Away)
+            .is_none());
+    }
+
+    #[test]
+    fn test_player_stats_use_player_box_score() {
+        let game = lakers_at_celtics();
+
+        let query = GameQuery::new()
+            .player(PlayerId(20))
+            .stat("pts>41".parse().unwrap());
+
+        assert!(query.matches(&game, Visiting::Away).is_none());
+
+        let row = GameQuery::new()
+            .player(PlayerId(20))
+            .stat("pts>=41".parse().unwrap())
+            .matches(&game, Visiting::Away)
+            .unwrap();
+
+        assert_eq!(row.player_name.unwrap().0, "LAL Star");
+        assert!(GameQuery::new()
+            .player(PlayerId(20))
+            .matches(&game, Visiting::Home)
+            .is_none());
+    }
+
+    #[test]
+    fn test_parse_stat_predicate() {
+        let predicate: StatPredicate = "fg3m >= 15".parse().unwrap();
+
+        assert_eq!(predicate.column, StatColumn::FG3M);
+        assert_eq!(predicate.to_string(), "fg3m>=15");
+
+        assert!("points>10".parse::<StatPredicate>().is_err());
+        assert!("pts>ten".parse::<StatPredicate>().is_err());
+        assert!("pts".parse::<StatPredicate>().is_err());
+    }
+
+    #[test]
+    fn test_parse_season_range() {
+        assert_eq!(parse_season_range("2015"), Ok(2015..=2015));
+        assert_eq!(parse_season_range("2010..2024"), Ok(2010..=2024));
+        assert_eq!(parse_season_range("2010..=2024"), Ok(2010..=2024));
+        assert!(parse_season_range("2024..2010").is_err());
+    }
+
+    #[test]
+    fn test_eras_are_limited_by_season_and_period() {
+        use crate::stats::season_period::SeasonPeriod::RegularSeason;
+        use crate::types::SeasonId;
+
+        let eras = GameQuery::new()
+            .seasons(2010..=2012)
+            .period(RegularSeason)
+            .eras();
+
+        assert_eq!(
+            eras,
+            vec![
+                SeasonId::from((2010, RegularSeason)),
+                SeasonId::from((2011, RegularSeason)),
+                SeasonId::from((2012, RegularSeason)),
+            ]
+        );
+    }
+}
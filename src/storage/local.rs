@@ -0,0 +1,224 @@
+use crate::constants::config::config;
+
+use crate::storage::Storage;
+
+use std::fs;
+use std::io::{self, Read};
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+/// ## LocalStorage
+///
+/// files under a local directory, the default. a key is the file's path under `root`, or under
+/// `models` with a `models/` prefix when the models root is kept somewhere else.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+    models: Option<PathBuf>,
+}
+
+impl LocalStorage {
+    pub fn new(root: PathBuf) -> Self {
+        LocalStorage { root, models: None }
+    }
+
+    /// the data root and models root of the config.
+    pub fn from_config() -> Self {
+        let config = config();
+
+        LocalStorage {
+            root: config.data.clone(),
+            models: (!config.models.starts_with(&config.data)).then(|| config.models.clone()),
+        }
+    }
+
+    pub fn path(&self, key: &str) -> PathBuf {
+        match (key.strip_prefix("models/"), &self.models) {
+            (Some(rest), Some(models)) => models.join(rest),
+            _ => self.root.join(key),
+        }
+    }
+
+    /// the key of a path, or `None` if it isn't under the root or the models root.
+    pub fn key(&self, path: &Path) -> Option<String> {
+        let relative = match (path.strip_prefix(&self.root), &self.models) {
+            (Ok(relative), _) => relative.to_path_buf(),
+            (Err(_), Some(models)) => Path::new("models").join(path.strip_prefix(models).ok()?),
+            (Err(_), None) => return None,
+        };
+
+        let parts = relative
+            .components()
+            .map(|c| c.as_os_str().to_str())
+            .collect::<Option<Vec<_>>>()?;
+
+        Some(parts.join("/"))
+    }
+
+    pub fn read_prefix_at(path: &Path, len: usize) -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::with_capacity(len);
+
+        fs::File::open(path)?
+            .take(len as u64)
+            .read_to_end(&mut bytes)?;
+
+        Ok(bytes)
+    }
+
+    /// the modification time and size of a file.
+    pub fn stamp_at(path: &Path) -> io::Result<String> {
+        let metadata = fs::metadata(path)?;
+
+        let modified = metadata
+            .modified()?
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default();
+
+        Ok(format!("{}-{}", modified.as_nanos(), metadata.len()))
+    }
+
+    pub fn write_at(path: &Path, contents: &[u8]) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            //this creates the directory from the ground up.
+            fs::create_dir_all(parent)?;
+        }
+
+        fs::write(path, contents)
+    }
+
+    fn walk(&self, dir: &Path, keys: &mut Vec<String>) -> io::Result<()> {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+
+            if path.is_dir() {
+                self.walk(&path, keys)?;
+            } else if let Some(key) = self.key(&path) {
+                keys.push(key);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Storage for LocalStorage {
+    fn read(&self, key: &str) -> io::Result<Vec<u8>> {
+        fs::read(self.path(key))
+    }
+
+    fn open(&self, key: &str) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(fs::File::open(self.path(key))?))
+    }
+
+    fn read_prefix(&self, key: &str, len: usize) -> io::Result<Vec<u8>> {
+        LocalStorage::read_prefix_at(&self.path(key), len)
+    }
+
+    fn write(&self, key: &str, contents: &[u8]) -> io::Result<()> {
+        LocalStorage::write_at(&self.path(key), contents)
+    }
+
+    fn exists(&self, key: &str) -> io::Result<bool> {
+        Ok(self.path(key).is_file())
+    }
+
+    fn stamp(&self, key: &str) -> io::Result<String> {
+        LocalStorage::stamp_at(&self.path(key))
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        match fs::remove_file(self.path(key)) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let mut keys = Vec::new();
+
+        let mut dirs = vec![self.root.clone()];
+        dirs.extend(self.models.clone());
+
+        for dir in dirs.iter().filter(|dir| dir.is_dir()) {
+            self.walk(dir, &mut keys)?;
+        }
+
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        keys.dedup();
+
+        Ok(keys)
+    }
+
+    fn describe(&self) -> String {
+        self.root.display().to_string()
+    }
+}
+
+#[cfg(test)]
+mod test_local_storage {
+    use super::*;
+
+    #[test]
+    fn test_write_read_and_list() {
+        let root = std::env::temp_dir().join(format!("warheads_storage_{}", std::process::id()));
+
+        let storage = LocalStorage::new(root.clone());
+
+        storage.write("nba/volumes/2024.vol", b"volume").unwrap();
+        storage.write("nba/lineage/2024.json", b"{}").unwrap();
+        storage.write("exports/games.csv", b"a,b").unwrap();
+
+        assert_eq!(storage.read("nba/volumes/2024.vol").unwrap(), b"volume");
+        assert_eq!(
+            storage.read_prefix("nba/volumes/2024.vol", 3).unwrap(),
+            b"vol"
+        );
+        assert_eq!(
+            storage.read_prefix("exports/games.csv", 10).unwrap(),
+            b"a,b"
+        );
+        assert!(storage.exists("exports/games.csv").unwrap());
+        assert!(!storage.exists("exports/players.csv").unwrap());
+
+        let stamp = storage.stamp("exports/games.csv").unwrap();
+
+        storage.write("exports/games.csv", b"a,b,c").unwrap();
+
+        assert_ne!(storage.stamp("exports/games.csv").unwrap(), stamp);
+
+        assert_eq!(
+            storage.list("nba/").unwrap(),
+            vec!["nba/lineage/2024.json", "nba/volumes/2024.vol"]
+        );
+        assert_eq!(storage.list("").unwrap().len(), 3);
+
+        storage.remove("exports/games.csv").unwrap();
+        storage.remove("exports/games.csv").unwrap();
+        assert_eq!(storage.list("").unwrap().len(), 2);
+
+        assert_eq!(
+            storage.key(&root.join("nba").join("volumes").join("2024.vol")),
+            Some("nba/volumes/2024.vol".to_string())
+        );
+        assert_eq!(storage.key(Path::new("/not/the/root")), None);
+
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_models_outside_the_root_are_prefixed() {
+        let storage = LocalStorage {
+            root: PathBuf::from("/data"),
+            models: Some(PathBuf::from("/models")),
+        };
+
+        let records = PathBuf::from("/models/elo/records.csv");
+
+        assert_eq!(
+            storage.key(&records).as_deref(),
+            Some("models/elo/records.csv")
+        );
+        assert_eq!(storage.path("models/elo/records.csv"), records);
+    }
+}
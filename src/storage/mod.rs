@@ -0,0 +1,189 @@
+pub mod local;
+
+pub mod s3;
+
+pub mod sigv4;
+
+use crate::constants::config::{config, StorageKind};
+
+use crate::storage::local::LocalStorage;
+use crate::storage::s3::S3Storage;
+
+use once_cell::sync::OnceCell;
+
+use std::fmt::Debug;
+use std::future::Future;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use std::{fs, io, thread};
+
+use tokio::runtime::{Builder, Handle, RuntimeFlavor};
+
+/// ## Storage
+///
+/// where data files and model artifacts live. files are addressed by a key: their path under the
+/// data root with `/` separators (e.g. `nba/volumes/2024_regular_season.vol`).
+pub trait Storage: Send + Sync + Debug {
+    fn read(&self, key: &str) -> io::Result<Vec<u8>>;
+
+    /// a reader over a file. backends that can't stream read the whole file first.
+    fn open(&self, key: &str) -> io::Result<Box<dyn Read + Send>> {
+        Ok(Box::new(io::Cursor::new(self.read(key)?)))
+    }
+
+    /// the first `len` bytes of a file, or all of it if it is shorter.
+    fn read_prefix(&self, key: &str, len: usize) -> io::Result<Vec<u8>>;
+
+    /// write a file, creating whatever directories it needs.
+    fn write(&self, key: &str, contents: &[u8]) -> io::Result<()>;
+
+    fn exists(&self, key: &str) -> io::Result<bool>;
+
+    /// a value that changes whenever the file is rewritten, so a copy read earlier can be checked
+    /// without reading the file again.
+    fn stamp(&self, key: &str) -> io::Result<String>;
+
+    /// delete a file. deleting a file that doesn't exist is not an error.
+    fn remove(&self, key: &str) -> io::Result<()>;
+
+    /// every key that starts with `prefix`, sorted.
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>>;
+
+    /// where the files are, for messages.
+    fn describe(&self) -> String;
+}
+
+static STORAGE: OnceCell<Box<dyn Storage>> = OnceCell::new();
+
+/// the configured storage backend. see `Config::storage`.
+pub fn storage() -> &'static dyn Storage {
+    STORAGE
+        .get_or_init(|| match config().storage {
+            StorageKind::Local => Box::new(LocalStorage::from_config()),
+            StorageKind::S3 => match S3Storage::from_config() {
+                Ok(s3) => Box::new(s3),
+                Err(e) => panic!("{e}\n💀 failed to configure s3 storage"),
+            },
+        })
+        .as_ref()
+}
+
+// the path level functions below are what the rest of the crate uses. paths under the data root
+// (or models root) go through the configured storage, anything else is read from the local
+// filesystem.
+
+pub fn read(path: &Path) -> io::Result<Vec<u8>> {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().read(&key),
+        None => fs::read(path),
+    }
+}
+
+pub fn read_to_string(path: &Path) -> io::Result<String> {
+    String::from_utf8(read(path)?).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+}
+
+pub fn open(path: &Path) -> io::Result<Box<dyn Read + Send>> {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().open(&key),
+        None => Ok(Box::new(fs::File::open(path)?)),
+    }
+}
+
+pub fn read_prefix(path: &Path, len: usize) -> io::Result<Vec<u8>> {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().read_prefix(&key, len),
+        None => LocalStorage::read_prefix_at(path, len),
+    }
+}
+
+pub fn write(path: &Path, contents: impl AsRef<[u8]>) -> io::Result<()> {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().write(&key, contents.as_ref()),
+        None => LocalStorage::write_at(path, contents.as_ref()),
+    }
+}
+
+pub fn exists(path: &Path) -> bool {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().exists(&key).unwrap_or(false),
+        None => path.exists(),
+    }
+}
+
+pub fn stamp(path: &Path) -> io::Result<String> {
+    match LocalStorage::from_config().key(path) {
+        Some(key) => storage().stamp(&key),
+        None => LocalStorage::stamp_at(path),
+    }
+}
+
+/// every file under `dir`, at any depth, as the path it would have in the data root.
+pub fn list(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let local = LocalStorage::from_config();
+
+    match local.key(dir) {
+        Some(key) => {
+            let prefix = if key.is_empty() {
+                key
+            } else {
+                format!("{key}/")
+            };
+
+            Ok(storage()
+                .list(&prefix)?
+                .iter()
+                .map(|key| local.path(key))
+                .collect())
+        }
+        None => LocalStorage::new(dir.to_path_buf())
+            .list("")
+            .map(|keys| keys.iter().map(|key| dir.join(key)).collect()),
+    }
+}
+
+/// delete `dir` and every file under it. returns the number of files deleted.
+pub fn remove_all(dir: &Path) -> io::Result<usize> {
+    let files = list(dir)?;
+
+    for file in &files {
+        match LocalStorage::from_config().key(file) {
+            Some(key) => storage().remove(&key)?,
+            None => fs::remove_file(file)?,
+        }
+    }
+
+    // the empty directories left behind locally
+    if dir.is_dir() {
+        fs::remove_dir_all(dir)?;
+    }
+
+    Ok(files.len())
+}
+
+/// run a future to completion from synchronous code, whether or not a runtime is already running.
+pub(crate) fn block_on<F>(future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    let runtime = || {
+        Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .expect("💀 failed to build a runtime for storage requests")
+    };
+
+    match Handle::try_current() {
+        Ok(handle) if handle.runtime_flavor() == RuntimeFlavor::MultiThread => {
+            tokio::task::block_in_place(|| handle.block_on(future))
+        }
+        // a current thread runtime can't be blocked from inside, so run it on another thread
+        Ok(_) => thread::scope(|s| {
+            s.spawn(|| runtime().block_on(future))
+                .join()
+                .expect("💀 storage request panicked")
+        }),
+        Err(_) => runtime().block_on(future),
+    }
+}
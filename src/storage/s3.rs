@@ -0,0 +1,377 @@
+use crate::checksum::checksum::sha256;
+
+use crate::constants::config::{config, S3Config};
+
+use crate::storage::sigv4::{canonical_query, uri_encode, SignedRequest, Signer, EMPTY_PAYLOAD};
+use crate::storage::{block_on, Storage};
+
+use chrono::Utc;
+
+use once_cell::sync::Lazy;
+
+use regex::Regex;
+
+use reqwest::header::{HeaderMap, ETAG, LAST_MODIFIED};
+use reqwest::{Client, Method, StatusCode};
+
+use std::io;
+
+use thiserror::Error;
+
+/// access key variables, in the order they are checked. the last pair is what `services/start_s3`
+/// starts minio with.
+const CREDENTIAL_VARS: [(&str, &str); 3] = [
+    ("WARHEADS_S3_ACCESS_KEY", "WARHEADS_S3_SECRET_KEY"),
+    ("AWS_ACCESS_KEY_ID", "AWS_SECRET_ACCESS_KEY"),
+    ("MINIO_ROOT_USER", "MINIO_ROOT_PASSWORD"),
+];
+
+static KEY: Lazy<Regex> = Lazy::new(|| Regex::new(r"<Key>(.*?)</Key>").unwrap());
+
+static CONTINUATION: Lazy<Regex> =
+    Lazy::new(|| Regex::new(r"<NextContinuationToken>(.*?)</NextContinuationToken>").unwrap());
+
+/// ## S3Storage
+///
+/// objects in an s3 compatible bucket (aws, minio, localstack). requests are path style, i.e.
+/// `{endpoint}/{bucket}/{prefix}/{key}`, which every s3 compatible service accepts.
+#[derive(Debug)]
+pub struct S3Storage {
+    client: Client,
+    signer: Signer,
+    endpoint: String,
+    host: String,
+    bucket: String,
+    prefix: String,
+}
+
+impl S3Storage {
+    pub fn new(s3: &S3Config, access_key: String, secret_key: String) -> Result<Self, S3Error> {
+        let bucket = s3.bucket.clone().ok_or(S3Error::MissingBucket)?;
+
+        let endpoint = s3.endpoint.trim_end_matches('/').to_string();
+
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint.as_str(), |(_, host)| host)
+            .to_string();
+
+        Ok(S3Storage {
+            client: Client::new(),
+            signer: Signer::new(access_key, secret_key, s3.region.clone()),
+            endpoint,
+            host,
+            bucket,
+            prefix: s3.prefix.trim_matches('/').to_string(),
+        })
+    }
+
+    /// the bucket from the config, with credentials from the environment.
+    pub fn from_config() -> Result<Self, S3Error> {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+        let (access_key, secret_key) = CREDENTIAL_VARS
+            .iter()
+            .find_map(|(access, secret)| Some((var(access)?, var(secret)?)))
+            .ok_or(S3Error::MissingCredentials)?;
+
+        S3Storage::new(&config().s3, access_key, secret_key)
+    }
+
+    /// the object key of a storage key, under the prefix.
+    fn object(&self, key: &str) -> String {
+        match self.prefix.as_str() {
+            "" => key.to_string(),
+            prefix => format!("{prefix}/{key}"),
+        }
+    }
+
+    /// send a signed request for `key` (or the bucket itself when `key` is `None`).
+    fn send(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> io::Result<(StatusCode, Vec<u8>)> {
+        self.request(method, key, query, headers, body)
+            .map(|(status, _, body)| (status, body))
+    }
+
+    /// the same as `send`, also returning the headers of the response.
+    fn request(
+        &self,
+        method: Method,
+        key: Option<&str>,
+        query: &[(&str, &str)],
+        headers: &[(&str, &str)],
+        body: Vec<u8>,
+    ) -> io::Result<(StatusCode, HeaderMap, Vec<u8>)> {
+        let path = match key {
+            Some(key) => format!("/{}/{}", self.bucket, uri_encode(&self.object(key), false)),
+            None => format!("/{}", self.bucket),
+        };
+
+        let payload_hash = match body.is_empty() {
+            true => EMPTY_PAYLOAD.to_string(),
+            false => sha256(&body),
+        };
+
+        let request = SignedRequest {
+            method: method.as_str(),
+            host: &self.host,
+            path: &path,
+            query,
+            headers,
+            payload_hash: &payload_hash,
+        };
+
+        let (authorization, amz_date) = self.signer.authorize(&request, Utc::now());
+
+        let mut url = format!("{}{path}", self.endpoint);
+
+        if !query.is_empty() {
+            url.push('?');
+            url.push_str(&canonical_query(query));
+        }
+
+        let mut builder = self
+            .client
+            .request(method, url)
+            .header("authorization", authorization)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash);
+
+        for (name, value) in headers {
+            builder = builder.header(*name, *value);
+        }
+
+        if !body.is_empty() {
+            builder = builder.body(body);
+        }
+
+        block_on(async {
+            let response = builder.send().await.map_err(io::Error::other)?;
+
+            let status = response.status();
+            let headers = response.headers().clone();
+            let bytes = response.bytes().await.map_err(io::Error::other)?;
+
+            Ok((status, headers, bytes.to_vec()))
+        })
+    }
+
+    fn check(&self, key: &str, status: StatusCode, body: &[u8]) -> io::Result<()> {
+        match status {
+            s if s.is_success() => Ok(()),
+            StatusCode::NOT_FOUND => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!(
+                    "❌ s3://{}/{} does not exist",
+                    self.bucket,
+                    self.object(key)
+                ),
+            )),
+            s => Err(io::Error::other(format!(
+                "❌ {s}: {}\n❌ s3 request for s3://{}/{} failed",
+                String::from_utf8_lossy(body).trim(),
+                self.bucket,
+                self.object(key)
+            ))),
+        }
+    }
+}
+
+impl Storage for S3Storage {
+    fn read(&self, key: &str) -> io::Result<Vec<u8>> {
+        let (status, body) = self.send(Method::GET, Some(key), &[], &[], vec![])?;
+
+        self.check(key, status, &body)?;
+
+        Ok(body)
+    }
+
+    fn read_prefix(&self, key: &str, len: usize) -> io::Result<Vec<u8>> {
+        if len == 0 {
+            return Ok(vec![]);
+        }
+
+        let range = format!("bytes=0-{}", len - 1);
+
+        let (status, mut body) =
+            self.send(Method::GET, Some(key), &[], &[("range", &range)], vec![])?;
+
+        // objects shorter than the range come back whole, or as 416 if they're empty
+        if status == StatusCode::RANGE_NOT_SATISFIABLE {
+            return Ok(vec![]);
+        }
+
+        self.check(key, status, &body)?;
+
+        body.truncate(len);
+
+        Ok(body)
+    }
+
+    fn write(&self, key: &str, contents: &[u8]) -> io::Result<()> {
+        let (status, body) = self.send(Method::PUT, Some(key), &[], &[], contents.to_vec())?;
+
+        self.check(key, status, &body)
+    }
+
+    fn exists(&self, key: &str) -> io::Result<bool> {
+        let (status, body) = self.send(Method::HEAD, Some(key), &[], &[], vec![])?;
+
+        match status {
+            StatusCode::NOT_FOUND => Ok(false),
+            status => self.check(key, status, &body).map(|_| true),
+        }
+    }
+
+    fn stamp(&self, key: &str) -> io::Result<String> {
+        let (status, headers, body) = self.request(Method::HEAD, Some(key), &[], &[], vec![])?;
+
+        self.check(key, status, &body)?;
+
+        headers
+            .get(ETAG)
+            .or_else(|| headers.get(LAST_MODIFIED))
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+            .ok_or_else(|| {
+                io::Error::other(format!(
+                    "❌ s3://{}/{} has no etag",
+                    self.bucket,
+                    self.object(key)
+                ))
+            })
+    }
+
+    fn remove(&self, key: &str) -> io::Result<()> {
+        let (status, body) = self.send(Method::DELETE, Some(key), &[], &[], vec![])?;
+
+        match status {
+            StatusCode::NOT_FOUND => Ok(()),
+            status => self.check(key, status, &body),
+        }
+    }
+
+    fn list(&self, prefix: &str) -> io::Result<Vec<String>> {
+        let object_prefix = self.object(prefix);
+
+        let mut keys = Vec::new();
+        let mut token: Option<String> = None;
+
+        loop {
+            let mut query = vec![("list-type", "2"), ("prefix", object_prefix.as_str())];
+
+            if let Some(token) = &token {
+                query.push(("continuation-token", token.as_str()));
+            }
+
+            let (status, body) = self.send(Method::GET, None, &query, &[], vec![])?;
+
+            self.check(prefix, status, &body)?;
+
+            let xml = String::from_utf8_lossy(&body);
+
+            keys.extend(KEY.captures_iter(&xml).filter_map(|c| {
+                let object = unescape(&c[1]);
+
+                match self.prefix.as_str() {
+                    "" => Some(object),
+                    prefix => object
+                        .strip_prefix(&format!("{prefix}/"))
+                        .map(str::to_string),
+                }
+            }));
+
+            match CONTINUATION.captures(&xml) {
+                Some(c) => token = Some(unescape(&c[1])),
+                None => break,
+            }
+        }
+
+        keys.sort();
+
+        Ok(keys)
+    }
+
+    fn describe(&self) -> String {
+        match self.prefix.as_str() {
+            "" => format!("s3://{} ({})", self.bucket, self.endpoint),
+            prefix => format!("s3://{}/{prefix} ({})", self.bucket, self.endpoint),
+        }
+    }
+}
+
+/// undo the xml escaping of keys in a listing.
+fn unescape(s: &str) -> String {
+    s.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[derive(Debug, Error)]
+pub enum S3Error {
+    #[error("❌ no s3 bucket is configured. set WARHEADS_S3_BUCKET or `bucket` under [s3] in warheads.toml")]
+    MissingBucket,
+    #[error("❌ no s3 credentials found. set WARHEADS_S3_ACCESS_KEY and WARHEADS_S3_SECRET_KEY")]
+    MissingCredentials,
+}
+
+#[cfg(test)]
+mod test_s3 {
+    use super::*;
+
+    fn s3(prefix: &str) -> S3Storage {
+        let config = S3Config {
+            endpoint: "http://localhost:9000/".to_string(),
+            bucket: Some("warheads".to_string()),
+            region: "us-east-1".to_string(),
+            prefix: prefix.to_string(),
+        };
+
+        S3Storage::new(&config, "minio".to_string(), "minio123".to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_objects_are_under_the_prefix() {
+        assert_eq!(
+            s3("").object("nba/volumes/2024.vol"),
+            "nba/volumes/2024.vol"
+        );
+        assert_eq!(
+            s3("/team/").object("nba/volumes/2024.vol"),
+            "team/nba/volumes/2024.vol"
+        );
+        assert_eq!(s3("team").host, "localhost:9000");
+        assert_eq!(
+            s3("team").describe(),
+            "s3://warheads/team (http://localhost:9000)"
+        );
+    }
+
+    #[test]
+    fn test_bucket_is_required() {
+        let config = S3Config {
+            endpoint: "http://localhost:9000".to_string(),
+            bucket: None,
+            region: "us-east-1".to_string(),
+            prefix: String::new(),
+        };
+
+        assert!(matches!(
+            S3Storage::new(&config, String::new(), String::new()),
+            Err(S3Error::MissingBucket)
+        ));
+    }
+
+    #[test]
+    fn test_unescape_keys() {
+        assert_eq!(unescape("a&amp;b &lt;c&gt;"), "a&b <c>");
+    }
+}
@@ -0,0 +1,518 @@
+use std::collections::{HashMap, HashSet};
+use std::io;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState, Tabs};
+use ratatui::{DefaultTerminal, Frame};
+
+use thiserror::Error;
+
+use crate::dapi::season_manager::get_current_era;
+
+use crate::ml::model::Model;
+use crate::ml::models::elo_models::elo_tracker::EloTracker;
+
+use crate::stats::chronology::Chronology;
+use crate::stats::prediction::Prediction;
+use crate::stats::record::Record;
+use crate::stats::season_period::SeasonPeriod;
+
+use crate::tui::game_ratings::GameRatings;
+use crate::tui::progress::hide_progress;
+use crate::tui::tui_display::TuiDisplay;
+
+use crate::types::{GameId, PlayerId, PlayerName, SeasonId, TeamAbbreviation, TeamName};
+
+/// how many players the leaderboard shows.
+const LEADERS: usize = 100;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tab {
+    Games,
+    Standings,
+    Leaders,
+    Game,
+}
+
+impl Tab {
+    const ALL: [Tab; 4] = [Tab::Games, Tab::Standings, Tab::Leaders, Tab::Game];
+
+    fn title(&self) -> &'static str {
+        match self {
+            Tab::Games => "1 games",
+            Tab::Standings => "2 standings",
+            Tab::Leaders => "3 leaders",
+            Tab::Game => "4 game",
+        }
+    }
+
+    fn index(&self) -> usize {
+        Tab::ALL.iter().position(|tab| tab == self).unwrap_or(0)
+    }
+
+    fn next(&self) -> Tab {
+        Tab::ALL[(self.index() + 1) % Tab::ALL.len()]
+    }
+
+    fn prev(&self) -> Tab {
+        Tab::ALL[(self.index() + Tab::ALL.len() - 1) % Tab::ALL.len()]
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Standing {
+    pub team_abbr: TeamAbbreviation,
+    pub team_name: TeamName,
+    pub record: Record,
+}
+
+impl Standing {
+    fn pct(&self) -> f64 {
+        match self.record.wins + self.record.losses {
+            0 => 0.0,
+            games => self.record.wins as f64 / games as f64,
+        }
+    }
+}
+
+/// ## DashboardData
+///
+/// everything the dashboard shows, loaded before it takes over the terminal.
+#[derive(Debug, Default)]
+pub struct DashboardData {
+    pub predictions: Vec<Prediction>,
+    pub standings: Vec<Standing>,
+    /// the highest rated players of this season and last, best first.
+    pub leaders: Vec<(PlayerName, i64)>,
+    pub ratings: HashMap<PlayerId, i64>,
+    /// what failed to load, shown under the tabs instead of stopping the dashboard.
+    pub notices: Vec<String>,
+}
+
+impl DashboardData {
+    /// standings of the current regular season and elo ratings from `ratings_run` of the elo model,
+    /// next to the `predictions` of upcoming games (or why there are none).
+    pub fn load(predictions: Result<Vec<Prediction>, String>, ratings_run: Option<&str>) -> Self {
+        let mut data = DashboardData::default();
+
+        match predictions {
+            Ok(predictions) => data.predictions = predictions,
+            Err(e) => data.notices.push(e),
+        }
+
+        let era = get_current_era();
+
+        data.standings = standings(&Chronology::from_era(SeasonId::from((
+            era.year(),
+            SeasonPeriod::RegularSeason,
+        ))));
+
+        let elo = EloTracker::new().model_name();
+
+        match EloTracker::from_run(ratings_run) {
+            Ok(tracker) => data.ratings = tracker.current_ratings().clone(),
+            Err(e) => data.notices.push(format!(
+                "{e}\n❌ no player ratings. try `warheads train {elo}`"
+            )),
+        }
+
+        let chronology = Chronology::with_lookback(era);
+        let directory = chronology.player_directory();
+
+        let mut leaders = data
+            .ratings
+            .iter()
+            .filter_map(|(id, rating)| Some((directory.get(*id)?.clone(), *rating)))
+            .collect::<Vec<_>>();
+
+        leaders.sort_by_key(|(_, rating)| -rating);
+        leaders.truncate(LEADERS);
+
+        data.leaders = leaders;
+
+        data
+    }
+}
+
+/// every team that played in `chronology`, best record first.
+pub fn standings(chronology: &Chronology) -> Vec<Standing> {
+    let mut seen = HashSet::new();
+    let mut standings = Vec::new();
+
+    for game in chronology.games() {
+        for team in [game.home(), game.away()] {
+            if seen.insert(team.team_id()) {
+                standings.push(Standing {
+                    team_abbr: team.team_abbr(),
+                    team_name: team.team_name(),
+                    record: chronology.calculate_record(team.team_id()),
+                });
+            }
+        }
+    }
+
+    standings.sort_by(|a, b| b.pct().total_cmp(&a.pct()));
+
+    standings
+}
+
+/// ## Dashboard
+///
+/// a full screen view of upcoming games, standings and player ratings. `tab`/arrow keys switch
+/// tabs, `j`/`k` move, `enter` opens a game's rosters and `q` quits.
+pub struct Dashboard {
+    data: DashboardData,
+    tab: Tab,
+    games: TableState,
+    standings: TableState,
+    leaders: TableState,
+    /// the rosters of games opened so far, or why they couldn't be rated.
+    opened: HashMap<GameId, Result<String, String>>,
+    open: Option<GameId>,
+    quit: bool,
+}
+
+impl Dashboard {
+    pub fn new(data: DashboardData) -> Self {
+        let select_first = |len: usize| TableState::default().with_selected((len > 0).then_some(0));
+
+        Dashboard {
+            games: select_first(data.predictions.len()),
+            standings: select_first(data.standings.len()),
+            leaders: select_first(data.leaders.len()),
+            data,
+            tab: Tab::Games,
+            opened: HashMap::new(),
+            open: None,
+            quit: false,
+        }
+    }
+
+    pub fn run(mut self) -> Result<(), DashboardError> {
+        hide_progress();
+
+        let mut terminal = ratatui::init();
+
+        let result = self.event_loop(&mut terminal);
+
+        ratatui::restore();
+
+        result.map_err(DashboardError::TerminalError)
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.handle_key(key.code);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn tab(&self) -> Tab {
+        self.tab
+    }
+
+    pub fn open_game(&self) -> Option<GameId> {
+        self.open
+    }
+
+    pub fn quit(&self) -> bool {
+        self.quit
+    }
+
+    pub fn handle_key(&mut self, code: KeyCode) {
+        match code {
+            KeyCode::Char('q') => self.quit = true,
+            KeyCode::Esc if self.tab == Tab::Game => self.tab = Tab::Games,
+            KeyCode::Esc => self.quit = true,
+            KeyCode::Tab | KeyCode::Right | KeyCode::Char('l') => self.tab = self.tab.next(),
+            KeyCode::BackTab | KeyCode::Left | KeyCode::Char('h') => self.tab = self.tab.prev(),
+            KeyCode::Char('1') => self.tab = Tab::Games,
+            KeyCode::Char('2') => self.tab = Tab::Standings,
+            KeyCode::Char('3') => self.tab = Tab::Leaders,
+            KeyCode::Char('4') => self.tab = Tab::Game,
+            KeyCode::Down | KeyCode::Char('j') => self.step(1),
+            KeyCode::Up | KeyCode::Char('k') => self.step(-1),
+            KeyCode::Enter if self.tab == Tab::Games => self.open_selected(),
+            _ => {}
+        }
+    }
+
+    fn step(&mut self, by: isize) {
+        let (state, len) = match self.tab {
+            Tab::Games => (&mut self.games, self.data.predictions.len()),
+            Tab::Standings => (&mut self.standings, self.data.standings.len()),
+            Tab::Leaders => (&mut self.leaders, self.data.leaders.len()),
+            Tab::Game => return,
+        };
+
+        if len == 0 {
+            return;
+        }
+
+        let selected = state.selected().unwrap_or(0) as isize;
+
+        state.select(Some((selected + by).clamp(0, len as isize - 1) as usize));
+    }
+
+    fn open_selected(&mut self) {
+        let Some(prediction) = self
+            .games
+            .selected()
+            .and_then(|i| self.data.predictions.get(i))
+        else {
+            return;
+        };
+
+        let card = prediction.card();
+        let ratings = &self.data.ratings;
+
+        self.opened.entry(card.game_id()).or_insert_with(|| {
+            GameRatings::new(card, ratings)
+                .map(|game_ratings| game_ratings.display())
+                .map_err(|e| e.to_string())
+        });
+
+        self.open = Some(card.game_id());
+        self.tab = Tab::Game;
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let notices = self
+            .data
+            .notices
+            .iter()
+            .map(|n| n.lines().count())
+            .sum::<usize>() as u16;
+
+        let [tabs, notice, body, help] = Layout::vertical([
+            Constraint::Length(3),
+            Constraint::Length(notices),
+            Constraint::Min(0),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Tabs::new(Tab::ALL.iter().map(|tab| tab.title()))
+                .block(Block::default().borders(Borders::ALL).title(" warheads "))
+                .select(self.tab.index())
+                .highlight_style(Style::default().add_modifier(Modifier::REVERSED)),
+            tabs,
+        );
+
+        frame.render_widget(Paragraph::new(self.data.notices.join("\n")), notice);
+
+        match self.tab {
+            Tab::Games => self.draw_games(frame, body),
+            Tab::Standings => self.draw_standings(frame, body),
+            Tab::Leaders => self.draw_leaders(frame, body),
+            Tab::Game => self.draw_game(frame, body),
+        }
+
+        frame.render_widget(
+            Line::from("tab/←→ switch  1-4 jump  j/k move  enter open game  esc back  q quit"),
+            help,
+        );
+    }
+
+    fn draw_games(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.data.predictions.iter().map(|prediction| {
+            let card = prediction.card();
+            let p = prediction.probability();
+
+            Row::new([
+                Cell::from(format!("{}", card.date())),
+                Cell::from(card.away().team_abbr().emphasize()),
+                Cell::from(card.home().team_abbr().emphasize()),
+                Cell::from(format!("{:>5.1}%", (1.0 - p) * 100.0)),
+                Cell::from(format!("{:>5.1}%", p * 100.0)),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(12),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header(["date", "away", "home", "away %", "home %"]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" upcoming games "),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, area, &mut self.games);
+    }
+
+    fn draw_standings(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self.data.standings.iter().enumerate().map(|(i, standing)| {
+            Row::new([
+                Cell::from(format!("{}", i + 1)),
+                Cell::from(standing.team_abbr.emphasize()),
+                Cell::from(standing.team_name.0.clone()),
+                Cell::from(standing.record.to_string()),
+                Cell::from(format!("{:.3}", standing.pct())),
+            ])
+        });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(4),
+                Constraint::Length(6),
+                Constraint::Length(26),
+                Constraint::Length(8),
+                Constraint::Length(6),
+            ],
+        )
+        .header(header(["#", "team", "", "record", "pct"]))
+        .block(Block::default().borders(Borders::ALL).title(" standings "))
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, area, &mut self.standings);
+    }
+
+    fn draw_leaders(&mut self, frame: &mut Frame, area: Rect) {
+        let rows = self
+            .data
+            .leaders
+            .iter()
+            .enumerate()
+            .map(|(i, (name, rating))| {
+                Row::new([
+                    Cell::from(format!("{}", i + 1)),
+                    Cell::from(name.to_string()),
+                    Cell::from(rating.to_string()),
+                ])
+            });
+
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(5),
+                Constraint::Length(28),
+                Constraint::Length(8),
+            ],
+        )
+        .header(header(["#", "player", "elo"]))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" player ratings "),
+        )
+        .row_highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+
+        frame.render_stateful_widget(table, area, &mut self.leaders);
+    }
+
+    fn draw_game(&self, frame: &mut Frame, area: Rect) {
+        let text = match self.open.and_then(|game_id| self.opened.get(&game_id)) {
+            Some(Ok(ratings)) => ratings.clone(),
+            Some(Err(e)) => e.clone(),
+            None => String::from("select a game on the games tab and press enter"),
+        };
+
+        frame.render_widget(
+            Paragraph::new(text).block(Block::default().borders(Borders::ALL).title(" rosters ")),
+            area,
+        );
+    }
+}
+
+fn header<const N: usize>(titles: [&'static str; N]) -> Row<'static> {
+    Row::new(titles).style(Style::default().add_modifier(Modifier::BOLD))
+}
+
+#[derive(Debug, Error)]
+pub enum DashboardError {
+    #[error("❌ {0}\n❌ failed to draw the dashboard.")]
+    TerminalError(io::Error),
+}
+
+#[cfg(test)]
+mod test_dashboard {
+    use super::{Dashboard, DashboardData, Standing, Tab};
+
+    use crate::stats::record::Record;
+
+    use crate::types::{TeamAbbreviation, TeamName};
+
+    use ratatui::crossterm::event::KeyCode;
+
+    use std::str::FromStr;
+
+    fn dashboard() -> Dashboard {
+        let standing = |abbr: &str, wins: u64, losses: u64| Standing {
+            team_abbr: TeamAbbreviation::from_str(abbr).unwrap(),
+            team_name: TeamName(abbr.to_owned()),
+            record: Record::wl(wins, losses),
+        };
+
+        Dashboard::new(DashboardData {
+            standings: vec![standing("BOS", 10, 2), standing("NYK", 8, 4)],
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_tabs_wrap() {
+        let mut dashboard = dashboard();
+
+        dashboard.handle_key(KeyCode::BackTab);
+        assert_eq!(dashboard.tab(), Tab::Game);
+
+        dashboard.handle_key(KeyCode::Tab);
+        dashboard.handle_key(KeyCode::Tab);
+        assert_eq!(dashboard.tab(), Tab::Standings);
+
+        dashboard.handle_key(KeyCode::Char('3'));
+        assert_eq!(dashboard.tab(), Tab::Leaders);
+    }
+
+    #[test]
+    fn test_selection_stays_in_bounds() {
+        let mut dashboard = dashboard();
+
+        dashboard.handle_key(KeyCode::Char('2'));
+        dashboard.handle_key(KeyCode::Up);
+        assert_eq!(dashboard.standings.selected(), Some(0));
+
+        for _ in 0..5 {
+            dashboard.handle_key(KeyCode::Down);
+        }
+        assert_eq!(dashboard.standings.selected(), Some(1));
+    }
+
+    #[test]
+    fn test_enter_without_games_and_quit() {
+        let mut dashboard = dashboard();
+
+        dashboard.handle_key(KeyCode::Enter);
+        assert_eq!(dashboard.tab(), Tab::Games);
+        assert_eq!(dashboard.open_game(), None);
+
+        dashboard.handle_key(KeyCode::Char('4'));
+        dashboard.handle_key(KeyCode::Esc);
+        assert_eq!(dashboard.tab(), Tab::Games);
+        assert!(!dashboard.quit());
+
+        dashboard.handle_key(KeyCode::Char('q'));
+        assert!(dashboard.quit());
+    }
+}
@@ -4,13 +4,15 @@ use std::collections::HashMap;
 use crate::format;
 
 use crate::ml::cdf;
-use crate::stats::chronology::Chronology;
+use crate::stats::chronology::{Chronology, ChronologyError};
 use crate::stats::gamecard::GameCard;
 
 use crate::tui::tui_display::TuiDisplay;
 
 use crate::types::{PlayerId, PlayerName};
 
+use thiserror::Error;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GameRatings {
     game_card: GameCard,
@@ -19,43 +21,40 @@ pub struct GameRatings {
 }
 
 impl GameRatings {
-    pub fn new(gamecard: &GameCard, ratings: &HashMap<PlayerId, i64>) -> Self {
-        let chronology = Chronology::from_era(gamecard.season());
+    pub fn new(
+        gamecard: &GameCard,
+        ratings: &HashMap<PlayerId, i64>,
+    ) -> Result<Self, GameRatingsError> {
+        let mut chronology = Chronology::new();
 
-        let home_expected_roster =
-            chronology.get_expected_roster(gamecard.home().team_id(), gamecard.game_id());
-        let away_expected_roster =
-            chronology.get_expected_roster(gamecard.away().team_id(), gamecard.game_id());
+        chronology
+            .load_with_lookback(gamecard.season())
+            .map_err(GameRatingsError::ChronologyError)?;
 
         let player_directory = chronology.player_directory();
 
-        GameRatings {
-            game_card: gamecard.clone(),
-            home_ratings: home_expected_roster
-                .iter()
+        let rate = |team_id| {
+            chronology
+                .get_expected_roster(team_id, gamecard.game_id())
+                .into_iter()
                 .map(|id| {
-                    (
-                        *id,
-                        (
-                            player_directory.get(*id).unwrap().clone(),
-                            *ratings.get(id).unwrap(),
-                        ),
-                    )
-                }) //maybe panic on this?
-                .collect(),
-            away_ratings: away_expected_roster
-                .iter()
-                .map(|id| {
-                    (
-                        *id,
-                        (
-                            player_directory.get(*id).unwrap().clone(),
-                            *ratings.get(id).unwrap(),
-                        ),
-                    )
+                    let name = player_directory
+                        .get(id)
+                        .ok_or(GameRatingsError::UnknownPlayer(id))?;
+                    let rating = ratings
+                        .get(&id)
+                        .ok_or(GameRatingsError::UnratedPlayer(id))?;
+
+                    Ok((id, (name.clone(), *rating)))
                 })
-                .collect(),
-        }
+                .collect::<Result<HashMap<_, _>, GameRatingsError>>()
+        };
+
+        Ok(GameRatings {
+            game_card: gamecard.clone(),
+            home_ratings: rate(gamecard.home().team_id())?,
+            away_ratings: rate(gamecard.away().team_id())?,
+        })
     }
 
     pub fn home_roster(&self) -> Option<Vec<PlayerId>> {
@@ -210,3 +209,13 @@ impl TuiDisplay for GameRatings {
         s
     }
 }
+
+#[derive(Debug, Error)]
+pub enum GameRatingsError {
+    #[error("{0}\n❌ failed to load the season of the game")]
+    ChronologyError(ChronologyError),
+    #[error("❌ player {0} is on the expected roster but not in the player directory")]
+    UnknownPlayer(PlayerId),
+    #[error("❌ player {0} is on the expected roster but has no rating")]
+    UnratedPlayer(PlayerId),
+}
@@ -1,4 +1,6 @@
+pub mod dashboard;
 pub mod game_ratings;
+pub mod progress;
 pub mod prompter;
 pub mod tui;
 pub mod tui_display;
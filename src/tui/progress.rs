@@ -0,0 +1,210 @@
+use std::io::IsTerminal;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use indicatif::{MultiProgress, ProgressBar, ProgressStyle};
+
+use once_cell::sync::Lazy;
+
+use crate::logging::logger::{self, LogFormat};
+
+/// every bar on screen, so the bars of nested operations stack instead of drawing over each other.
+static BARS: Lazy<MultiProgress> = Lazy::new(MultiProgress::new);
+
+/// set while something else owns the terminal, like the dashboard.
+static HIDDEN: AtomicBool = AtomicBool::new(false);
+
+/// how often progress is logged when it can't be drawn as a bar.
+const LINE_INTERVAL: Duration = Duration::from_secs(10);
+
+/// a progress bar counting `len` items, e.g. files checksummed.
+pub fn progress_bar(len: u64, message: &str) -> ProgressBar {
+    let bar = ProgressBar::new(len);
+
+    bar.set_style(
+        ProgressStyle::with_template("{msg} [{bar:40.cyan/blue}] {pos}/{len} ({eta})")
+            .expect("💀 invalid progress bar template")
+            .progress_chars("=> "),
+    );
+
+    bar.set_message(message.to_owned());
+
+    bar
+}
+
+/// a spinner counting steps of an operation that doesn't know how many it will take.
+fn spinner(message: &str) -> ProgressBar {
+    let bar = ProgressBar::new_spinner();
+
+    bar.set_style(
+        ProgressStyle::with_template("{spinner:.cyan} {msg} {pos} ({elapsed})")
+            .expect("💀 invalid spinner template"),
+    );
+
+    bar.set_message(message.to_owned());
+    bar.enable_steady_tick(Duration::from_millis(120));
+
+    bar
+}
+
+/// stop drawing progress bars, e.g. while a full screen app is running.
+pub fn hide_progress() {
+    HIDDEN.store(true, Ordering::Relaxed);
+}
+
+/// run `f` with the bars cleared from the screen, so lines written by `f` aren't drawn over.
+pub fn suspend<R>(f: impl FnOnce() -> R) -> R {
+    BARS.suspend(f)
+}
+
+/// ## Progress
+///
+/// how far along a long operation is. it's drawn as a bar while stdout is a terminal, and logged
+/// as a plain line every few seconds otherwise. nothing is shown when info messages are filtered
+/// out. the bar is cleared when the progress is dropped.
+pub struct Progress(Display);
+
+enum Display {
+    Bar(ProgressBar),
+    Lines(Lines),
+    Hidden,
+}
+
+impl Progress {
+    /// progress through `len` items.
+    pub fn new(len: u64, message: &str) -> Self {
+        Progress::show(Some(len), message)
+    }
+
+    /// progress through an unknown number of steps.
+    pub fn unbounded(message: &str) -> Self {
+        Progress::show(None, message)
+    }
+
+    fn show(len: Option<u64>, message: &str) -> Self {
+        if HIDDEN.load(Ordering::Relaxed) || !log::log_enabled!(log::Level::Info) {
+            return Progress(Display::Hidden);
+        }
+
+        if !std::io::stdout().is_terminal() || logger::format() != Some(LogFormat::Human) {
+            return Progress(Display::Lines(Lines::new(len, message)));
+        }
+
+        let bar = match len {
+            Some(len) => progress_bar(len, message),
+            None => spinner(message),
+        };
+
+        Progress(Display::Bar(BARS.add(bar)))
+    }
+
+    pub fn inc(&self, n: u64) {
+        match &self.0 {
+            Display::Bar(bar) => bar.inc(n),
+            Display::Lines(lines) => lines.inc(n),
+            Display::Hidden => {}
+        }
+    }
+
+    /// done. the same as dropping the progress.
+    pub fn finish(self) {}
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        match &self.0 {
+            Display::Bar(bar) => {
+                bar.finish_and_clear();
+                BARS.remove(bar);
+            }
+            Display::Lines(lines) => lines.finish(),
+            Display::Hidden => {}
+        }
+    }
+}
+
+/// progress as a line every `LINE_INTERVAL`, so operations that finish quickly don't log at all.
+struct Lines {
+    message: String,
+    len: Option<u64>,
+    position: AtomicU64,
+    start: Instant,
+    last_line: Mutex<Instant>,
+    logged: AtomicBool,
+}
+
+impl Lines {
+    fn new(len: Option<u64>, message: &str) -> Self {
+        let start = Instant::now();
+
+        Lines {
+            message: message.to_owned(),
+            len,
+            position: AtomicU64::new(0),
+            start,
+            last_line: Mutex::new(start),
+            logged: AtomicBool::new(false),
+        }
+    }
+
+    fn inc(&self, n: u64) {
+        let position = self.position.fetch_add(n, Ordering::Relaxed) + n;
+
+        let mut last_line = self.last_line.lock().unwrap_or_else(|e| e.into_inner());
+
+        if last_line.elapsed() >= LINE_INTERVAL {
+            *last_line = Instant::now();
+
+            self.logged.store(true, Ordering::Relaxed);
+
+            log::info!("⏳ {} {}", self.message, self.count(position));
+        }
+    }
+
+    fn finish(&self) {
+        if !self.logged.load(Ordering::Relaxed) {
+            return;
+        }
+
+        log::info!(
+            "⌛ {} {} in {:.1}s",
+            self.message,
+            self.count(self.position.load(Ordering::Relaxed)),
+            self.start.elapsed().as_secs_f64()
+        );
+    }
+
+    fn count(&self, position: u64) -> String {
+        match self.len {
+            Some(len) => format!("{position}/{len}"),
+            None => position.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_progress {
+    use super::{Lines, Progress};
+
+    use std::sync::atomic::Ordering;
+
+    #[test]
+    fn test_lines_count() {
+        let lines = Lines::new(Some(30), "📡 fetching eras");
+
+        lines.inc(4);
+        lines.inc(3);
+
+        assert_eq!(lines.count(lines.position.load(Ordering::Relaxed)), "7/30");
+        assert_eq!(Lines::new(None, "🎯 optimizing").count(12), "12");
+    }
+
+    #[test]
+    fn test_hidden_without_logger() {
+        let progress = Progress::new(3, "🧮 rating games");
+
+        progress.inc(3);
+        progress.finish();
+    }
+}
@@ -135,3 +135,16 @@ pub fn prompt_and_delete(comparator: &str) -> bool {
         }
     }
 }
+
+pub fn prompt_optional(prompt: &str) -> Option<String> {
+    let input = Input::<String>::with_theme(&ColorfulTheme::default())
+        .with_prompt(prompt)
+        .allow_empty(true)
+        .interact_text()
+        .unwrap();
+
+    match input.trim() {
+        "" => None,
+        s => Some(s.to_owned()),
+    }
+}
@@ -175,7 +175,7 @@ impl TryFrom<&Value> for SeasonId {
         let s = match value.as_str() {
             Some(s) => s,
             None => {
-                eprintln!("⚠️ SeasonId is not a JSON String.");
+                log::warn!("⚠️ SeasonId is not a JSON String.");
 
                 return Err(());
             }
@@ -184,7 +184,7 @@ impl TryFrom<&Value> for SeasonId {
         match s.parse::<i32>() {
             Ok(x) => Ok(SeasonId::from(x)),
             Err(e) => {
-                eprintln!("⚠️ failed to parse an integer from the SeasonId field: {e}");
+                log::warn!("⚠️ failed to parse an integer from the SeasonId field: {e}");
 
                 Err(())
             }